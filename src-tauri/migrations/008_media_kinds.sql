@@ -0,0 +1,21 @@
+CREATE TABLE IF NOT EXISTS media_kinds (
+    id TEXT PRIMARY KEY,
+    label TEXT NOT NULL,
+    metadata_lookup INTEGER NOT NULL DEFAULT 1,
+    sort_order INTEGER NOT NULL DEFAULT 0
+);
+
+INSERT OR IGNORE INTO media_kinds (id, label, metadata_lookup, sort_order) VALUES
+    ('movie', 'Film', 1, 0),
+    ('series', 'Serie', 1, 1),
+    ('documentary', 'Dokumentation', 1, 2),
+    ('anime', 'Anime', 1, 3),
+    ('concert', 'Konzert', 1, 4),
+    ('standup', 'Stand-up', 1, 5),
+    ('home_video', 'Heimvideo', 0, 6);
+
+ALTER TABLE media_items ADD COLUMN media_kind TEXT REFERENCES media_kinds(id);
+
+UPDATE media_items SET media_kind = media_type WHERE media_kind IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_media_items_kind ON media_items(media_kind);
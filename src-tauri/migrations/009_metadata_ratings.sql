@@ -0,0 +1,7 @@
+ALTER TABLE metadata ADD COLUMN ratings TEXT;
+
+ALTER TABLE metadata ADD COLUMN field_sources TEXT;
+
+UPDATE metadata
+SET ratings = json_array(json_object('source', 'tmdb', 'value', rating, 'display', printf('%.1f', rating), 'votes', NULL))
+WHERE rating IS NOT NULL AND ratings IS NULL;
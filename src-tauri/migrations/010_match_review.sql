@@ -0,0 +1,21 @@
+ALTER TABLE metadata ADD COLUMN match_confidence REAL;
+
+ALTER TABLE metadata ADD COLUMN match_status TEXT;
+
+CREATE TABLE IF NOT EXISTS match_candidates (
+    media_item_id TEXT NOT NULL,
+    provider TEXT NOT NULL,
+    provider_id INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    overview TEXT,
+    poster_url TEXT,
+    release_date TEXT,
+    media_type TEXT NOT NULL,
+    score REAL NOT NULL,
+    rank INTEGER NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (media_item_id, provider, provider_id),
+    FOREIGN KEY (media_item_id) REFERENCES media_items(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_metadata_match_status ON metadata(match_status);
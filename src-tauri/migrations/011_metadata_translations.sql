@@ -0,0 +1,12 @@
+ALTER TABLE metadata ADD COLUMN language TEXT;
+
+CREATE TABLE IF NOT EXISTS metadata_translations (
+    media_item_id TEXT NOT NULL,
+    language TEXT NOT NULL,
+    title TEXT,
+    overview TEXT,
+    tagline TEXT,
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (media_item_id, language),
+    FOREIGN KEY (media_item_id) REFERENCES media_items(id) ON DELETE CASCADE
+);
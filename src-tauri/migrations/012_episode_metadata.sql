@@ -0,0 +1,19 @@
+CREATE TABLE IF NOT EXISTS episode_metadata (
+    media_item_id TEXT PRIMARY KEY,
+    tmdb_show_id INTEGER NOT NULL,
+    season_number INTEGER NOT NULL,
+    episode_number INTEGER NOT NULL,
+    tmdb_episode_id INTEGER,
+    name TEXT,
+    overview TEXT,
+    air_date TEXT,
+    still_url TEXT,
+    runtime INTEGER,
+    rating REAL,
+    guest_stars TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (media_item_id) REFERENCES media_items(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_episode_metadata_show ON episode_metadata(tmdb_show_id, season_number, episode_number);
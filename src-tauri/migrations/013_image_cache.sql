@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS image_cache (
+    url TEXT PRIMARY KEY,
+    file_path TEXT NOT NULL,
+    bytes INTEGER NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    last_accessed TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_image_cache_last_accessed ON image_cache(last_accessed);
@@ -0,0 +1,9 @@
+ALTER TABLE metadata ADD COLUMN locked_fields TEXT;
+
+CREATE TABLE IF NOT EXISTS metadata_refresh_log (
+    media_item_id TEXT PRIMARY KEY REFERENCES media_items(id) ON DELETE CASCADE,
+    attempted_at TEXT NOT NULL DEFAULT (datetime('now')),
+    error TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_metadata_updated_at ON metadata(updated_at);
@@ -0,0 +1,13 @@
+ALTER TABLE metadata ADD COLUMN imdb_id TEXT;
+ALTER TABLE metadata ADD COLUMN tvdb_id INTEGER;
+ALTER TABLE metadata ADD COLUMN wikidata_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_metadata_imdb_id ON metadata(imdb_id);
+CREATE INDEX IF NOT EXISTS idx_metadata_tvdb_id ON metadata(tvdb_id);
+CREATE INDEX IF NOT EXISTS idx_metadata_tmdb_id ON metadata(tmdb_id);
+
+UPDATE metadata
+SET imdb_id = CASE WHEN json_valid(raw_response)
+    THEN COALESCE(json_extract(raw_response, '$.imdb_id'), json_extract(raw_response, '$.imdbID'))
+    END
+WHERE imdb_id IS NULL AND raw_response IS NOT NULL AND raw_response != ''
@@ -0,0 +1,43 @@
+CREATE TABLE IF NOT EXISTS media_extras (
+    tmdb_id INTEGER NOT NULL,
+    media_type TEXT NOT NULL,
+    tagline TEXT,
+    fetched_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (tmdb_id, media_type)
+);
+
+CREATE TABLE IF NOT EXISTS media_videos (
+    tmdb_id INTEGER NOT NULL,
+    media_type TEXT NOT NULL,
+    site TEXT NOT NULL,
+    video_key TEXT NOT NULL,
+    name TEXT,
+    video_type TEXT,
+    language TEXT,
+    official INTEGER NOT NULL DEFAULT 0,
+    published_at TEXT,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (tmdb_id, media_type, site, video_key),
+    FOREIGN KEY (tmdb_id, media_type) REFERENCES media_extras(tmdb_id, media_type) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS media_keywords (
+    tmdb_id INTEGER NOT NULL,
+    media_type TEXT NOT NULL,
+    keyword_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    PRIMARY KEY (tmdb_id, media_type, keyword_id),
+    FOREIGN KEY (tmdb_id, media_type) REFERENCES media_extras(tmdb_id, media_type) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS media_certifications (
+    tmdb_id INTEGER NOT NULL,
+    media_type TEXT NOT NULL,
+    country TEXT NOT NULL,
+    certification TEXT NOT NULL,
+    release_date TEXT,
+    PRIMARY KEY (tmdb_id, media_type, country),
+    FOREIGN KEY (tmdb_id, media_type) REFERENCES media_extras(tmdb_id, media_type) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_media_keywords_keyword ON media_keywords(keyword_id);
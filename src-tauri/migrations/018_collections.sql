@@ -0,0 +1,31 @@
+ALTER TABLE metadata ADD COLUMN collection_id INTEGER;
+
+CREATE INDEX IF NOT EXISTS idx_metadata_collection_id ON metadata(collection_id);
+
+UPDATE metadata
+SET collection_id = CASE WHEN json_valid(raw_response)
+    THEN json_extract(raw_response, '$.belongs_to_collection.id')
+    END
+WHERE raw_response IS NOT NULL AND raw_response != '';
+
+CREATE TABLE IF NOT EXISTS collections (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    overview TEXT,
+    poster_url TEXT,
+    backdrop_url TEXT,
+    fetched_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS collection_parts (
+    collection_id INTEGER NOT NULL REFERENCES collections(id) ON DELETE CASCADE,
+    tmdb_id INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    overview TEXT,
+    poster_url TEXT,
+    release_date TEXT,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (collection_id, tmdb_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_collection_parts_tmdb_id ON collection_parts(tmdb_id);
@@ -0,0 +1,28 @@
+CREATE TABLE match_candidates_new (
+    media_item_id TEXT NOT NULL,
+    provider TEXT NOT NULL,
+    provider_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    overview TEXT,
+    poster_url TEXT,
+    release_date TEXT,
+    media_type TEXT NOT NULL,
+    score REAL NOT NULL,
+    rank INTEGER NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (media_item_id, provider, provider_id),
+    FOREIGN KEY (media_item_id) REFERENCES media_items(id) ON DELETE CASCADE
+);
+
+INSERT INTO match_candidates_new
+    (media_item_id, provider, provider_id, title, overview, poster_url, release_date,
+     media_type, score, rank, created_at)
+SELECT media_item_id, provider,
+       CASE WHEN provider = 'omdb' THEN 'tt' || printf('%07d', provider_id)
+            ELSE CAST(provider_id AS TEXT) END,
+       title, overview, poster_url, release_date, media_type, score, rank, created_at
+FROM match_candidates;
+
+DROP TABLE match_candidates;
+
+ALTER TABLE match_candidates_new RENAME TO match_candidates;
@@ -0,0 +1,16 @@
+CREATE TABLE IF NOT EXISTS watchlist (
+    tmdb_id INTEGER NOT NULL,
+    media_type TEXT NOT NULL CHECK(media_type IN ('movie', 'tv')),
+    title TEXT NOT NULL,
+    year INTEGER,
+    poster_url TEXT,
+    added_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (tmdb_id, media_type)
+);
+
+CREATE TABLE IF NOT EXISTS watched (
+    tmdb_id INTEGER NOT NULL,
+    media_type TEXT NOT NULL CHECK(media_type IN ('movie', 'tv')),
+    watched_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (tmdb_id, media_type)
+);
@@ -0,0 +1,54 @@
+CREATE TABLE IF NOT EXISTS people (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    profile_url TEXT
+);
+
+CREATE TABLE IF NOT EXISTS media_credits (
+    media_item_id TEXT NOT NULL REFERENCES media_items(id) ON DELETE CASCADE,
+    person_id INTEGER NOT NULL REFERENCES people(id) ON DELETE CASCADE,
+    department TEXT NOT NULL,
+    role TEXT NOT NULL,
+    character TEXT,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (media_item_id, person_id, department, role)
+);
+
+CREATE INDEX IF NOT EXISTS idx_media_credits_person ON media_credits(person_id);
+
+CREATE INDEX IF NOT EXISTS idx_people_name ON people(name);
+
+INSERT INTO people (id, name, profile_url)
+SELECT json_extract(p.value, '$.id'), json_extract(p.value, '$.name'), json_extract(p.value, '$.profile_url')
+FROM metadata meta
+JOIN media_items m ON m.id = meta.media_item_id,
+     json_each(CASE WHEN json_valid(meta.cast_crew) THEN meta.cast_crew END, '$.cast') p
+WHERE json_type(p.value, '$.id') = 'integer' AND json_type(p.value, '$.name') = 'text'
+ON CONFLICT(id) DO UPDATE SET name = excluded.name,
+    profile_url = COALESCE(excluded.profile_url, people.profile_url);
+
+INSERT INTO people (id, name, profile_url)
+SELECT json_extract(p.value, '$.id'), json_extract(p.value, '$.name'), json_extract(p.value, '$.profile_url')
+FROM metadata meta
+JOIN media_items m ON m.id = meta.media_item_id,
+     json_each(CASE WHEN json_valid(meta.cast_crew) THEN meta.cast_crew END, '$.crew') p
+WHERE json_type(p.value, '$.id') = 'integer' AND json_type(p.value, '$.name') = 'text'
+ON CONFLICT(id) DO UPDATE SET name = excluded.name,
+    profile_url = COALESCE(excluded.profile_url, people.profile_url);
+
+INSERT OR IGNORE INTO media_credits (media_item_id, person_id, department, role, character, sort_order)
+SELECT meta.media_item_id, json_extract(p.value, '$.id'), 'Acting', 'Actor',
+       json_extract(p.value, '$.character'), p.key
+FROM metadata meta
+JOIN media_items m ON m.id = meta.media_item_id,
+     json_each(CASE WHEN json_valid(meta.cast_crew) THEN meta.cast_crew END, '$.cast') p
+WHERE json_type(p.value, '$.id') = 'integer' AND json_type(p.value, '$.name') = 'text';
+
+INSERT OR IGNORE INTO media_credits (media_item_id, person_id, department, role, character, sort_order)
+SELECT meta.media_item_id, json_extract(p.value, '$.id'),
+       COALESCE(json_extract(p.value, '$.department'), 'Crew'), COALESCE(json_extract(p.value, '$.job'), 'Crew'),
+       NULL, p.key
+FROM metadata meta
+JOIN media_items m ON m.id = meta.media_item_id,
+     json_each(CASE WHEN json_valid(meta.cast_crew) THEN meta.cast_crew END, '$.crew') p
+WHERE json_type(p.value, '$.id') = 'integer' AND json_type(p.value, '$.name') = 'text'
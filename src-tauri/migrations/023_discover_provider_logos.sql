@@ -0,0 +1 @@
+ALTER TABLE discover_lists ADD COLUMN provider_logo TEXT;
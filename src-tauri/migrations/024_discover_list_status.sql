@@ -0,0 +1,9 @@
+ALTER TABLE discover_lists ADD COLUMN list_key TEXT;
+ALTER TABLE discover_lists ADD COLUMN sort_order INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE discover_lists ADD COLUMN last_error TEXT;
+UPDATE discover_lists SET sort_order = rowid, list_key = CASE
+    WHEN list_type = 'imdb_top' THEN 'imdb_top'
+    WHEN title LIKE 'Top Serien%' THEN 'tv:' || provider
+    ELSE 'movie:' || provider
+END;
+CREATE INDEX IF NOT EXISTS idx_discover_lists_key ON discover_lists(list_key);
@@ -0,0 +1,30 @@
+PRAGMA foreign_keys = OFF;
+
+CREATE TABLE discover_lists_new (
+    id TEXT PRIMARY KEY,
+    list_type TEXT NOT NULL CHECK(list_type IN (
+        'imdb_top', 'streaming_popular', 'trending_day', 'trending_week',
+        'now_playing', 'upcoming', 'airing_week', 'top_rated_tv', 'custom'
+    )),
+    provider TEXT,
+    country TEXT,
+    title TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    provider_logo TEXT,
+    list_key TEXT,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT
+);
+
+INSERT INTO discover_lists_new
+    (id, list_type, provider, country, title, created_at, provider_logo, list_key, sort_order, last_error)
+SELECT id, list_type, provider, country, title, created_at, provider_logo, list_key, sort_order, last_error
+FROM discover_lists;
+
+DROP TABLE discover_lists;
+
+ALTER TABLE discover_lists_new RENAME TO discover_lists;
+
+CREATE INDEX IF NOT EXISTS idx_discover_lists_key ON discover_lists(list_key);
+
+PRAGMA foreign_keys = ON;
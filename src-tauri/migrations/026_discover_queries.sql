@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS discover_queries (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    query_json TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
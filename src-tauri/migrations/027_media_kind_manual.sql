@@ -0,0 +1 @@
+ALTER TABLE media_items ADD COLUMN media_kind_manual INTEGER NOT NULL DEFAULT 0;
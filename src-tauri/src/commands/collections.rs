@@ -0,0 +1,161 @@
+//! Filmreihen (TMDb-Collections) und Vollständigkeit der Bibliothek.
+
+use std::collections::HashMap;
+
+use tauri::State;
+
+use crate::commands::rd_streams::check_stream_availability;
+use crate::db::DbPool;
+use crate::services::language::LanguagePrefs;
+use crate::services::parental::KidsFilter;
+use crate::services::storage;
+use crate::services::tmdb_api::{self, CollectionPart};
+
+/// Danach werden Filmreihen neu geladen (neue Teile, geänderte Termine).
+const COLLECTION_MAX_AGE_DAYS: i64 = 30;
+
+#[derive(Debug, serde::Serialize)]
+pub struct CollectionMovie {
+    pub tmdb_id: i64,
+    pub title: String,
+    pub year: Option<i32>,
+    pub release_date: Option<String>,
+    pub poster_url: Option<String>,
+    /// Bibliothekseintrag, falls der Film vorhanden ist.
+    pub media_item_id: Option<String>,
+    /// Nur für fehlende, bereits erschienene Filme und nur mit `check_streams`;
+    /// `None`, wenn nicht geprüft oder die Suche fehlschlug.
+    pub has_streams: Option<bool>,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct CollectionCompleteness {
+    pub id: i64,
+    pub name: String,
+    pub overview: Option<String>,
+    pub poster_url: Option<String>,
+    pub backdrop_url: Option<String>,
+    pub owned: Vec<CollectionMovie>,
+    pub missing: Vec<CollectionMovie>,
+    /// Noch nicht erschienene Teile; zählen nicht als fehlend.
+    pub upcoming: Vec<CollectionMovie>,
+}
+
+/// Lädt Filmreihen der Bibliotheksfilme von TMDb. Liefert die Zahl der geladenen Reihen.
+pub(crate) async fn sync_collections(
+    pool: &DbPool,
+    settings: &HashMap<String, String>,
+    max_age_days: Option<i64>,
+) -> Result<u32, String> {
+    let ids = storage::get_collection_ids_to_fetch(pool, max_age_days).await?;
+    if ids.is_empty() {
+        return Ok(0);
+    }
+    let api_key = settings
+        .get("tmdb_api_key")
+        .filter(|k| !k.is_empty())
+        .ok_or("Kein TMDb API-Key konfiguriert.")?;
+    let languages = LanguagePrefs::from_settings(settings);
+
+    let mut fetched = 0;
+    for id in ids {
+        match tmdb_api::fetch_collection(api_key, &languages, id).await {
+            Ok(Some(collection)) => {
+                storage::replace_collection(pool, &collection).await?;
+                fetched += 1;
+            }
+            Ok(None) => log::info!("TMDb collection {} not found", id),
+            Err(e) => log::warn!("Fetching TMDb collection {} failed: {}", id, e),
+        }
+    }
+    Ok(fetched)
+}
+
+/// Lädt die Filmreihen aller Bibliotheksfilme neu (`force`) oder nur fehlende und veraltete.
+#[tauri::command]
+pub async fn refresh_collections(pool: State<'_, DbPool>, force: Option<bool>) -> Result<u32, String> {
+    let settings = storage::load_all_settings(&pool).await?;
+    let max_age = if force.unwrap_or(false) { None } else { Some(COLLECTION_MAX_AGE_DAYS) };
+    sync_collections(&pool, &settings, max_age).await
+}
+
+/// Lädt fehlende und veraltete Filmreihen; für die Hintergrund-Aktualisierung.
+pub(crate) async fn refresh_stale_collections(pool: &DbPool) -> Result<u32, String> {
+    let settings = storage::load_all_settings(pool).await?;
+    sync_collections(pool, &settings, Some(COLLECTION_MAX_AGE_DAYS)).await
+}
+
+/// Welche Filme jeder Filmreihe vorhanden sind und welche fehlen, aus den
+/// gespeicherten Filmreihen. Mit `check_streams` wird für fehlende Filme geprüft,
+/// ob Debridio oder Torrentio Streams kennen.
+#[tauri::command]
+pub async fn get_collection_completeness(
+    pool: State<'_, DbPool>,
+    check_streams: Option<bool>,
+) -> Result<Vec<CollectionCompleteness>, String> {
+    let settings = storage::load_all_settings(&pool).await?;
+    let owned_ids = storage::get_owned_movie_ids(&pool).await?;
+    let kids = KidsFilter::load(&pool, &settings).await?;
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let check_streams = check_streams.unwrap_or(false);
+
+    let mut report = Vec::new();
+    for collection in storage::get_library_collections(&pool).await? {
+        let mut entry = CollectionCompleteness {
+            id: collection.id,
+            name: collection.name,
+            overview: collection.overview,
+            poster_url: collection.poster_url,
+            backdrop_url: collection.backdrop_url,
+            owned: Vec::new(),
+            missing: Vec::new(),
+            upcoming: Vec::new(),
+        };
+        for part in collection.parts {
+            if kids.as_ref().is_some_and(|k| !k.allows(Some(part.tmdb_id), "movie")) {
+                continue;
+            }
+            let released = part.release_date.as_deref().is_some_and(|d| d <= today.as_str());
+            let media_item_id = owned_ids.get(&part.tmdb_id).cloned();
+            let movie = collection_movie(part, media_item_id.clone());
+            if media_item_id.is_some() {
+                entry.owned.push(movie);
+            } else if released {
+                entry.missing.push(movie);
+            } else {
+                entry.upcoming.push(movie);
+            }
+        }
+        if !entry.owned.is_empty() {
+            report.push(entry);
+        }
+    }
+
+    if check_streams {
+        let missing: Vec<(i64, String)> = report
+            .iter()
+            .flat_map(|c| c.missing.iter().map(|m| (m.tmdb_id, "movie".to_string())))
+            .collect();
+        let available = check_stream_availability(&pool, &missing).await?;
+        for movie in report.iter_mut().flat_map(|c| c.missing.iter_mut()) {
+            movie.has_streams = available.get(&(movie.tmdb_id, "movie".to_string())).copied();
+        }
+    }
+    Ok(report)
+}
+
+fn collection_movie(part: CollectionPart, media_item_id: Option<String>) -> CollectionMovie {
+    CollectionMovie {
+        year: part
+            .release_date
+            .as_deref()
+            .and_then(|d| d.get(..4))
+            .and_then(|y| y.parse().ok()),
+        tmdb_id: part.tmdb_id,
+        title: part.title,
+        release_date: part.release_date,
+        poster_url: part.poster_url,
+        media_item_id,
+        has_streams: None,
+    }
+}
@@ -1,7 +1,17 @@
+use crate::commands::rd_streams::check_stream_availability;
 use crate::db::DbPool;
-use crate::services::discovery::{self, StreamSearchResult, TmdbGenre};
+use crate::services::discovery::{
+    self, DiscoverQuery, DiscoverQueryPage, DiscoveryProvider, SavedDiscoverQuery, SearchPage,
+    StreamSearchResult, TmdbGenre,
+};
+use crate::services::language::LanguagePrefs;
+use crate::services::media_kind::tmdb_media_type;
+use crate::services::parental::KidsFilter;
 use crate::services::perplexity;
 use crate::services::storage::{self, DiscoverItemRow, DiscoverList};
+use crate::services::tmdb_http::CacheMode;
+use crate::services::tmdb_api::{self, SimilarTitle};
+use std::collections::{HashMap, HashSet};
 use tauri::State;
 
 #[tauri::command]
@@ -10,6 +20,8 @@ pub async fn search_streams(
     query: String,
     media_type: String,
     genre_id: Option<i64>,
+    hide_owned: Option<bool>,
+    hide_watched: Option<bool>,
 ) -> Result<Vec<StreamSearchResult>, String> {
     let settings = storage::load_all_settings(&pool).await?;
     let api_key = settings
@@ -18,11 +30,33 @@ pub async fn search_streams(
         .ok_or("Kein TMDb API-Key konfiguriert. Bitte in den Einstellungen setzen.")?
         .clone();
 
-    discovery::search_streams(&api_key, &query, &media_type, genre_id).await
+    let languages = LanguagePrefs::from_settings(&settings);
+    let mut results =
+        discovery::search_streams(&api_key, languages.primary(), &query, &media_type, genre_id).await?;
+
+    let titles: Vec<(i64, String)> = results.iter().map(|r| (r.tmdb_id, r.media_type.clone())).collect();
+    if let Some(filter) = KidsFilter::prepare(&pool, &settings, &titles).await? {
+        results.retain(|r| filter.allows(Some(r.tmdb_id), &r.media_type));
+    }
+    let hide = HideOptions::new(hide_owned, hide_watched);
+    if hide.any() {
+        let status = TitleStatus::load(&pool).await?;
+        results.retain(|r| !status.hidden(r.tmdb_id, &r.media_type, hide));
+    }
+    Ok(results)
 }
 
+/// Seitenweise Suche über Filme, Serien, Personen und Sammlungen; `types` leer = alle.
+/// Ein angehängtes Jahr ("Dune 1984") filtert Filme und Serien.
 #[tauri::command]
-pub async fn get_tmdb_genres(pool: State<'_, DbPool>) -> Result<Vec<TmdbGenre>, String> {
+pub async fn search_tmdb(
+    pool: State<'_, DbPool>,
+    query: String,
+    types: Option<Vec<String>>,
+    page: Option<u32>,
+    hide_owned: Option<bool>,
+    hide_watched: Option<bool>,
+) -> Result<SearchPage, String> {
     let settings = storage::load_all_settings(&pool).await?;
     let api_key = settings
         .get("tmdb_api_key")
@@ -30,14 +64,45 @@ pub async fn get_tmdb_genres(pool: State<'_, DbPool>) -> Result<Vec<TmdbGenre>,
         .ok_or("Kein TMDb API-Key konfiguriert. Bitte in den Einstellungen setzen.")?
         .clone();
 
-    discovery::get_tmdb_genres(&api_key).await
+    let languages = LanguagePrefs::from_settings(&settings);
+    let mut result = discovery::search_tmdb(
+        &api_key,
+        languages.primary(),
+        &query,
+        &types.unwrap_or_default(),
+        page.unwrap_or(1),
+    )
+    .await?;
+
+    // Freigaben und Bibliothek betreffen nur Filme und Serien.
+    let is_title = |media_type: &str| media_type == "movie" || media_type == "tv";
+    let titles: Vec<(i64, String)> = result
+        .results
+        .iter()
+        .filter(|r| is_title(&r.media_type))
+        .map(|r| (r.tmdb_id, r.media_type.clone()))
+        .collect();
+    if let Some(filter) = KidsFilter::prepare(&pool, &settings, &titles).await? {
+        result.results.retain(|r| !is_title(&r.media_type) || filter.allows(Some(r.tmdb_id), &r.media_type));
+    }
+    let hide = HideOptions::new(hide_owned, hide_watched);
+    if hide.any() {
+        let status = TitleStatus::load(&pool).await?;
+        result.results.retain(|r| !is_title(&r.media_type) || !status.hidden(r.tmdb_id, &r.media_type, hide));
+    }
+    Ok(result)
 }
 
+/// TMDb-`/discover` mit Filtern, seitenweise.
 #[tauri::command]
-pub async fn refresh_discover_lists(
+pub async fn discover_query(
     pool: State<'_, DbPool>,
+    query: DiscoverQuery,
+    page: Option<u32>,
     country: Option<String>,
-) -> Result<Vec<DiscoverList>, String> {
+    hide_owned: Option<bool>,
+    hide_watched: Option<bool>,
+) -> Result<DiscoverQueryPage, String> {
     let settings = storage::load_all_settings(&pool).await?;
     let api_key = settings
         .get("tmdb_api_key")
@@ -45,56 +110,260 @@ pub async fn refresh_discover_lists(
         .ok_or("Kein TMDb API-Key konfiguriert. Bitte in den Einstellungen setzen.")?
         .clone();
 
-    let country = country
+    let country = discover_country(&settings, country);
+    let languages = LanguagePrefs::from_settings(&settings);
+    let mut result =
+        discovery::discover_query(&api_key, languages.primary(), &country, &query, page.unwrap_or(1)).await?;
+
+    let titles: Vec<(i64, String)> = result.results.iter().map(|r| (r.tmdb_id, r.media_type.clone())).collect();
+    if let Some(filter) = KidsFilter::prepare(&pool, &settings, &titles).await? {
+        result.results.retain(|r| filter.allows(Some(r.tmdb_id), &r.media_type));
+    }
+    let hide = HideOptions::new(hide_owned, hide_watched);
+    if hide.any() {
+        let status = TitleStatus::load(&pool).await?;
+        result.results.retain(|r| !status.hidden(r.tmdb_id, &r.media_type, hide));
+    }
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn get_tmdb_genres(pool: State<'_, DbPool>) -> Result<Vec<TmdbGenre>, String> {
+    let settings = storage::load_all_settings(&pool).await?;
+    let api_key = settings
+        .get("tmdb_api_key")
+        .filter(|k| !k.is_empty())
+        .ok_or("Kein TMDb API-Key konfiguriert. Bitte in den Einstellungen setzen.")?
+        .clone();
+
+    let languages = LanguagePrefs::from_settings(&settings);
+    discovery::get_tmdb_genres(&api_key, languages.primary()).await
+}
+
+fn discover_country(settings: &HashMap<String, String>, country: Option<String>) -> String {
+    country
         .or_else(|| settings.get("discover_country").cloned())
-        .unwrap_or_else(|| "CH".to_string());
+        .filter(|c| !c.trim().is_empty())
+        .unwrap_or_else(|| "CH".to_string())
+}
 
-    let lists = discovery::fetch_all_lists(&api_key, &country).await?;
+#[derive(Debug, serde::Serialize)]
+pub struct WatchProviderOption {
+    pub tmdb_provider_id: u32,
+    pub name: String,
+    pub logo_url: Option<String>,
+    /// In `discover_providers` gewählt (bzw. Standardauswahl).
+    pub selected: bool,
+}
 
-    storage::clear_discover_data(&pool).await?;
+/// Streaming-Anbieter im Discover-Land (oder `country`) mit der aktuellen Auswahl.
+#[tauri::command]
+pub async fn get_watch_providers(
+    pool: State<'_, DbPool>,
+    country: Option<String>,
+) -> Result<Vec<WatchProviderOption>, String> {
+    let settings = storage::load_all_settings(&pool).await?;
+    let api_key = settings
+        .get("tmdb_api_key")
+        .filter(|k| !k.is_empty())
+        .ok_or("Kein TMDb API-Key konfiguriert. Bitte in den Einstellungen setzen.")?;
+    let country = discover_country(&settings, country);
+    let languages = LanguagePrefs::from_settings(&settings);
+    let selected = discovery::selected_provider_ids(settings.get("discover_providers").map(|s| s.as_str()));
+    Ok(discovery::fetch_watch_providers(api_key, languages.primary(), &country)
+        .await?
+        .into_iter()
+        .map(|p| WatchProviderOption {
+            selected: selected.contains(&p.tmdb_provider_id),
+            tmdb_provider_id: p.tmdb_provider_id,
+            name: p.name,
+            logo_url: p.logo_url,
+        })
+        .collect())
+}
 
-    for list in &lists {
-        let list_id = uuid::Uuid::new_v4().to_string();
+#[tauri::command]
+pub async fn refresh_discover_lists(
+    pool: State<'_, DbPool>,
+    country: Option<String>,
+    hide_owned: Option<bool>,
+    hide_watched: Option<bool>,
+) -> Result<Vec<DiscoverList>, String> {
+    let settings = storage::load_all_settings(&pool).await?;
+    let api_key = settings
+        .get("tmdb_api_key")
+        .filter(|k| !k.is_empty())
+        .ok_or("Kein TMDb API-Key konfiguriert. Bitte in den Einstellungen setzen.")?
+        .clone();
 
-        storage::insert_discover_list(
-            &pool,
-            &list_id,
-            &list.list_type,
-            list.provider.as_deref(),
-            &list.country,
-            &list.title,
-        )
-        .await?;
-
-        for (i, item) in list.items.iter().enumerate() {
-            storage::insert_discover_item(
-                &pool,
-                &uuid::Uuid::new_v4().to_string(),
-                &list_id,
-                &item.media_type,
-                None,
-                Some(item.tmdb_id),
-                &item.title,
-                item.year,
-                item.rating,
-                item.poster_url.as_deref(),
-                Some(&item.overview),
-                list.provider.as_deref(),
-                &item.raw_json,
-                i as i32,
-            )
-            .await?;
-        }
-    }
+    let country = discover_country(&settings, country);
+    let languages = LanguagePrefs::from_settings(&settings);
+    let selected = discovery::selected_provider_ids(settings.get("discover_providers").map(|s| s.as_str()));
+    let providers: Vec<DiscoveryProvider> =
+        match discovery::fetch_watch_providers(&api_key, languages.primary(), &country).await {
+            Ok(all) => all.into_iter().filter(|p| selected.contains(&p.tmdb_provider_id)).collect(),
+            Err(e) => {
+                log::warn!("Watch providers for {} unavailable, using stored selection: {}", country, e);
+                let logos: HashMap<u32, String> = storage::get_discover_lists(&pool)
+                    .await?
+                    .into_iter()
+                    .filter_map(|l| Some((l.provider?.parse().ok()?, l.provider_logo?)))
+                    .collect();
+                discovery::fallback_providers(&selected, &logos)
+            }
+        };
+    let queries = storage::get_discover_queries(&pool).await?;
+    // Manuelles Aktualisieren holt die Listen frisch, statt aus `http_cache`.
+    let lists = discovery::fetch_all_lists(
+        &api_key,
+        languages.primary(),
+        &country,
+        &providers,
+        &queries,
+        CacheMode::Refresh,
+    )
+    .await;
+    storage::replace_discover_lists(&pool, &lists).await?;
 
-    storage::get_discover_lists(&pool).await
+    // Auch die Einträge beibehaltener Listen, falls deren Freigaben noch fehlen.
+    let stored = storage::get_discover_lists(&pool).await?;
+    let titles: Vec<(i64, String)> = stored
+        .iter()
+        .flat_map(|l| l.items.iter().filter_map(|i| Some((i.tmdb_id?, i.media_type.clone()))))
+        .collect();
+    let filter = KidsFilter::prepare(&pool, &settings, &titles).await?;
+    let hide = HideOptions::new(hide_owned, hide_watched);
+    annotate_lists(&pool, filter_lists(stored, filter.as_ref()), hide).await
 }
 
 #[tauri::command]
 pub async fn get_discover_lists(
     pool: State<'_, DbPool>,
+    hide_owned: Option<bool>,
+    hide_watched: Option<bool>,
 ) -> Result<Vec<DiscoverList>, String> {
-    storage::get_discover_lists(&pool).await
+    let settings = storage::load_all_settings(&pool).await?;
+    let filter = KidsFilter::load(&pool, &settings).await?;
+    let lists = filter_lists(storage::get_discover_lists(&pool).await?, filter.as_ref());
+    annotate_lists(&pool, lists, HideOptions::new(hide_owned, hide_watched)).await
+}
+
+/// Speichert eine Abfrage als eigene Discover-Liste; sie erscheint nach dem nächsten Aktualisieren.
+/// Mit `id` wird eine bestehende Abfrage überschrieben.
+#[tauri::command]
+pub async fn save_discover_query(
+    pool: State<'_, DbPool>,
+    id: Option<String>,
+    name: String,
+    query: DiscoverQuery,
+) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Name der Abfrage fehlt.".to_string());
+    }
+    query.validate()?;
+    let id = id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    storage::save_discover_query(&pool, &id, name, &query).await?;
+    Ok(id)
+}
+
+#[tauri::command]
+pub async fn get_discover_queries(pool: State<'_, DbPool>) -> Result<Vec<SavedDiscoverQuery>, String> {
+    storage::get_discover_queries(&pool).await
+}
+
+#[tauri::command]
+pub async fn delete_discover_query(pool: State<'_, DbPool>, id: String) -> Result<(), String> {
+    storage::delete_discover_query(&pool, &id).await
+}
+
+/// Titel ausblenden, die schon in der Bibliothek liegen bzw. gesehen wurden.
+#[derive(Debug, Clone, Copy)]
+struct HideOptions {
+    owned: bool,
+    watched: bool,
+}
+
+impl HideOptions {
+    fn new(owned: Option<bool>, watched: Option<bool>) -> Self {
+        Self { owned: owned.unwrap_or(false), watched: watched.unwrap_or(false) }
+    }
+
+    fn any(self) -> bool {
+        self.owned || self.watched
+    }
+}
+
+/// Bibliothek (über `metadata.tmdb_id`), Watchlist und Gesehen je `(tmdb_id, "movie"|"tv")`.
+struct TitleStatus {
+    library: HashMap<(i64, String), String>,
+    watchlist: HashSet<(i64, String)>,
+    watched: HashSet<(i64, String)>,
+}
+
+impl TitleStatus {
+    async fn load(pool: &DbPool) -> Result<Self, String> {
+        Ok(Self {
+            library: storage::get_library_tmdb_items(pool).await?,
+            watchlist: storage::get_watchlist_keys(pool).await?,
+            watched: storage::get_watched_keys(pool).await?,
+        })
+    }
+
+    fn key(tmdb_id: i64, media_type: &str) -> (i64, String) {
+        (tmdb_id, tmdb_media_type(media_type).to_string())
+    }
+
+    fn hidden(&self, tmdb_id: i64, media_type: &str, hide: HideOptions) -> bool {
+        let key = Self::key(tmdb_id, media_type);
+        (hide.owned && self.library.contains_key(&key)) || (hide.watched && self.watched.contains(&key))
+    }
+
+    fn annotate(&self, item: &mut DiscoverItemRow) {
+        let Some(tmdb_id) = item.tmdb_id else { return };
+        let key = Self::key(tmdb_id, &item.media_type);
+        item.media_item_id = self.library.get(&key).cloned();
+        item.on_watchlist = self.watchlist.contains(&key);
+        item.watched = self.watched.contains(&key);
+    }
+}
+
+/// Setzt Bibliotheks-, Watchlist- und Gesehen-Angaben und blendet gewünschte Titel aus.
+/// Listen, die dadurch leer werden, entfallen.
+async fn annotate_lists(
+    pool: &DbPool,
+    lists: Vec<DiscoverList>,
+    hide: HideOptions,
+) -> Result<Vec<DiscoverList>, String> {
+    Ok(annotate_with(&TitleStatus::load(pool).await?, lists, hide))
+}
+
+fn annotate_with(status: &TitleStatus, mut lists: Vec<DiscoverList>, hide: HideOptions) -> Vec<DiscoverList> {
+    for list in &mut lists {
+        annotate_list(status, list, hide);
+    }
+    if hide.any() {
+        lists.retain(|l| !l.items.is_empty());
+    }
+    lists
+}
+
+fn annotate_list(status: &TitleStatus, list: &mut DiscoverList, hide: HideOptions) {
+    list.items.retain(|i| !i.tmdb_id.is_some_and(|id| status.hidden(id, &i.media_type, hide)));
+    for item in &mut list.items {
+        status.annotate(item);
+    }
+}
+
+/// Entfernt im Kindermodus gesperrte Titel und danach leere Listen.
+fn filter_lists(mut lists: Vec<DiscoverList>, filter: Option<&KidsFilter>) -> Vec<DiscoverList> {
+    if let Some(filter) = filter {
+        for list in &mut lists {
+            list.items.retain(|i| filter.allows(i.tmdb_id, &i.media_type));
+        }
+        lists.retain(|l| !l.items.is_empty());
+    }
+    lists
 }
 
 #[tauri::command]
@@ -102,16 +371,19 @@ pub async fn get_ai_recommendations_list(
     pool: State<'_, DbPool>,
     preset: String,
     force_refresh: Option<bool>,
+    hide_owned: Option<bool>,
+    hide_watched: Option<bool>,
 ) -> Result<DiscoverList, String> {
+    let hide = HideOptions::new(hide_owned, hide_watched);
     let force = force_refresh.unwrap_or(false);
+    let settings = storage::load_all_settings(&pool).await?;
 
     if !force {
         if let Some(cached) = storage::get_ai_recommendations_cached(&pool, &preset).await? {
-            return Ok(cached);
+            return filter_ai_list(&pool, &settings, cached, hide).await;
         }
     }
 
-    let settings = storage::load_all_settings(&pool).await?;
     let perplexity_key = settings
         .get("perplexity_api_key")
         .filter(|k| !k.is_empty())
@@ -123,6 +395,7 @@ pub async fn get_ai_recommendations_list(
         .ok_or("TMDb API-Key fehlt. In Einstellungen setzen.")?
         .clone();
     let favorite_films = settings.get("favorite_films").map(|s| s.as_str());
+    let languages = LanguagePrefs::from_settings(&settings);
 
     let ai_items = perplexity::get_ai_recommendations(
         &perplexity_key,
@@ -137,6 +410,7 @@ pub async fn get_ai_recommendations_list(
     for (i, ai) in ai_items.iter().enumerate() {
         let search_results = discovery::search_streams(
             &tmdb_key,
+            languages.primary(),
             &ai.title,
             "movie",
             None,
@@ -159,6 +433,9 @@ pub async fn get_ai_recommendations_list(
                 overview: Some(sr.overview),
                 provider: None,
                 sort_order: i as i32,
+                media_item_id: None,
+                on_watchlist: false,
+                watched: false,
             });
         }
         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
@@ -168,14 +445,36 @@ pub async fn get_ai_recommendations_list(
         id: list_id.clone(),
         list_type: "ai_recommendations".to_string(),
         provider: None,
+        provider_logo: None,
         country: None,
         title: preset_title(&preset),
         created_at: String::new(),
         items,
+        last_error: None,
+        stale: false,
     };
 
     storage::save_ai_recommendations_cache(&pool, &preset, &list).await?;
 
+    filter_ai_list(&pool, &settings, list, hide).await
+}
+
+/// Der Cache enthält alle Empfehlungen; gefiltert wird erst bei der Ausgabe.
+async fn filter_ai_list(
+    pool: &DbPool,
+    settings: &HashMap<String, String>,
+    mut list: DiscoverList,
+    hide: HideOptions,
+) -> Result<DiscoverList, String> {
+    let titles: Vec<(i64, String)> = list
+        .items
+        .iter()
+        .filter_map(|i| Some((i.tmdb_id?, i.media_type.clone())))
+        .collect();
+    if let Some(filter) = KidsFilter::prepare(pool, settings, &titles).await? {
+        list.items.retain(|i| filter.allows(i.tmdb_id, &i.media_type));
+    }
+    annotate_list(&TitleStatus::load(pool).await?, &mut list, hide);
     Ok(list)
 }
 
@@ -195,3 +494,231 @@ fn preset_title(preset: &str) -> String {
     }
     .to_string()
 }
+
+/// Danach werden ähnliche Titel neu bei TMDb geholt.
+const SIMILAR_MAX_AGE_DAYS: i64 = 7;
+/// Mehr zeigt die Detailseite nicht an; begrenzt auch die Stream-Suchen.
+const SIMILAR_LIMIT: usize = 20;
+
+#[derive(Debug, serde::Serialize)]
+pub struct SimilarTitleResponse {
+    pub tmdb_id: i64,
+    /// "movie" oder "tv"
+    pub media_type: String,
+    pub title: String,
+    pub year: Option<i32>,
+    pub rating: Option<f64>,
+    pub poster_url: Option<String>,
+    pub overview: Option<String>,
+    /// "recommendations" und/oder "similar"
+    pub sources: Vec<String>,
+    /// Bibliothekseintrag, falls der Titel vorhanden ist.
+    pub media_item_id: Option<String>,
+    pub on_watchlist: bool,
+    pub watched: bool,
+    /// Nur für fehlende Filme und nur mit `check_streams`; `None`, wenn nicht
+    /// geprüft oder die Suche fehlschlug.
+    pub has_streams: Option<bool>,
+}
+
+/// TMDb-Empfehlungen und ähnliche Titel zu einem Film oder einer Serie, pro
+/// Titel im Cache. Mit `check_streams` wird für fehlende Filme nach
+/// RealDebrid-/Debridio-Streams gesucht.
+#[tauri::command]
+pub async fn get_similar_titles(
+    pool: State<'_, DbPool>,
+    tmdb_id: i64,
+    media_type: String,
+    force_refresh: Option<bool>,
+    check_streams: Option<bool>,
+) -> Result<Vec<SimilarTitleResponse>, String> {
+    let settings = storage::load_all_settings(&pool).await?;
+    let media_type = tmdb_media_type(&media_type);
+
+    let cached = if force_refresh.unwrap_or(false) {
+        None
+    } else {
+        storage::get_similar_titles_cached(&pool, tmdb_id, media_type, SIMILAR_MAX_AGE_DAYS).await?
+    };
+    let mut titles = match cached {
+        Some(titles) => titles,
+        None => {
+            let api_key = settings
+                .get("tmdb_api_key")
+                .filter(|k| !k.is_empty())
+                .ok_or("Kein TMDb API-Key konfiguriert.")?;
+            let languages = LanguagePrefs::from_settings(&settings);
+            let titles = tmdb_api::fetch_similar_titles(api_key, &languages, tmdb_id, media_type).await?;
+            storage::save_similar_titles_cache(&pool, tmdb_id, media_type, &titles).await?;
+            titles
+        }
+    };
+
+    let keys: Vec<(i64, String)> = titles.iter().map(|t| (t.tmdb_id, t.media_type.clone())).collect();
+    if let Some(filter) = KidsFilter::prepare(&pool, &settings, &keys).await? {
+        titles.retain(|t| filter.allows(Some(t.tmdb_id), &t.media_type));
+    }
+    titles.truncate(SIMILAR_LIMIT);
+
+    let library = storage::get_library_tmdb_items(&pool).await?;
+    let watchlist = storage::get_watchlist_keys(&pool).await?;
+    let watched = storage::get_watched_keys(&pool).await?;
+    let available = if check_streams.unwrap_or(false) {
+        let unowned: Vec<(i64, String)> = titles
+            .iter()
+            .filter(|t| t.media_type == "movie" && !library.contains_key(&(t.tmdb_id, t.media_type.clone())))
+            .map(|t| (t.tmdb_id, t.media_type.clone()))
+            .collect();
+        check_stream_availability(&pool, &unowned).await?
+    } else {
+        HashMap::new()
+    };
+
+    let mut results = Vec::with_capacity(titles.len());
+    for title in titles {
+        let key = (title.tmdb_id, title.media_type.clone());
+        let media_item_id = library.get(&key).cloned();
+        let has_streams = available.get(&key).copied();
+        results.push(similar_title_response(
+            title,
+            media_item_id,
+            watchlist.contains(&key),
+            watched.contains(&key),
+            has_streams,
+        ));
+    }
+    Ok(results)
+}
+
+fn similar_title_response(
+    title: SimilarTitle,
+    media_item_id: Option<String>,
+    on_watchlist: bool,
+    watched: bool,
+    has_streams: Option<bool>,
+) -> SimilarTitleResponse {
+    SimilarTitleResponse {
+        tmdb_id: title.tmdb_id,
+        media_type: title.media_type,
+        title: title.title,
+        year: title.year,
+        rating: title.rating,
+        poster_url: title.poster_url,
+        overview: title.overview,
+        sources: title.sources,
+        media_item_id,
+        on_watchlist,
+        watched,
+        has_streams,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(tmdb_id: Option<i64>, media_type: &str) -> DiscoverItemRow {
+        DiscoverItemRow {
+            id: format!("{media_type}-{tmdb_id:?}"),
+            list_id: "list".into(),
+            media_type: media_type.into(),
+            external_id: None,
+            tmdb_id,
+            title: "Titel".into(),
+            year: None,
+            rating: None,
+            poster_url: None,
+            overview: None,
+            provider: None,
+            sort_order: 0,
+            media_item_id: None,
+            on_watchlist: false,
+            watched: false,
+        }
+    }
+
+    fn list(id: &str, items: Vec<DiscoverItemRow>) -> DiscoverList {
+        DiscoverList {
+            id: id.into(),
+            list_type: "custom".into(),
+            provider: None,
+            provider_logo: None,
+            country: None,
+            title: id.into(),
+            created_at: String::new(),
+            items,
+            last_error: None,
+            stale: false,
+        }
+    }
+
+    /// Film 1 in der Bibliothek, Serie 2 auf der Watchlist und gesehen, Serie 3 in der Bibliothek.
+    fn status() -> TitleStatus {
+        TitleStatus {
+            library: HashMap::from([
+                ((1, "movie".to_string()), "media-1".to_string()),
+                ((3, "tv".to_string()), "media-3".to_string()),
+            ]),
+            watchlist: HashSet::from([(2, "tv".to_string())]),
+            watched: HashSet::from([(2, "tv".to_string())]),
+        }
+    }
+
+    fn ids(list: &DiscoverList) -> Vec<Option<i64>> {
+        list.items.iter().map(|i| i.tmdb_id).collect()
+    }
+
+    #[test]
+    fn annotate_sets_flags_and_maps_series_to_tv() {
+        let lists = vec![list("a", vec![
+            item(Some(1), "movie"),
+            item(Some(2), "series"),
+            item(Some(3), "tv"),
+            item(Some(1), "tv"),
+            item(None, "movie"),
+        ])];
+        let lists = annotate_with(&status(), lists, HideOptions::new(None, None));
+        let items = &lists[0].items;
+        assert_eq!(items.len(), 5);
+
+        assert_eq!(items[0].media_item_id.as_deref(), Some("media-1"));
+        assert!(!items[0].on_watchlist && !items[0].watched);
+
+        assert_eq!(items[1].media_item_id, None);
+        assert!(items[1].on_watchlist && items[1].watched);
+
+        assert_eq!(items[2].media_item_id.as_deref(), Some("media-3"));
+
+        // Gleiche ID, anderer Typ: kein Treffer.
+        assert_eq!(items[3].media_item_id, None);
+        assert!(items[4].media_item_id.is_none() && !items[4].on_watchlist);
+    }
+
+    #[test]
+    fn hide_owned_removes_library_titles() {
+        let lists = vec![list("a", vec![item(Some(1), "movie"), item(Some(2), "tv"), item(Some(3), "series")])];
+        let lists = annotate_with(&status(), lists, HideOptions::new(Some(true), None));
+        assert_eq!(ids(&lists[0]), vec![Some(2)]);
+    }
+
+    #[test]
+    fn hide_watched_removes_watched_titles() {
+        let lists = vec![list("a", vec![item(Some(1), "movie"), item(Some(2), "series"), item(None, "tv")])];
+        let lists = annotate_with(&status(), lists, HideOptions::new(None, Some(true)));
+        assert_eq!(ids(&lists[0]), vec![Some(1), None]);
+        assert_eq!(lists[0].items[0].media_item_id.as_deref(), Some("media-1"));
+    }
+
+    #[test]
+    fn hiding_drops_lists_that_become_empty() {
+        let lists = vec![
+            list("owned", vec![item(Some(1), "movie"), item(Some(3), "tv")]),
+            list("rest", vec![item(Some(4), "movie")]),
+        ];
+        let hidden = annotate_with(&status(), lists.clone(), HideOptions::new(Some(true), Some(true)));
+        assert_eq!(hidden.iter().map(|l| l.id.as_str()).collect::<Vec<_>>(), vec!["rest"]);
+
+        let shown = annotate_with(&status(), vec![list("empty", Vec::new())], HideOptions::new(None, None));
+        assert_eq!(shown.len(), 1);
+    }
+}
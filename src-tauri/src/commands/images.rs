@@ -0,0 +1,116 @@
+use crate::db::DbPool;
+use crate::services::image_cache::{self, ImageCacheStats};
+use crate::services::storage;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+fn cache_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(image_cache::cache_dir(&app_data_dir))
+}
+
+static EVICTING: AtomicBool = AtomicBool::new(false);
+
+async fn max_bytes(pool: &DbPool) -> Result<i64, String> {
+    let settings = storage::load_all_settings(pool).await?;
+    Ok(image_cache::max_bytes(&settings))
+}
+
+/// Räumt den Cache im Hintergrund auf; läuft schon ein Durchgang, entfällt dieser.
+fn spawn_evict(pool: DbPool, keep: String) {
+    if EVICTING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        let result = async {
+            let max = max_bytes(&pool).await?;
+            image_cache::evict(&pool, max, Some(&keep)).await
+        }
+        .await;
+        EVICTING.store(false, Ordering::SeqCst);
+        if let Err(e) = result {
+            log::warn!("Image cache eviction failed: {}", e);
+        }
+    });
+}
+
+/// Lokaler Pfad für ein Bild; lädt es bei Bedarf herunter. `size` wählt bei
+/// TMDb-Bildern eine Variante (z.B. "w185").
+#[tauri::command]
+pub async fn get_cached_image(
+    app: AppHandle,
+    pool: State<'_, DbPool>,
+    url: String,
+    size: Option<String>,
+) -> Result<String, String> {
+    let url = match size {
+        Some(size) => image_cache::variant_url(&url, &size),
+        None => url,
+    };
+    if let Some(path) = image_cache::lookup(&pool, &url).await? {
+        return Ok(path.to_string_lossy().to_string());
+    }
+    let dir = cache_dir(&app)?;
+    let client = reqwest::Client::new();
+    let path = image_cache::get_or_fetch(&pool, &client, &dir, &url).await?;
+    spawn_evict(pool.inner().clone(), url);
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Nur bereits gecachte Bilder (kein Netzwerk), URL -> lokaler Pfad.
+#[tauri::command]
+pub async fn get_cached_image_paths(
+    pool: State<'_, DbPool>,
+    urls: Vec<String>,
+) -> Result<HashMap<String, String>, String> {
+    let mut out = HashMap::new();
+    for url in urls {
+        if let Some(path) = image_cache::lookup(&pool, &url).await? {
+            out.insert(url, path.to_string_lossy().to_string());
+        }
+    }
+    Ok(out)
+}
+
+/// Lädt alle Bilder der Bibliothek in den Cache und räumt danach einmal auf.
+/// Emits "image-cache-progress" (erledigt, gesamt), then "image-cache-complete".
+#[tauri::command]
+pub async fn cache_library_images(
+    app: AppHandle,
+    pool: State<'_, DbPool>,
+) -> Result<u32, String> {
+    let dir = cache_dir(&app)?;
+    let max = max_bytes(&pool).await?;
+    let urls = image_cache::library_image_urls(&pool).await?;
+    let client = reqwest::Client::new();
+    let total = urls.len();
+    let mut cached = 0u32;
+
+    for (i, url) in urls.iter().enumerate() {
+        match image_cache::get_or_fetch(&pool, &client, &dir, url).await {
+            Ok(_) => cached += 1,
+            Err(e) => log::warn!("Image cache failed for {}: {}", url, e),
+        }
+        let _ = app.emit("image-cache-progress", (i + 1, total));
+    }
+    if let Err(e) = image_cache::evict(&pool, max, None).await {
+        log::warn!("Image cache eviction failed: {}", e);
+    }
+
+    let _ = app.emit("image-cache-complete", cached);
+    Ok(cached)
+}
+
+#[tauri::command]
+pub async fn get_image_cache_stats(pool: State<'_, DbPool>) -> Result<ImageCacheStats, String> {
+    let max = max_bytes(&pool).await?;
+    image_cache::stats(&pool, max).await
+}
+
+#[tauri::command]
+pub async fn clear_image_cache(app: AppHandle, pool: State<'_, DbPool>) -> Result<(), String> {
+    let dir = cache_dir(&app)?;
+    image_cache::clear(&pool, &dir).await
+}
@@ -1,7 +1,9 @@
 use crate::db::DbPool;
 use crate::services::local_media::{self, PathCheckResult, ScannedFile};
+use crate::services::media_kind;
+use crate::services::parental::KidsFilter;
 use crate::services::perplexity;
-use crate::services::storage::{self, LibraryItemWithMeta, MediaItem};
+use crate::services::storage::{self, LibraryItemWithMeta, MediaItem, MediaKind};
 use tauri::{AppHandle, Emitter, State};
 
 #[tauri::command]
@@ -30,6 +32,7 @@ pub async fn scan_media_dirs_progressive(
                 media_type: file.media_type.clone(),
                 file_size: Some(file.file_size),
                 series_name: None,
+                media_kind: Some(file.media_kind.clone()),
                 created_at: String::new(),
                 updated_at: String::new(),
             };
@@ -69,30 +72,79 @@ pub async fn scan_media_dirs(pool: State<'_, DbPool>, paths: Vec<String>) -> Res
             media_type: file.media_type.clone(),
             file_size: Some(file.file_size),
             series_name: None,
+            media_kind: Some(file.media_kind.clone()),
             created_at: String::new(),
             updated_at: String::new(),
         };
         storage::upsert_media_item(&pool, &item).await?;
     }
 
-    storage::get_all_media_items(&pool).await
+    storage::get_all_media_items(&pool, None).await
 }
 
 #[tauri::command]
-pub async fn get_library(pool: State<'_, DbPool>) -> Result<Vec<MediaItem>, String> {
-    storage::get_all_media_items(&pool).await
+pub async fn get_library(
+    pool: State<'_, DbPool>,
+    media_kind: Option<String>,
+) -> Result<Vec<MediaItem>, String> {
+    let mut items = storage::get_all_media_items(&pool, media_kind.as_deref()).await?;
+    let settings = storage::load_all_settings(&pool).await?;
+    if let Some(filter) = KidsFilter::load(&pool, &settings).await? {
+        let tmdb_ids = storage::get_item_tmdb_ids(&pool).await?;
+        items.retain(|i| filter.allows(tmdb_ids.get(&i.id).copied(), &i.media_type));
+    }
+    Ok(items)
 }
 
 #[tauri::command]
-pub async fn get_library_with_metadata(pool: State<'_, DbPool>) -> Result<Vec<LibraryItemWithMeta>, String> {
-    storage::get_all_media_items_with_metadata(&pool).await
+pub async fn get_library_with_metadata(
+    pool: State<'_, DbPool>,
+    media_kind: Option<String>,
+) -> Result<Vec<LibraryItemWithMeta>, String> {
+    let mut items = storage::get_all_media_items_with_metadata(&pool, media_kind.as_deref()).await?;
+    let settings = storage::load_all_settings(&pool).await?;
+    if let Some(filter) = KidsFilter::load(&pool, &settings).await? {
+        items.retain(|i| {
+            filter.allows(i.metadata.as_ref().and_then(|m| m.tmdb_id), &i.item.media_type)
+        });
+    }
+    Ok(items)
 }
 
 #[tauri::command]
-pub async fn get_media_item(pool: State<'_, DbPool>, id: String) -> Result<Option<MediaItem>, String> {
+pub async fn get_media_kinds(pool: State<'_, DbPool>) -> Result<Vec<MediaKind>, String> {
+    storage::get_media_kinds(&pool).await
+}
+
+#[tauri::command]
+pub async fn set_media_kind(
+    pool: State<'_, DbPool>,
+    id: String,
+    media_kind: String,
+) -> Result<Option<MediaItem>, String> {
+    if storage::get_media_kind(&pool, &media_kind).await?.is_none() {
+        return Err(format!("Unbekannte Medienart: {}", media_kind));
+    }
+    storage::update_media_kind(&pool, &id, &media_kind).await?;
     storage::get_media_item_by_id(&pool, &id).await
 }
 
+#[tauri::command]
+pub async fn get_media_item(pool: State<'_, DbPool>, id: String) -> Result<Option<MediaItem>, String> {
+    let item = match storage::get_media_item_by_id(&pool, &id).await? {
+        Some(item) => item,
+        None => return Ok(None),
+    };
+    let settings = storage::load_all_settings(&pool).await?;
+    if let Some(filter) = KidsFilter::load(&pool, &settings).await? {
+        let tmdb_id = storage::get_metadata_for_item(&pool, &id).await?.and_then(|m| m.tmdb_id);
+        if !filter.allows(tmdb_id, &item.media_type) {
+            return Err("Im Kindermodus nicht verfügbar.".to_string());
+        }
+    }
+    Ok(Some(item))
+}
+
 #[tauri::command]
 pub async fn delete_media_item(pool: State<'_, DbPool>, id: String) -> Result<(), String> {
     storage::delete_media_item(&pool, &id).await
@@ -112,7 +164,7 @@ pub async fn improve_classifications_with_perplexity(
         .ok_or("Perplexity API-Key fehlt. In Einstellungen setzen.")?
         .clone();
 
-    let items = storage::get_all_media_items(&pool).await?;
+    let items = storage::get_all_media_items(&pool, None).await?;
     if items.is_empty() {
         return Ok(0);
     }
@@ -134,11 +186,16 @@ pub async fn improve_classifications_with_perplexity(
                         } else {
                             "movie"
                         };
+                        let kind = media_kind::normalize_media_kind(
+                            class.media_kind.as_deref(),
+                            media_type,
+                        );
                         if storage::update_media_classification(
                             &pool,
                             &item.id,
                             media_type,
                             class.series_name.as_deref(),
+                            &kind,
                         )
                         .await
                         .is_ok()
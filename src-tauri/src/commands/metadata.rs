@@ -1,8 +1,14 @@
 use crate::db::DbPool;
+use crate::services::language::{self, LanguagePrefs};
+use crate::services::local_media;
+use crate::services::match_scoring::{self, MatchInput};
+use crate::services::metadata_merge::MergePolicy;
+use crate::services::metadata_provider::{ImageSet, MetadataResult, MetadataTranslation};
 use crate::services::metadata_service::MetadataService;
 use crate::services::perplexity;
-use crate::services::storage::{self, MediaMetadata};
+use crate::services::storage::{self, EpisodeMetadata, MatchCandidate, MediaItem, MediaMetadata};
 use crate::services::tmdb_api;
+use crate::services::tmdb_http;
 use regex::Regex;
 use tauri::{AppHandle, Emitter, State};
 
@@ -34,6 +40,9 @@ fn extract_series_name(title: &str) -> String {
     }
 }
 
+const METADATA_LOOKUP_DISABLED: &str =
+    "Für diese Medienart (z.B. Heimvideos) werden keine Online-Metadaten gesucht.";
+
 #[tauri::command]
 pub async fn fetch_metadata(
     pool: State<'_, DbPool>,
@@ -45,51 +54,15 @@ pub async fn fetch_metadata(
         .await?
         .ok_or("Media item not found")?;
 
+    if !storage::allows_metadata_lookup(&pool, &item).await? {
+        return Err(METADATA_LOOKUP_DISABLED.into());
+    }
+
     if !meta_service.has_providers().await {
         return Err("No metadata providers configured. Set a TMDb API key in Settings.".into());
     }
 
-    let results = meta_service
-        .search(&item.title, year, &item.media_type)
-        .await?;
-
-    let first = match results.into_iter().next() {
-        Some(r) => r,
-        None => return Ok(None),
-    };
-
-    let detail = meta_service
-        .fetch_details(first.provider_id, &item.media_type, Some("tmdb"))
-        .await?;
-
-    let detail = match detail {
-        Some(d) => d,
-        None => first,
-    };
-
-    let metadata = MediaMetadata {
-        id: uuid::Uuid::new_v4().to_string(),
-        media_item_id: media_item_id.clone(),
-        tmdb_id: Some(detail.provider_id),
-        title: Some(detail.title),
-        overview: Some(detail.overview),
-        poster_url: detail.poster_url,
-        backdrop_url: detail.backdrop_url,
-        release_date: detail.release_date,
-        rating: detail.rating,
-        runtime: detail.runtime,
-        genres: Some(serde_json::to_string(&detail.genres).unwrap_or_default()),
-        cast_crew: detail
-            .cast_crew
-            .as_ref()
-            .map(|cc| serde_json::to_string(cc).unwrap_or_default()),
-        raw_response: Some(detail.raw_response),
-        created_at: String::new(),
-        updated_at: String::new(),
-    };
-
-    storage::upsert_metadata(&pool, &metadata).await?;
-    storage::get_metadata_for_item(&pool, &media_item_id).await
+    fetch_metadata_inner(pool.inner(), meta_service.inner(), &item, year).await
 }
 
 #[tauri::command]
@@ -99,13 +72,42 @@ pub async fn fetch_metadata_by_tmdb_id(
     media_item_id: String,
     tmdb_id: i64,
     media_type: String,
+) -> Result<Option<MediaMetadata>, String> {
+    fetch_metadata_by_provider_id(
+        pool.inner(),
+        meta_service.inner(),
+        &media_item_id,
+        "tmdb",
+        &tmdb_id.to_string(),
+        &media_type,
+    )
+    .await
+}
+
+/// Übernimmt einen vom Nutzer gewählten Treffer. Gilt als bestätigt, offene
+/// Review-Kandidaten werden verworfen.
+async fn fetch_metadata_by_provider_id(
+    pool: &DbPool,
+    meta_service: &MetadataService,
+    media_item_id: &str,
+    provider: &str,
+    provider_id: &str,
+    media_type: &str,
 ) -> Result<Option<MediaMetadata>, String> {
     if !meta_service.has_providers().await {
         return Err("No metadata providers configured. Set a TMDb API key in Settings.".into());
     }
 
+    let item = storage::get_media_item_by_id(pool, media_item_id).await?;
+    if let Some(item) = &item {
+        if !storage::allows_metadata_lookup(pool, item).await? {
+            return Err(METADATA_LOOKUP_DISABLED.into());
+        }
+    }
+
+    let policy = merge_policy(pool).await?;
     let detail = meta_service
-        .fetch_details(tmdb_id, &media_type, Some("tmdb"))
+        .fetch_details_merged(provider_id, media_type, provider, &policy)
         .await?;
 
     let detail = match detail {
@@ -113,35 +115,21 @@ pub async fn fetch_metadata_by_tmdb_id(
         None => return Ok(None),
     };
 
-    let metadata = MediaMetadata {
-        id: uuid::Uuid::new_v4().to_string(),
-        media_item_id: media_item_id.clone(),
-        tmdb_id: Some(detail.provider_id),
-        title: Some(detail.title),
-        overview: Some(detail.overview),
-        poster_url: detail.poster_url,
-        backdrop_url: detail.backdrop_url,
-        release_date: detail.release_date,
-        rating: detail.rating,
-        runtime: detail.runtime,
-        genres: Some(serde_json::to_string(&detail.genres).unwrap_or_default()),
-        cast_crew: detail
-            .cast_crew
-            .as_ref()
-            .map(|cc| serde_json::to_string(cc).unwrap_or_default()),
-        raw_response: Some(detail.raw_response),
-        created_at: String::new(),
-        updated_at: String::new(),
-    };
-
-    let persist = storage::get_media_item_by_id(&pool, &media_item_id)
-        .await?
-        .is_some();
-    if persist {
-        storage::upsert_metadata(&pool, &metadata).await?;
-        storage::get_metadata_for_item(&pool, &media_item_id).await
-    } else {
-        Ok(Some(metadata))
+    let translations = detail.translations.clone();
+    let mut metadata = metadata_from_result(media_item_id, detail);
+    metadata.match_confidence = Some(1.0);
+    metadata.match_status = Some(match_scoring::STATUS_CONFIRMED.to_string());
+
+    match item {
+        Some(item) => {
+            storage::upsert_metadata(pool, &metadata).await?;
+            storage::replace_metadata_translations(pool, media_item_id, &translations).await?;
+            storage::replace_match_candidates(pool, media_item_id, &[]).await?;
+            refresh_episode_metadata(pool, meta_service, &item, &metadata).await;
+            store_media_extras(pool, &metadata, &item.media_type).await;
+            storage::get_metadata_for_item(pool, media_item_id).await
+        }
+        None => Ok(Some(metadata)),
     }
 }
 
@@ -153,6 +141,29 @@ pub async fn get_metadata(
     storage::get_metadata_for_item(&pool, &media_item_id).await
 }
 
+/// Gecachte Titel/Handlungen in den Sprachen der Fallback-Kette.
+#[tauri::command]
+pub async fn get_metadata_translations(
+    pool: State<'_, DbPool>,
+    media_item_id: String,
+) -> Result<Vec<MetadataTranslation>, String> {
+    storage::get_metadata_translations(&pool, &media_item_id).await
+}
+
+/// Alle Poster und Backdrops eines Titels beim Provider (Standard TMDb).
+#[tauri::command]
+pub async fn get_metadata_images(
+    meta_service: State<'_, MetadataService>,
+    provider: Option<String>,
+    provider_id: String,
+    media_type: String,
+) -> Result<ImageSet, String> {
+    let provider = provider.unwrap_or_else(|| "tmdb".to_string());
+    meta_service
+        .fetch_images(&provider, &provider_id, &media_type)
+        .await
+}
+
 #[tauri::command]
 pub async fn search_metadata(
     meta_service: State<'_, MetadataService>,
@@ -164,17 +175,21 @@ pub async fn search_metadata(
         return Err("No metadata providers configured. Set a TMDb API key in Settings.".into());
     }
 
-    let results = meta_service.search(&title, year, &media_type).await?;
+    let results = meta_service
+        .search(&title, year, &media_type, Some("tmdb"))
+        .await?;
 
     Ok(results
         .into_iter()
-        .map(|r| MetadataSearchResult {
-            tmdb_id: r.provider_id,
-            title: r.title,
-            overview: r.overview,
-            poster_url: r.poster_url,
-            release_date: r.release_date,
-            rating: r.rating,
+        .filter_map(|r| {
+            Some(MetadataSearchResult {
+                rating: r.primary_rating(),
+                tmdb_id: r.tmdb_id()?,
+                title: r.title,
+                overview: r.overview,
+                poster_url: r.poster_url,
+                release_date: r.release_date,
+            })
         })
         .collect())
 }
@@ -196,16 +211,7 @@ pub async fn fetch_metadata_batch(
 
     for item in items_without_meta {
         let year = extract_year_from_title(&item.title);
-        match fetch_metadata_inner(
-            pool.inner(),
-            meta_service.inner(),
-            &item.id,
-            &item.title,
-            &item.media_type,
-            year,
-        )
-        .await
-        {
+        match fetch_metadata_inner(pool.inner(), meta_service.inner(), &item, year).await {
             Ok(Some(meta)) => {
                 fetched += 1;
                 let _ = app.emit("metadata-fetched", (&item, &meta));
@@ -219,7 +225,7 @@ pub async fn fetch_metadata_batch(
     Ok(fetched)
 }
 
-fn extract_year_from_title(title: &str) -> Option<u16> {
+pub(crate) fn extract_year_from_title(title: &str) -> Option<u16> {
     let start = title.find('(')?;
     let rest = title.get(start + 1..)?;
     let year_str = rest.get(..4)?;
@@ -230,44 +236,361 @@ fn extract_year_from_title(title: &str) -> Option<u16> {
     }
 }
 
-async fn fetch_metadata_inner(
+/// Wie viele der bestplatzierten Kandidaten für den Laufzeitvergleich nachgeladen werden.
+const RUNTIME_CHECK_CANDIDATES: usize = 3;
+/// Wie viele Kandidaten für die Review-Queue gespeichert werden.
+const REVIEW_CANDIDATES: usize = 5;
+
+pub(crate) async fn fetch_metadata_inner(
     pool: &DbPool,
     meta_service: &MetadataService,
-    media_item_id: &str,
-    title: &str,
-    media_type: &str,
+    item: &MediaItem,
     year: Option<u16>,
 ) -> Result<Option<MediaMetadata>, String> {
-    let search_title = if media_type == "series" {
-        extract_series_name(title)
+    let search_title = if item.media_type == "series" {
+        extract_series_name(&item.title)
     } else {
-        title.to_string()
+        item.title.clone()
     };
-    let results = meta_service.search(&search_title, year, media_type).await?;
-    let first = match results.into_iter().next() {
-        Some(r) => r,
-        None => return Ok(None),
+    let results = meta_service
+        .search(&search_title, year, &item.media_type, None)
+        .await?;
+    if results.is_empty() {
+        return Ok(None);
+    }
+
+    // Bei Serien ist die Datei eine einzelne Folge: ffprobe und Detailabrufe je
+    // Folge kosten viel und sagen über die Serie wenig, also ohne Laufzeit.
+    let duration_minutes = if item.media_type == "series" {
+        None
+    } else {
+        match_scoring::probe_duration_minutes(&item.file_path).await
     };
+    let input = MatchInput {
+        title: search_title,
+        year,
+        media_type: item.media_type.clone(),
+        duration_minutes,
+    };
+    let mut ranked = match_scoring::rank_candidates(&input, results);
+
+    // Suchtreffer enthalten keine Laufzeit; für die Spitzenkandidaten nachladen.
+    if input.duration_minutes.is_some() {
+        for candidate in ranked.iter_mut().take(RUNTIME_CHECK_CANDIDATES) {
+            let detail = meta_service
+                .fetch_details(
+                    &candidate.result.provider_id,
+                    &item.media_type,
+                    Some(&candidate.result.provider),
+                )
+                .await;
+            if let Ok(Some(detail)) = detail {
+                candidate.result.runtime = detail.runtime;
+                candidate.score = match_scoring::score_candidate(&input, &candidate.result);
+            }
+        }
+        ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+    }
 
+    let review = match_scoring::needs_review(&ranked);
+    let best = ranked[0].clone();
+
+    let policy = merge_policy(pool).await?;
     let detail = meta_service
-        .fetch_details(first.provider_id, media_type, Some("tmdb"))
+        .fetch_details_merged(
+            &best.result.provider_id,
+            &item.media_type,
+            &best.result.provider,
+            &policy,
+        )
         .await?;
 
     let detail = match detail {
         Some(d) => d,
-        None => first,
+        None => best.result,
+    };
+
+    let translations = detail.translations.clone();
+    let mut metadata = metadata_from_result(&item.id, detail);
+    metadata.match_confidence = Some(best.score);
+    metadata.match_status = Some(
+        if review {
+            match_scoring::STATUS_NEEDS_REVIEW
+        } else {
+            match_scoring::STATUS_AUTO
+        }
+        .to_string(),
+    );
+    storage::upsert_metadata(pool, &metadata).await?;
+    storage::replace_metadata_translations(pool, &item.id, &translations).await?;
+    refresh_episode_metadata(pool, meta_service, item, &metadata).await;
+    store_media_extras(pool, &metadata, &item.media_type).await;
+
+    let candidates: Vec<MatchCandidate> = if review {
+        ranked
+            .into_iter()
+            .take(REVIEW_CANDIDATES)
+            .enumerate()
+            .map(|(rank, c)| MatchCandidate {
+                media_item_id: item.id.clone(),
+                provider: c.result.provider,
+                provider_id: c.result.provider_id,
+                title: c.result.title,
+                overview: Some(c.result.overview).filter(|o| !o.is_empty()),
+                poster_url: c.result.poster_url,
+                release_date: c.result.release_date,
+                media_type: item.media_type.clone(),
+                score: c.score,
+                rank: rank as i64,
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+    storage::replace_match_candidates(pool, &item.id, &candidates).await?;
+
+    storage::get_metadata_for_item(pool, &item.id).await
+}
+
+/// Lädt die Details eines bereits zugeordneten Items neu, ohne die Zuordnung
+/// anzutasten. Ohne TMDb-ID bleibt nur eine neue Suche.
+pub(crate) async fn refresh_metadata_inner(
+    pool: &DbPool,
+    meta_service: &MetadataService,
+    item: &MediaItem,
+    existing: &MediaMetadata,
+) -> Result<Option<MediaMetadata>, String> {
+    let tmdb_id = match existing.tmdb_id {
+        Some(id) => id,
+        None => {
+            let year = extract_year_from_title(&item.title);
+            return fetch_metadata_inner(pool, meta_service, item, year).await;
+        }
+    };
+
+    let policy = merge_policy(pool).await?;
+    let detail = meta_service
+        .fetch_details_merged(&tmdb_id.to_string(), &item.media_type, "tmdb", &policy)
+        .await?;
+    let detail = match detail {
+        Some(d) => d,
+        None => return Ok(None),
     };
 
-    let metadata = MediaMetadata {
+    let translations = detail.translations.clone();
+    let mut metadata = metadata_from_result(&item.id, detail);
+    metadata.match_confidence = existing.match_confidence;
+    metadata.match_status = existing.match_status.clone();
+    storage::upsert_metadata(pool, &metadata).await?;
+    storage::replace_metadata_translations(pool, &item.id, &translations).await?;
+    refresh_episode_metadata(pool, meta_service, item, &metadata).await;
+    store_media_extras(pool, &metadata, &item.media_type).await;
+    storage::get_metadata_for_item(pool, &item.id).await
+}
+
+/// Speichert Videos, Keywords und Freigaben aus der TMDb-Detailantwort.
+/// Fehler werden nur geloggt.
+async fn store_media_extras(pool: &DbPool, metadata: &MediaMetadata, media_type: &str) {
+    let tmdb_id = match metadata.tmdb_id {
+        Some(id) => id,
+        None => return,
+    };
+    let raw: serde_json::Value = match metadata
+        .raw_response
+        .as_deref()
+        .and_then(|r| serde_json::from_str(r).ok())
+    {
+        Some(raw) => raw,
+        None => return,
+    };
+    if raw.get("videos").is_none() {
+        return;
+    }
+    let languages = match storage::load_all_settings(pool).await {
+        Ok(settings) => LanguagePrefs::from_settings(&settings),
+        Err(_) => LanguagePrefs::default(),
+    };
+    let info = tmdb_api::parse_media_extended(&raw, media_type, &languages);
+    if let Err(e) = storage::replace_media_extras(pool, tmdb_id, media_type, &info).await {
+        log::warn!("Storing extras for TMDb {} failed: {}", tmdb_id, e);
+    }
+}
+
+/// Holt die Folgen-Metadaten, wenn das Item eine Serien-Datei mit erkennbarer
+/// Staffel/Folge ist. Fehler werden nur geloggt, die Serien-Metadaten bleiben gültig.
+async fn refresh_episode_metadata(
+    pool: &DbPool,
+    meta_service: &MetadataService,
+    item: &MediaItem,
+    show: &MediaMetadata,
+) {
+    let tmdb_show_id = match show.tmdb_id {
+        Some(id) if item.media_type == "series" => id,
+        _ => return,
+    };
+    if let Err(e) = fetch_episode_inner(pool, meta_service, item, tmdb_show_id).await {
+        log::warn!("Episode metadata for {} failed: {}", item.title, e);
+    }
+}
+
+async fn fetch_episode_inner(
+    pool: &DbPool,
+    meta_service: &MetadataService,
+    item: &MediaItem,
+    tmdb_show_id: i64,
+) -> Result<Option<EpisodeMetadata>, String> {
+    let (season, episode) = match local_media::parse_episode(&item.title) {
+        Some(se) => se,
+        None => return Ok(None),
+    };
+
+    let details = meta_service
+        .fetch_episode("tmdb", &tmdb_show_id.to_string(), season, episode)
+        .await?;
+    let details = match details {
+        Some(d) => d,
+        None => return Ok(None),
+    };
+
+    let ep = EpisodeMetadata {
+        media_item_id: item.id.clone(),
+        tmdb_show_id,
+        season_number: season as i64,
+        episode_number: episode as i64,
+        tmdb_episode_id: details.provider_id.parse().ok(),
+        name: details.name,
+        overview: details.overview,
+        air_date: details.air_date,
+        still_url: details.still_url,
+        runtime: details.runtime,
+        rating: details.rating,
+        guest_stars: (!details.guest_stars.is_empty())
+            .then(|| serde_json::to_string(&details.guest_stars).unwrap_or_default()),
+        created_at: String::new(),
+        updated_at: String::new(),
+    };
+    storage::upsert_episode_metadata(pool, &ep).await?;
+    storage::get_episode_metadata(pool, &item.id).await
+}
+
+#[tauri::command]
+pub async fn get_episode_metadata(
+    pool: State<'_, DbPool>,
+    media_item_id: String,
+) -> Result<Option<EpisodeMetadata>, String> {
+    storage::get_episode_metadata(&pool, &media_item_id).await
+}
+
+/// Lädt die Folgen-Metadaten neu; setzt voraus, dass die Serie bereits zugeordnet ist.
+#[tauri::command]
+pub async fn fetch_episode_metadata(
+    pool: State<'_, DbPool>,
+    meta_service: State<'_, MetadataService>,
+    media_item_id: String,
+) -> Result<Option<EpisodeMetadata>, String> {
+    let item = storage::get_media_item_by_id(&pool, &media_item_id)
+        .await?
+        .ok_or("Media item not found")?;
+    if item.media_type != "series" {
+        return Err("Nur für Serien-Folgen verfügbar.".into());
+    }
+    let tmdb_show_id = storage::get_metadata_for_item(&pool, &media_item_id)
+        .await?
+        .and_then(|m| m.tmdb_id)
+        .ok_or("Serie ist noch keinem TMDb-Eintrag zugeordnet.")?;
+
+    fetch_episode_inner(pool.inner(), meta_service.inner(), &item, tmdb_show_id).await
+}
+
+#[derive(serde::Serialize)]
+pub struct ReviewQueueEntry {
+    pub item: MediaItem,
+    pub metadata: Option<MediaMetadata>,
+    pub candidates: Vec<MatchCandidate>,
+}
+
+/// Alle Items, deren automatischer Treffer unsicher war, mit den besten Kandidaten.
+#[tauri::command]
+pub async fn get_match_review_queue(
+    pool: State<'_, DbPool>,
+) -> Result<Vec<ReviewQueueEntry>, String> {
+    let ids = storage::get_media_item_ids_needing_review(&pool).await?;
+    let mut queue = Vec::with_capacity(ids.len());
+    for id in ids {
+        let item = match storage::get_media_item_by_id(&pool, &id).await? {
+            Some(item) => item,
+            None => continue,
+        };
+        queue.push(ReviewQueueEntry {
+            metadata: storage::get_metadata_for_item(&pool, &id).await?,
+            candidates: storage::get_match_candidates(&pool, &id).await?,
+            item,
+        });
+    }
+    Ok(queue)
+}
+
+/// Bestätigt den aktuellen Treffer (ohne `provider_id`) oder übernimmt einen
+/// anderen Kandidaten.
+#[tauri::command]
+pub async fn confirm_match(
+    pool: State<'_, DbPool>,
+    meta_service: State<'_, MetadataService>,
+    media_item_id: String,
+    provider: Option<String>,
+    provider_id: Option<String>,
+) -> Result<Option<MediaMetadata>, String> {
+    let provider_id = match provider_id {
+        Some(id) => id,
+        None => {
+            storage::set_match_status(&pool, &media_item_id, match_scoring::STATUS_CONFIRMED)
+                .await?;
+            storage::replace_match_candidates(&pool, &media_item_id, &[]).await?;
+            return storage::get_metadata_for_item(&pool, &media_item_id).await;
+        }
+    };
+    let provider = provider.unwrap_or_else(|| "tmdb".to_string());
+
+    let item = storage::get_media_item_by_id(&pool, &media_item_id)
+        .await?
+        .ok_or("Media item not found")?;
+    let media_type = storage::get_match_candidates(&pool, &media_item_id)
+        .await?
+        .into_iter()
+        .find(|c| c.provider == provider && c.provider_id == provider_id)
+        .map(|c| c.media_type)
+        .unwrap_or(item.media_type);
+
+    fetch_metadata_by_provider_id(
+        pool.inner(),
+        meta_service.inner(),
+        &media_item_id,
+        &provider,
+        &provider_id,
+        &media_type,
+    )
+    .await
+}
+
+async fn merge_policy(pool: &DbPool) -> Result<MergePolicy, String> {
+    let settings = storage::load_all_settings(pool).await?;
+    Ok(MergePolicy::from_settings(&settings))
+}
+
+/// Baut die DB-Zeile aus einem Provider-Ergebnis. `tmdb_id` wird nur für
+/// TMDb-Ergebnisse gesetzt, da andere Provider eigene ID-Räume haben.
+fn metadata_from_result(media_item_id: &str, detail: MetadataResult) -> MediaMetadata {
+    let rating = detail.primary_rating();
+    MediaMetadata {
         id: uuid::Uuid::new_v4().to_string(),
         media_item_id: media_item_id.to_string(),
-        tmdb_id: Some(detail.provider_id),
+        tmdb_id: detail.tmdb_id(),
         title: Some(detail.title),
         overview: Some(detail.overview),
         poster_url: detail.poster_url,
         backdrop_url: detail.backdrop_url,
         release_date: detail.release_date,
-        rating: detail.rating,
+        rating,
         runtime: detail.runtime,
         genres: Some(serde_json::to_string(&detail.genres).unwrap_or_default()),
         cast_crew: detail
@@ -275,12 +598,20 @@ async fn fetch_metadata_inner(
             .as_ref()
             .map(|cc| serde_json::to_string(cc).unwrap_or_default()),
         raw_response: Some(detail.raw_response),
+        ratings: (!detail.ratings.is_empty())
+            .then(|| serde_json::to_string(&detail.ratings).unwrap_or_default()),
+        field_sources: (!detail.field_sources.is_empty())
+            .then(|| serde_json::to_string(&detail.field_sources).unwrap_or_default()),
+        match_confidence: None,
+        match_status: None,
+        language: detail.language,
+        locked_fields: None,
+        imdb_id: detail.external_ids.imdb_id,
+        tvdb_id: detail.external_ids.tvdb_id,
+        wikidata_id: detail.external_ids.wikidata_id,
         created_at: String::new(),
         updated_at: String::new(),
-    };
-
-    storage::upsert_metadata(&pool, &metadata).await?;
-    storage::get_metadata_for_item(&pool, media_item_id).await
+    }
 }
 
 #[derive(serde::Serialize)]
@@ -296,17 +627,17 @@ pub struct MetadataSearchResult {
 #[tauri::command]
 pub async fn get_person_details(
     pool: State<'_, DbPool>,
+    meta_service: State<'_, MetadataService>,
     person_id: i64,
     known_for: Option<String>,
 ) -> Result<PersonDetailsResponse, String> {
     let settings = storage::load_all_settings(&pool).await?;
-    let tmdb_key = settings
-        .get("tmdb_api_key")
-        .filter(|k| !k.is_empty())
-        .ok_or("Kein TMDb API-Key konfiguriert.")?
-        .clone();
-
-    let mut details = tmdb_api::fetch_person_details(&tmdb_key, person_id).await?;
+    let info = meta_service
+        .fetch_person("tmdb", &person_id.to_string())
+        .await?
+        .ok_or("Person nicht gefunden.")?;
+    let id = info.provider_id.parse().unwrap_or(person_id);
+    let mut details = tmdb_api::PersonDetails::from_info(id, info);
 
     if let Some(pp_key) = settings.get("perplexity_api_key").filter(|k| !k.is_empty()) {
         if let Ok(anecdotes) = perplexity::get_person_anecdotes(
@@ -384,7 +715,17 @@ pub async fn get_media_extended_info(
         .ok_or("Kein TMDb API-Key konfiguriert.")?
         .clone();
 
-    let mut info = tmdb_api::fetch_media_extended(&tmdb_key, tmdb_id, &media_type).await?;
+    let media_type = if media_type == "tv" { "series".to_string() } else { media_type };
+    let languages = LanguagePrefs::from_settings(&settings);
+    let mut info = match storage::get_media_extras(&pool, tmdb_id, &media_type).await? {
+        Some(info) => info,
+        None => {
+            let info =
+                tmdb_api::fetch_media_extended(&tmdb_key, &languages, tmdb_id, &media_type).await?;
+            storage::replace_media_extras(&pool, tmdb_id, &media_type, &info).await?;
+            info
+        }
+    };
 
     if let Some(pp_key) = settings.get("perplexity_api_key").filter(|k| !k.is_empty()) {
         let t = title.as_deref().unwrap_or("Unbekannt");
@@ -395,9 +736,26 @@ pub async fn get_media_extended_info(
         }
     }
 
+    // Freigabe für das Land der Metadaten-Sprache, sonst Discover-Land, sonst USA.
+    let countries: Vec<String> = languages
+        .chain()
+        .iter()
+        .filter_map(|t| language::iso_3166_1(t).map(String::from))
+        .chain(settings.get("discover_country").cloned())
+        .chain(std::iter::once("US".to_string()))
+        .collect();
+    let certification = countries
+        .iter()
+        .find_map(|c| info.certifications.iter().find(|x| &x.country == c))
+        .cloned();
+
     Ok(MediaExtendedInfoResponse {
         tagline: info.tagline,
         trivia_facts: info.trivia_facts,
+        certification,
+        videos: info.videos,
+        keywords: info.keywords,
+        certifications: info.certifications,
     })
 }
 
@@ -405,6 +763,11 @@ pub async fn get_media_extended_info(
 pub struct MediaExtendedInfoResponse {
     pub tagline: Option<String>,
     pub trivia_facts: Vec<String>,
+    /// Freigabe für das bevorzugte Land.
+    pub certification: Option<tmdb_api::Certification>,
+    pub videos: Vec<tmdb_api::MediaVideo>,
+    pub keywords: Vec<tmdb_api::Keyword>,
+    pub certifications: Vec<tmdb_api::Certification>,
 }
 
 #[tauri::command]
@@ -413,7 +776,7 @@ pub async fn get_actor_movie_suggestions(
     tmdb_id: i64,
     media_type: String,
     actor_ids: Vec<i64>,
-    director_ids: Vec<i64>,
+    crew_ids: Vec<i64>,
 ) -> Result<Vec<ActorMovieSuggestionResponse>, String> {
     let settings = storage::load_all_settings(&pool).await?;
     let api_key = settings
@@ -422,9 +785,18 @@ pub async fn get_actor_movie_suggestions(
         .ok_or("Kein TMDb API-Key konfiguriert.")?
         .clone();
 
-    let suggestions =
-        tmdb_api::fetch_actor_movie_suggestions(&api_key, tmdb_id, &media_type, &actor_ids, &director_ids, 12)
-            .await?;
+    let languages = LanguagePrefs::from_settings(&settings);
+    let owned = storage::get_library_tmdb_items(&pool).await?.into_keys().collect();
+    let suggestions = tmdb_api::fetch_credit_suggestions(
+        &api_key,
+        &languages,
+        tmdb_id,
+        &media_type,
+        &actor_ids,
+        &crew_ids,
+        &owned,
+    )
+    .await?;
 
     Ok(suggestions
         .into_iter()
@@ -448,3 +820,99 @@ pub struct ActorMovieSuggestionResponse {
     pub media_type: String,
     pub match_reason: String,
 }
+
+/// Verwirft alle zwischengespeicherten TMDb-Antworten.
+#[tauri::command]
+pub async fn clear_tmdb_cache(pool: State<'_, DbPool>) -> Result<(), String> {
+    tmdb_http::clear_cache(&pool).await
+}
+
+/// Sperrt Felder gegen Überschreiben durch spätere Aktualisierungen.
+/// `fields` ersetzt die bisherige Sperrliste.
+#[tauri::command]
+pub async fn set_metadata_locked_fields(
+    pool: State<'_, DbPool>,
+    media_item_id: String,
+    fields: Vec<String>,
+) -> Result<Option<MediaMetadata>, String> {
+    if let Some(field) = fields
+        .iter()
+        .find(|f| !storage::LOCKABLE_FIELDS.contains(&f.as_str()))
+    {
+        return Err(format!("Feld kann nicht gesperrt werden: {}", field));
+    }
+    let mut fields = fields;
+    fields.sort();
+    fields.dedup();
+    storage::set_locked_fields(&pool, &media_item_id, &fields).await?;
+    storage::get_metadata_for_item(&pool, &media_item_id).await
+}
+
+#[derive(serde::Serialize)]
+pub struct ExternalIdLookup {
+    pub imdb_id: Option<String>,
+    pub tvdb_id: Option<i64>,
+    /// TMDb-Titel zu der ID.
+    pub matches: Vec<tmdb_api::FindResult>,
+    /// Bibliothekseinträge, die bereits mit dieser ID verknüpft sind.
+    pub library_item_ids: Vec<String>,
+    /// Neue Metadaten, wenn `media_item_id` übergeben und zugeordnet wurde.
+    pub metadata: Option<MediaMetadata>,
+}
+
+/// Löst einen IMDb-Link bzw. eine IMDb- oder TVDB-ID über TMDb `/find` auf.
+/// Mit `media_item_id` wird das Item direkt dem passenden Titel zugeordnet.
+#[tauri::command]
+pub async fn lookup_external_id(
+    pool: State<'_, DbPool>,
+    meta_service: State<'_, MetadataService>,
+    input: String,
+    media_item_id: Option<String>,
+) -> Result<ExternalIdLookup, String> {
+    let external = tmdb_api::ExternalRef::parse(&input)
+        .ok_or("Keine IMDb- oder TVDB-ID erkannt.")?;
+    let (imdb_id, tvdb_id) = match &external {
+        tmdb_api::ExternalRef::Imdb(id) => (Some(id.clone()), None),
+        tmdb_api::ExternalRef::Tvdb(id) => (None, Some(*id)),
+    };
+
+    let settings = storage::load_all_settings(&pool).await?;
+    let api_key = settings
+        .get("tmdb_api_key")
+        .filter(|k| !k.is_empty())
+        .ok_or("Kein TMDb API-Key konfiguriert.")?;
+    let languages = LanguagePrefs::from_settings(&settings);
+
+    let matches = tmdb_api::find_by_external_id(api_key, &languages, &external).await?;
+    let library_item_ids =
+        storage::find_media_item_ids_by_external_id(&pool, imdb_id.as_deref(), tvdb_id).await?;
+
+    let mut metadata = None;
+    if let Some(media_item_id) = media_item_id {
+        let item = storage::get_media_item_by_id(&pool, &media_item_id)
+            .await?
+            .ok_or("Media item not found")?;
+        let best = matches
+            .iter()
+            .find(|m| m.media_type == item.media_type)
+            .or_else(|| matches.first())
+            .ok_or("TMDb kennt keinen Titel zu dieser ID.")?;
+        metadata = fetch_metadata_by_provider_id(
+            pool.inner(),
+            meta_service.inner(),
+            &media_item_id,
+            "tmdb",
+            &best.tmdb_id.to_string(),
+            &best.media_type,
+        )
+        .await?;
+    }
+
+    Ok(ExternalIdLookup {
+        imdb_id,
+        tvdb_id,
+        matches,
+        library_item_ids,
+        metadata,
+    })
+}
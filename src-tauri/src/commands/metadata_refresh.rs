@@ -0,0 +1,177 @@
+//! Hintergrund-Aktualisierung der Metadaten.
+//!
+//! Läuft kurz nach dem Start und danach alle paar Stunden. Pro Durchlauf wird
+//! nur ein begrenztes Kontingent an Items bearbeitet: zuerst Items ohne
+//! Metadaten, dann solche, deren Metadaten älter als die eingestellte Frist sind.
+//! Anschließend werden fehlende und veraltete Filmreihen nachgeladen.
+//!
+//! Einstellungen:
+//! - `metadata_refresh_enabled` – Standard an
+//! - `metadata_refresh_max_age_days` – ab wann Metadaten als veraltet gelten (Standard 30)
+//! - `metadata_refresh_batch_size` – Items pro Durchlauf (Standard 25)
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::commands::collections::refresh_stale_collections;
+use crate::commands::metadata::{extract_year_from_title, fetch_metadata_inner, refresh_metadata_inner};
+use crate::db::DbPool;
+use crate::services::metadata_service::MetadataService;
+use crate::services::storage;
+
+const DEFAULT_MAX_AGE_DAYS: i64 = 30;
+const DEFAULT_BATCH_SIZE: i64 = 25;
+/// Items ohne Treffer werden frühestens nach dieser Zeit erneut gesucht.
+const MISSING_RETRY_HOURS: i64 = 24;
+
+const STARTUP_DELAY: Duration = Duration::from_secs(120);
+const REFRESH_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+/// Pause zwischen zwei Items, damit Vordergrund-Anfragen Vorrang behalten.
+const ITEM_DELAY: Duration = Duration::from_millis(500);
+
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct RefreshSummary {
+    /// Items, die erstmals Metadaten bekommen haben.
+    pub added: u32,
+    pub refreshed: u32,
+    pub failed: u32,
+}
+
+struct RunningGuard;
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        RUNNING.store(false, Ordering::SeqCst);
+    }
+}
+
+fn setting_i64(settings: &HashMap<String, String>, key: &str, default: i64) -> i64 {
+    settings
+        .get(key)
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(default)
+}
+
+/// Startet den periodischen Durchlauf. Wird einmal beim App-Start aufgerufen,
+/// nachdem Pool und MetadataService registriert sind.
+pub fn spawn_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(STARTUP_DELAY).await;
+        loop {
+            let enabled = match storage::load_all_settings(app.state::<DbPool>().inner()).await {
+                Ok(settings) => settings
+                    .get("metadata_refresh_enabled")
+                    .map(|v| v != "false")
+                    .unwrap_or(true),
+                Err(_) => false,
+            };
+            if enabled {
+                match run_pass(&app).await {
+                    Ok(summary) => log::info!(
+                        "Metadata refresh: {} added, {} refreshed, {} failed",
+                        summary.added, summary.refreshed, summary.failed
+                    ),
+                    Err(e) => log::warn!("Metadata refresh skipped: {}", e),
+                }
+                match refresh_stale_collections(app.state::<DbPool>().inner()).await {
+                    Ok(0) => {}
+                    Ok(count) => log::info!("Collection refresh: {} collections loaded", count),
+                    Err(e) => log::warn!("Collection refresh skipped: {}", e),
+                }
+            }
+            tokio::time::sleep(REFRESH_INTERVAL).await;
+        }
+    });
+}
+
+/// Ein Durchlauf. Emits "metadata-fetched" (item, metadata) je aktualisiertem
+/// Item, "metadata-refresh-progress" (erledigt, gesamt) und zum Schluss
+/// "metadata-refresh-complete" mit der Zusammenfassung.
+async fn run_pass(app: &AppHandle) -> Result<RefreshSummary, String> {
+    if RUNNING.swap(true, Ordering::SeqCst) {
+        return Err("Aktualisierung läuft bereits.".into());
+    }
+    let _guard = RunningGuard;
+
+    let pool = app.state::<DbPool>();
+    let meta_service = app.state::<MetadataService>();
+    if !meta_service.has_providers().await {
+        return Err("No metadata providers configured.".into());
+    }
+
+    let settings = storage::load_all_settings(&pool).await?;
+    let max_age_days = setting_i64(&settings, "metadata_refresh_max_age_days", DEFAULT_MAX_AGE_DAYS);
+    let batch_size = setting_i64(&settings, "metadata_refresh_batch_size", DEFAULT_BATCH_SIZE);
+
+    let missing = storage::get_items_pending_metadata(&pool, MISSING_RETRY_HOURS, batch_size).await?;
+    let remaining = batch_size - missing.len() as i64;
+    let stale = if remaining > 0 {
+        storage::get_items_with_stale_metadata(&pool, max_age_days, remaining).await?
+    } else {
+        Vec::new()
+    };
+
+    let total = missing.len() + stale.len();
+    let mut summary = RefreshSummary::default();
+    let mut done = 0usize;
+
+    for item in missing {
+        let year = extract_year_from_title(&item.title);
+        let result = fetch_metadata_inner(&pool, &meta_service, &item, year).await;
+        let error = result.as_ref().err().cloned();
+        storage::record_refresh_attempt(&pool, &item.id, error.as_deref()).await?;
+        match result {
+            Ok(Some(meta)) => {
+                summary.added += 1;
+                let _ = app.emit("metadata-fetched", (&item, &meta));
+            }
+            Ok(None) => {}
+            Err(e) => {
+                summary.failed += 1;
+                log::warn!("Metadata lookup for {} failed: {}", item.title, e);
+            }
+        }
+        done += 1;
+        let _ = app.emit("metadata-refresh-progress", (done, total));
+        tokio::time::sleep(ITEM_DELAY).await;
+    }
+
+    for item in stale {
+        let existing = match storage::get_metadata_for_item(&pool, &item.id).await? {
+            Some(m) => m,
+            None => continue,
+        };
+        let result = refresh_metadata_inner(&pool, &meta_service, &item, &existing).await;
+        let error = result.as_ref().err().cloned();
+        storage::record_refresh_attempt(&pool, &item.id, error.as_deref()).await?;
+        match result {
+            Ok(Some(meta)) => {
+                summary.refreshed += 1;
+                let _ = app.emit("metadata-fetched", (&item, &meta));
+            }
+            Ok(None) => {}
+            Err(e) => {
+                summary.failed += 1;
+                log::warn!("Metadata refresh for {} failed: {}", item.title, e);
+            }
+        }
+        done += 1;
+        let _ = app.emit("metadata-refresh-progress", (done, total));
+        tokio::time::sleep(ITEM_DELAY).await;
+    }
+
+    let _ = app.emit("metadata-refresh-complete", &summary);
+    Ok(summary)
+}
+
+/// Startet sofort einen Durchlauf, unabhängig von `metadata_refresh_enabled`.
+#[tauri::command]
+pub async fn refresh_stale_metadata(app: AppHandle) -> Result<RefreshSummary, String> {
+    run_pass(&app).await
+}
@@ -2,7 +2,13 @@ pub mod library;
 pub mod settings;
 pub mod realdebrid;
 pub mod metadata;
+pub mod metadata_refresh;
 pub mod player;
 pub mod discover;
 pub mod rd_streams;
 pub mod music;
+pub mod images;
+pub mod parental;
+pub mod collections;
+pub mod watchlist;
+pub mod people;
@@ -0,0 +1,140 @@
+//! Kindermodus-Einstellungen. Jede Änderung erfordert die PIN; ohne PIN lässt
+//! sich der Kindermodus nicht einschalten.
+
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::sync::Mutex;
+
+use crate::db::DbPool;
+use crate::services::parental::{self, KidsPolicy};
+use crate::services::storage;
+
+/// Verzögerung nach falscher PIN, damit sich PINs nicht durchprobieren lassen.
+/// Wächst mit jedem weiteren Fehlversuch bis `MAX_WRONG_PIN_DELAY`.
+const WRONG_PIN_DELAY: Duration = Duration::from_secs(1);
+const MAX_WRONG_PIN_DELAY: Duration = Duration::from_secs(30);
+
+/// Fehlversuche seit der letzten richtigen PIN. Die Prüfung hält die Sperre
+/// samt Verzögerung, parallele Aufrufe warten also nacheinander.
+static FAILED_PIN_ATTEMPTS: Mutex<u32> = Mutex::const_new(0);
+
+#[derive(Debug, serde::Serialize)]
+pub struct KidsModeStatus {
+    pub enabled: bool,
+    pub country: String,
+    pub max_certification: Option<String>,
+    pub has_pin: bool,
+}
+
+async fn load_status(pool: &DbPool) -> Result<KidsModeStatus, String> {
+    let settings = storage::load_all_settings(pool).await?;
+    Ok(KidsModeStatus {
+        enabled: KidsPolicy::from_settings(&settings).is_some(),
+        country: parental::country(&settings),
+        max_certification: settings
+            .get("kids_max_certification")
+            .filter(|c| !c.is_empty())
+            .cloned(),
+        has_pin: settings.get("kids_pin_hash").is_some_and(|h| !h.is_empty()),
+    })
+}
+
+async fn check_pin(pool: &DbPool, pin: &str) -> Result<(), String> {
+    let stored = storage::load_all_settings(pool)
+        .await?
+        .get("kids_pin_hash")
+        .filter(|h| !h.is_empty())
+        .cloned()
+        .ok_or("Zuerst eine PIN festlegen.")?;
+    let mut failed = FAILED_PIN_ATTEMPTS.lock().await;
+    if parental::verify_pin(pin, &stored) {
+        *failed = 0;
+        Ok(())
+    } else {
+        *failed = failed.saturating_add(1);
+        tokio::time::sleep((WRONG_PIN_DELAY * *failed).min(MAX_WRONG_PIN_DELAY)).await;
+        Err("Falsche PIN.".into())
+    }
+}
+
+#[tauri::command]
+pub async fn get_kids_mode_status(pool: State<'_, DbPool>) -> Result<KidsModeStatus, String> {
+    load_status(&pool).await
+}
+
+/// Legt die PIN fest. Ist schon eine gesetzt, muss sie als `current_pin` mitkommen.
+#[tauri::command]
+pub async fn set_kids_pin(
+    pool: State<'_, DbPool>,
+    current_pin: Option<String>,
+    new_pin: String,
+) -> Result<(), String> {
+    if load_status(&pool).await?.has_pin {
+        check_pin(&pool, current_pin.as_deref().unwrap_or("")).await?;
+    }
+    parental::validate_pin(&new_pin)?;
+    storage::save_setting(&pool, "kids_pin_hash", &parental::hash_pin(&new_pin)).await
+}
+
+/// Schaltet den Kindermodus und setzt Höchstfreigabe und Land.
+/// Emits "kids-mode-changed" mit dem neuen Status.
+#[tauri::command]
+pub async fn set_kids_mode(
+    app: AppHandle,
+    pool: State<'_, DbPool>,
+    pin: String,
+    enabled: bool,
+    max_certification: Option<String>,
+    country: Option<String>,
+) -> Result<KidsModeStatus, String> {
+    check_pin(&pool, &pin).await?;
+
+    if let Some(country) = country {
+        let country = country.trim().to_ascii_uppercase();
+        if country.len() != 2 || !country.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(format!("Ungültiger Ländercode: {}", country));
+        }
+        storage::save_setting(&pool, "kids_country", &country).await?;
+    }
+    if let Some(max) = max_certification {
+        let max = max.trim().to_string();
+        let country = parental::country(&storage::load_all_settings(&pool).await?);
+        if parental::min_age(&country, &max).is_none() {
+            return Err(format!("Unbekannte Freigabe für {}: {}", country, max));
+        }
+        storage::save_setting(&pool, "kids_max_certification", &max).await?;
+    }
+    if enabled && load_status(&pool).await?.max_certification.is_none() {
+        return Err("Zuerst eine Höchstfreigabe wählen.".into());
+    }
+    storage::save_setting(&pool, "kids_mode_enabled", if enabled { "true" } else { "false" }).await?;
+
+    if enabled {
+        spawn_library_backfill(app.clone());
+    }
+    let status = load_status(&pool).await?;
+    let _ = app.emit("kids-mode-changed", &status);
+    Ok(status)
+}
+
+/// Holt im Hintergrund Freigaben für Bibliothekstitel, die noch keine haben,
+/// etwa weil ihre Metadaten aus der Zeit vor den gespeicherten Freigaben stammen.
+fn spawn_library_backfill(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let pool = app.state::<DbPool>();
+        let result = async {
+            let settings = storage::load_all_settings(&pool).await?;
+            let titles = storage::get_library_titles_without_extras(&pool).await?;
+            if !titles.is_empty() {
+                parental::fetch_missing_extras(&pool, &settings, &titles).await;
+                let _ = app.emit("kids-mode-changed", &load_status(&pool).await?);
+            }
+            Ok::<_, String>(())
+        }
+        .await;
+        if let Err(e) = result {
+            log::warn!("Certification backfill failed: {}", e);
+        }
+    });
+}
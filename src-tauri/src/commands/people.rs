@@ -0,0 +1,268 @@
+//! Personen: Filmografie abgeglichen mit Bibliothek, Watchlist und Gesehen,
+//! dazu der Personen-Index der Bibliothek (`people`/`media_credits`).
+
+use std::cmp::Reverse;
+
+use tauri::State;
+
+use crate::db::DbPool;
+use crate::services::language::LanguagePrefs;
+use crate::services::parental::KidsFilter;
+use crate::services::storage::{self, LibraryPerson};
+use crate::services::tmdb_api::{self, PersonCredit};
+
+/// Abteilung der Rollen; steht immer vorne.
+const ACTING: &str = "Acting";
+
+const DEFAULT_COLLABORATORS: i64 = 20;
+const DEFAULT_LIBRARY_PEOPLE: i64 = 50;
+
+#[derive(Debug, serde::Serialize)]
+pub struct FilmographyCredit {
+    pub tmdb_id: i64,
+    /// "movie" oder "tv"
+    pub media_type: String,
+    pub title: String,
+    pub year: Option<i32>,
+    pub release_date: Option<String>,
+    pub poster_url: Option<String>,
+    /// Rollen bzw. Tätigkeiten in dieser Abteilung, z.B. Director und Screenplay.
+    pub roles: Vec<String>,
+    pub episode_count: Option<i64>,
+    /// Bibliothekseintrag, falls der Titel vorhanden ist (bei Serien eine Episode).
+    pub media_item_id: Option<String>,
+    pub on_watchlist: bool,
+    pub watched: bool,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct FilmographyDepartment {
+    pub department: String,
+    /// Neueste zuerst; angekündigte Titel ohne Datum ganz vorne.
+    pub credits: Vec<FilmographyCredit>,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct PersonFilmography {
+    pub person_id: i64,
+    pub departments: Vec<FilmographyDepartment>,
+    /// Verschiedene Titel vor dem Filter `owned_only`.
+    pub total_titles: usize,
+    pub owned_titles: usize,
+}
+
+/// Film- und Serien-Credits einer TMDb-Person nach Abteilung, je Titel mit
+/// Bibliotheks-, Watchlist- und Gesehen-Status. `owned_only` lässt nur
+/// vorhandene Titel übrig.
+#[tauri::command]
+pub async fn get_person_filmography(
+    pool: State<'_, DbPool>,
+    person_id: i64,
+    owned_only: Option<bool>,
+) -> Result<PersonFilmography, String> {
+    let settings = storage::load_all_settings(&pool).await?;
+    let api_key = settings
+        .get("tmdb_api_key")
+        .filter(|k| !k.is_empty())
+        .ok_or("Kein TMDb API-Key konfiguriert.")?;
+    let languages = LanguagePrefs::from_settings(&settings);
+    let mut credits = tmdb_api::fetch_person_credits(api_key, &languages, person_id)
+        .await?
+        .ok_or("Person nicht gefunden.")?;
+
+    if let Some(filter) = KidsFilter::load(&pool, &settings).await? {
+        credits.retain(|c| filter.allows(Some(c.tmdb_id), &c.media_type));
+    }
+
+    let library = storage::get_library_tmdb_items(&pool).await?;
+    let watchlist = storage::get_watchlist_keys(&pool).await?;
+    let watched = storage::get_watched_keys(&pool).await?;
+
+    let mut titles = std::collections::HashSet::new();
+    let mut owned = std::collections::HashSet::new();
+    for c in &credits {
+        let key = (c.tmdb_id, c.media_type.clone());
+        if library.contains_key(&key) {
+            owned.insert(key.clone());
+        }
+        titles.insert(key);
+    }
+
+    let owned_only = owned_only.unwrap_or(false);
+    let mut departments: Vec<FilmographyDepartment> = Vec::new();
+    for credit in credits {
+        let key = (credit.tmdb_id, credit.media_type.clone());
+        let media_item_id = library.get(&key).cloned();
+        if owned_only && media_item_id.is_none() {
+            continue;
+        }
+        let department = match departments.iter_mut().find(|d| d.department == credit.department) {
+            Some(d) => d,
+            None => {
+                departments.push(FilmographyDepartment {
+                    department: credit.department.clone(),
+                    credits: Vec::new(),
+                });
+                departments.last_mut().unwrap()
+            }
+        };
+        let existing = department
+            .credits
+            .iter_mut()
+            .find(|c| c.tmdb_id == credit.tmdb_id && c.media_type == credit.media_type);
+        match existing {
+            Some(entry) => merge_credit(entry, credit),
+            None => department.credits.push(FilmographyCredit {
+                on_watchlist: watchlist.contains(&key),
+                watched: watched.contains(&key),
+                media_item_id,
+                ..filmography_credit(credit)
+            }),
+        }
+    }
+
+    for d in &mut departments {
+        d.credits.sort_by(|a, b| {
+            let newest = match (&a.release_date, &b.release_date) {
+                (Some(x), Some(y)) => y.cmp(x),
+                (None, Some(_)) => std::cmp::Ordering::Less,
+                (Some(_), None) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            };
+            newest.then_with(|| a.title.cmp(&b.title))
+        });
+    }
+    departments.sort_by_key(|d| (d.department != ACTING, Reverse(d.credits.len())));
+
+    Ok(PersonFilmography {
+        person_id,
+        departments,
+        total_titles: titles.len(),
+        owned_titles: owned.len(),
+    })
+}
+
+fn filmography_credit(credit: PersonCredit) -> FilmographyCredit {
+    FilmographyCredit {
+        tmdb_id: credit.tmdb_id,
+        media_type: credit.media_type,
+        title: credit.title,
+        year: credit.year,
+        release_date: credit.release_date,
+        poster_url: credit.poster_url,
+        roles: credit.role.into_iter().collect(),
+        episode_count: credit.episode_count,
+        media_item_id: None,
+        on_watchlist: false,
+        watched: false,
+    }
+}
+
+/// Weiterer Credit desselben Titels in derselben Abteilung (mehrere Rollen/Tätigkeiten).
+fn merge_credit(entry: &mut FilmographyCredit, credit: PersonCredit) {
+    if let Some(role) = credit.role {
+        if !entry.roles.contains(&role) {
+            entry.roles.push(role);
+        }
+    }
+    entry.episode_count = match (entry.episode_count, credit.episode_count) {
+        (Some(a), Some(b)) => Some(a + b),
+        (a, b) => a.or(b),
+    };
+}
+
+/// Bibliothekstitel einer Person; Episoden einer Serie sind zusammengefasst.
+#[derive(Debug, serde::Serialize)]
+pub struct PersonLibraryTitle {
+    /// Bei Serien eine der Episoden.
+    pub media_item_id: String,
+    pub tmdb_id: Option<i64>,
+    /// "movie" oder "series"
+    pub media_type: String,
+    pub title: String,
+    pub year: Option<i32>,
+    pub poster_url: Option<String>,
+    pub characters: Vec<String>,
+    /// Crew-Tätigkeiten, z.B. Director.
+    pub jobs: Vec<String>,
+}
+
+/// Was die Bibliothek mit dieser Person enthält, neueste Titel zuerst.
+#[tauri::command]
+pub async fn get_person_library_titles(
+    pool: State<'_, DbPool>,
+    person_id: i64,
+) -> Result<Vec<PersonLibraryTitle>, String> {
+    let settings = storage::load_all_settings(&pool).await?;
+    let mut credits = storage::get_person_item_credits(&pool, person_id).await?;
+    if let Some(filter) = KidsFilter::load(&pool, &settings).await? {
+        credits.retain(|c| filter.allows(c.tmdb_id, &c.media_type));
+    }
+
+    let mut titles: Vec<PersonLibraryTitle> = Vec::new();
+    for credit in credits {
+        let existing = titles.iter_mut().find(|t| {
+            t.media_type == credit.media_type
+                && match (t.tmdb_id, credit.tmdb_id) {
+                    (Some(a), Some(b)) => a == b,
+                    _ => t.media_item_id == credit.media_item_id,
+                }
+        });
+        let title = match existing {
+            Some(t) => t,
+            None => {
+                titles.push(PersonLibraryTitle {
+                    year: credit
+                        .release_date
+                        .as_deref()
+                        .and_then(|d| d.get(..4))
+                        .and_then(|y| y.parse().ok()),
+                    media_item_id: credit.media_item_id,
+                    tmdb_id: credit.tmdb_id,
+                    media_type: credit.media_type,
+                    title: credit.title,
+                    poster_url: credit.poster_url,
+                    characters: Vec::new(),
+                    jobs: Vec::new(),
+                });
+                titles.last_mut().unwrap()
+            }
+        };
+        let (list, value) = if credit.department == ACTING {
+            (&mut title.characters, credit.character)
+        } else {
+            (&mut title.jobs, Some(credit.role))
+        };
+        if let Some(value) = value.filter(|v| !v.trim().is_empty()) {
+            if !list.contains(&value) {
+                list.push(value);
+            }
+        }
+    }
+    Ok(titles)
+}
+
+/// Wer in der Bibliothek am häufigsten mit dieser Person zusammen auftaucht.
+#[tauri::command]
+pub async fn get_person_collaborators(
+    pool: State<'_, DbPool>,
+    person_id: i64,
+    limit: Option<i64>,
+) -> Result<Vec<LibraryPerson>, String> {
+    storage::get_person_collaborators(&pool, person_id, limit.unwrap_or(DEFAULT_COLLABORATORS)).await
+}
+
+/// Häufigste Personen der Bibliothek, optional nach Namen gefiltert.
+#[tauri::command]
+pub async fn get_library_people(
+    pool: State<'_, DbPool>,
+    query: Option<String>,
+    limit: Option<i64>,
+) -> Result<Vec<LibraryPerson>, String> {
+    storage::get_library_people(
+        &pool,
+        query.as_deref().unwrap_or(""),
+        limit.unwrap_or(DEFAULT_LIBRARY_PEOPLE),
+    )
+    .await
+}
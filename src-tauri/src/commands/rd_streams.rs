@@ -1,9 +1,92 @@
 use crate::db::DbPool;
+use crate::services::language::LanguagePrefs;
+use crate::services::parental::KidsFilter;
 use crate::services::rd_streams::{self, RdStreamLink};
 use crate::services::storage;
+use std::collections::HashMap;
+use std::sync::Arc;
 use tauri::State;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 
-async fn do_search_rd_streams(
+/// Gleichzeitige Abfragen bei der Verfügbarkeitsprüfung.
+const AVAILABILITY_CONCURRENCY: usize = 4;
+
+/// IMDb-ID aus der Bibliothek, sonst über TMDb (und dann gespeichert).
+async fn resolve_imdb_id(
+    pool: &DbPool,
+    api_key: Option<&str>,
+    tmdb_id: i64,
+    media_type: &str,
+) -> Option<String> {
+    if let Ok(Some(imdb_id)) = storage::get_imdb_id_for_tmdb(pool, tmdb_id, media_type).await {
+        return Some(imdb_id);
+    }
+    let imdb_id = rd_streams::get_imdb_id(api_key?, tmdb_id, media_type)
+        .await
+        .ok()
+        .flatten()?;
+    if let Err(e) = storage::set_imdb_id_for_tmdb(pool, tmdb_id, media_type, &imdb_id).await {
+        log::warn!("Could not store IMDb id for TMDb {}: {}", tmdb_id, e);
+    }
+    Some(imdb_id)
+}
+
+/// Prüft, ob Debridio oder Torrentio Streams zu den Titeln (TMDb-ID, "movie"/"series")
+/// kennen. Anders als `do_search_rd_streams` wird dabei nichts zu RealDebrid
+/// hinzugefügt. Titel ohne IMDb-ID oder mit fehlgeschlagener Abfrage fehlen im Ergebnis.
+pub(crate) async fn check_stream_availability(
+    pool: &DbPool,
+    titles: &[(i64, String)],
+) -> Result<HashMap<(i64, String), bool>, String> {
+    let settings = storage::load_all_settings(pool).await?;
+    let api_key = settings.get("tmdb_api_key").filter(|k| !k.is_empty()).cloned();
+    let debridio_base = settings
+        .get("debridio_url")
+        .filter(|k| !k.is_empty())
+        .and_then(|u| rd_streams::extract_debridio_base_url(u));
+    let has_rd = storage::load_rd_token(pool).await?.is_some();
+    if debridio_base.is_none() && !has_rd {
+        return Ok(HashMap::new());
+    }
+
+    let semaphore = Arc::new(Semaphore::new(AVAILABILITY_CONCURRENCY));
+    let mut tasks = JoinSet::new();
+    for (tmdb_id, media_type) in titles.iter().cloned() {
+        let semaphore = semaphore.clone();
+        let (pool, api_key, debridio_base) = (pool.clone(), api_key.clone(), debridio_base.clone());
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let imdb_id = resolve_imdb_id(&pool, api_key.as_deref(), tmdb_id, &media_type).await?;
+            if let Some(base_url) = &debridio_base {
+                let found = rd_streams::fetch_debridio_streams(base_url, &imdb_id, &media_type, None, None, None)
+                    .await
+                    .is_ok_and(|streams| !streams.is_empty());
+                if found || !has_rd {
+                    return Some(((tmdb_id, media_type), found));
+                }
+            }
+            let streams = rd_streams::fetch_torrentio_streams(&imdb_id, &media_type, None, None)
+                .await
+                .ok()?;
+            Some(((tmdb_id, media_type), !streams.is_empty()))
+        });
+    }
+
+    let mut available = HashMap::new();
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok(Some((key, found))) => {
+                available.insert(key, found);
+            }
+            Ok(None) => {}
+            Err(e) => log::warn!("Stream check aborted: {}", e),
+        }
+    }
+    Ok(available)
+}
+
+pub(crate) async fn do_search_rd_streams(
     pool: &DbPool,
     title: &str,
     year: Option<u32>,
@@ -34,6 +117,7 @@ async fn do_search_rd_streams(
     if effective_tmdb_id.is_none() && api_key.is_some() {
         if let Ok(Some(id)) = rd_streams::search_tmdb_for_id(
             api_key.as_ref().unwrap(),
+            LanguagePrefs::from_settings(&settings).primary(),
             &title,
             year,
             &media_type,
@@ -44,20 +128,19 @@ async fn do_search_rd_streams(
         }
     }
 
-    let imdb_id_opt = if (media_type == "movie" || media_type == "series")
-        && effective_tmdb_id.is_some()
-        && api_key.is_some()
-    {
-        rd_streams::get_imdb_id(
-            api_key.as_ref().unwrap(),
-            effective_tmdb_id.unwrap(),
-            &media_type,
-        )
-        .await
-        .ok()
-        .flatten()
-    } else {
-        None
+    let titles: Vec<(i64, String)> =
+        effective_tmdb_id.map(|id| (id, media_type.to_string())).into_iter().collect();
+    if let Some(filter) = KidsFilter::prepare(pool, &settings, &titles).await? {
+        if !filter.allows(effective_tmdb_id, media_type) {
+            return Err("Im Kindermodus nicht verfügbar.".to_string());
+        }
+    }
+
+    let imdb_id_opt = match effective_tmdb_id {
+        Some(tmdb_id) if media_type == "movie" || media_type == "series" => {
+            resolve_imdb_id(pool, api_key.map(|k| k.as_str()), tmdb_id, media_type).await
+        }
+        _ => None,
     };
 
     if let (Some(base_url), Some(imdb_id)) = (
@@ -205,7 +288,14 @@ pub async fn search_debrid_streams(
 
     if let Some(key) = &api_key {
         if let Ok(Some((tmdb_id, tmdb_title, tmdb_year))) =
-            rd_streams::search_tmdb_for_match(key, &title, year, &media_type).await
+            rd_streams::search_tmdb_for_match(
+                key,
+                LanguagePrefs::from_settings(&settings).primary(),
+                &title,
+                year,
+                &media_type,
+            )
+            .await
         {
             matched_tmdb_id = Some(tmdb_id);
             matched_title = Some(tmdb_title);
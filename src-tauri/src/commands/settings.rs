@@ -1,6 +1,8 @@
 use crate::db::DbPool;
 use crate::services::discovery;
+use crate::services::language::LanguagePrefs;
 use crate::services::metadata_service::MetadataService;
+use crate::services::parental;
 use crate::services::perplexity;
 use crate::services::quickconnect;
 use crate::services::storage;
@@ -20,22 +22,41 @@ pub async fn save_settings(
     meta_service: State<'_, MetadataService>,
     settings: HashMap<String, String>,
 ) -> Result<(), String> {
+    // Kindermodus nur über die PIN-geschützten Commands.
+    if let Some(key) = settings.keys().find(|k| parental::is_protected_setting(k)) {
+        return Err(format!("Einstellung {} ist geschützt.", key));
+    }
     for (key, value) in &settings {
         storage::save_setting(&pool, key, value).await?;
     }
 
-    if let Some(api_key) = settings.get("tmdb_api_key") {
-        if !api_key.is_empty() {
-            meta_service.set_tmdb_key(api_key.clone()).await;
+    // Sprachwechsel erfordert einen neu konfigurierten TMDb-Provider.
+    let tmdb_changed = ["tmdb_api_key", "metadata_language", "metadata_language_fallbacks"]
+        .iter()
+        .any(|k| settings.contains_key(*k));
+    if tmdb_changed {
+        let all = storage::load_all_settings(&pool).await?;
+        if let Some(api_key) = all.get("tmdb_api_key").filter(|k| !k.is_empty()) {
+            meta_service
+                .set_tmdb_key(api_key.clone(), LanguagePrefs::from_settings(&all))
+                .await;
         }
     }
+    if let Some(api_key) = settings.get("omdb_api_key") {
+        meta_service.set_omdb_key(api_key.clone()).await;
+    }
+    if let Some(enabled) = settings.get("tvmaze_enabled") {
+        meta_service.set_tvmaze_enabled(enabled != "false").await;
+    }
 
     Ok(())
 }
 
 #[tauri::command]
 pub async fn load_settings(pool: State<'_, DbPool>) -> Result<HashMap<String, String>, String> {
-    storage::load_all_settings(&pool).await
+    let mut settings = storage::load_all_settings(&pool).await?;
+    settings.retain(|k, _| !parental::is_protected_setting(k));
+    Ok(settings)
 }
 
 #[tauri::command]
@@ -64,7 +85,8 @@ pub async fn check_api_configuration_status(
             message: "Kein API-Key gesetzt.".to_string(),
         }
     } else {
-        match discovery::get_tmdb_genres(&effective_tmdb_key).await {
+        let languages = LanguagePrefs::from_settings(&settings);
+        match discovery::get_tmdb_genres(&effective_tmdb_key, languages.primary()).await {
             Ok(genres) => ApiServiceStatus {
                 configured: true,
                 connected: true,
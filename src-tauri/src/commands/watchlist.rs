@@ -0,0 +1,61 @@
+//! Watchlist und Gesehen-Markierungen, geführt über TMDb-ID und "movie"/"tv".
+
+use tauri::State;
+
+use crate::db::DbPool;
+use crate::services::media_kind::tmdb_media_type;
+use crate::services::parental::KidsFilter;
+use crate::services::storage::{self, WatchlistEntry};
+
+#[tauri::command]
+pub async fn get_watchlist(pool: State<'_, DbPool>) -> Result<Vec<WatchlistEntry>, String> {
+    let settings = storage::load_all_settings(&pool).await?;
+    let mut entries = storage::get_watchlist(&pool).await?;
+    if let Some(filter) = KidsFilter::load(&pool, &settings).await? {
+        entries.retain(|e| filter.allows(Some(e.tmdb_id), &e.media_type));
+    }
+    Ok(entries)
+}
+
+/// `media_type` darf auch "series" sein.
+#[tauri::command]
+pub async fn add_to_watchlist(
+    pool: State<'_, DbPool>,
+    tmdb_id: i64,
+    media_type: String,
+    title: String,
+    year: Option<i32>,
+    poster_url: Option<String>,
+) -> Result<(), String> {
+    storage::add_to_watchlist(
+        &pool,
+        &WatchlistEntry {
+            tmdb_id,
+            media_type: tmdb_media_type(&media_type).to_string(),
+            title,
+            year,
+            poster_url,
+            added_at: None,
+        },
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn remove_from_watchlist(
+    pool: State<'_, DbPool>,
+    tmdb_id: i64,
+    media_type: String,
+) -> Result<(), String> {
+    storage::remove_from_watchlist(&pool, tmdb_id, tmdb_media_type(&media_type)).await
+}
+
+#[tauri::command]
+pub async fn set_watched(
+    pool: State<'_, DbPool>,
+    tmdb_id: i64,
+    media_type: String,
+    watched: bool,
+) -> Result<(), String> {
+    storage::set_watched(&pool, tmdb_id, tmdb_media_type(&media_type), watched).await
+}
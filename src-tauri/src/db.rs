@@ -35,15 +35,39 @@ async fn run_migrations(pool: &DbPool) -> Result<(), sqlx::Error> {
         ("5", include_str!("../migrations/005_media_series_name.sql")),
         ("6", include_str!("../migrations/006_ai_recommendations_cache.sql")),
         ("7", include_str!("../migrations/007_music_tables.sql")),
+        ("8", include_str!("../migrations/008_media_kinds.sql")),
+        ("9", include_str!("../migrations/009_metadata_ratings.sql")),
+        ("10", include_str!("../migrations/010_match_review.sql")),
+        ("11", include_str!("../migrations/011_metadata_translations.sql")),
+        ("12", include_str!("../migrations/012_episode_metadata.sql")),
+        ("13", include_str!("../migrations/013_image_cache.sql")),
+        ("14", include_str!("../migrations/014_http_cache.sql")),
+        ("15", include_str!("../migrations/015_metadata_refresh.sql")),
+        ("16", include_str!("../migrations/016_external_ids.sql")),
+        ("17", include_str!("../migrations/017_media_extras.sql")),
+        ("18", include_str!("../migrations/018_collections.sql")),
+        ("19", include_str!("../migrations/019_match_candidates_text_ids.sql")),
+        ("20", include_str!("../migrations/020_watchlist_watched.sql")),
+        ("21", include_str!("../migrations/021_people.sql")),
+        ("22", include_str!("../migrations/022_similar_titles_cache.sql")),
+        ("23", include_str!("../migrations/023_discover_provider_logos.sql")),
+        ("24", include_str!("../migrations/024_discover_list_status.sql")),
+        ("25", include_str!("../migrations/025_discover_list_types.sql")),
+        ("26", include_str!("../migrations/026_discover_queries.sql")),
+        ("27", include_str!("../migrations/027_media_kind_manual.sql")),
     ];
 
+    // Alle Migrationen auf einer Verbindung: SQLite prüft Tabellennamen gegen das
+    // Schema der jeweiligen Verbindung, ein DROP + RENAME über zwei Verbindungen schlägt fehl.
+    let mut conn = pool.acquire().await?;
+
     for (version, sql) in migrations {
         let version_num: i64 = version.parse().unwrap();
         let already_applied: bool = sqlx::query_scalar::<_, i64>(
             "SELECT COUNT(*) FROM schema_migrations WHERE version = ?1"
         )
         .bind(version_num)
-        .fetch_one(pool)
+        .fetch_one(&mut *conn)
         .await? > 0;
 
         if already_applied {
@@ -53,13 +77,13 @@ async fn run_migrations(pool: &DbPool) -> Result<(), sqlx::Error> {
         for statement in sql.split(';') {
             let trimmed = statement.trim();
             if !trimmed.is_empty() {
-                sqlx::query(trimmed).execute(pool).await?;
+                sqlx::query(trimmed).execute(&mut *conn).await?;
             }
         }
 
         sqlx::query("INSERT INTO schema_migrations (version) VALUES (?1)")
             .bind(version_num)
-            .execute(pool)
+            .execute(&mut *conn)
             .await?;
 
         log::info!("Applied migration v{}", version);
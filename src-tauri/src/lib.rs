@@ -2,6 +2,16 @@ mod db;
 mod services;
 mod commands;
 
+/// Schnittstelle für die Integrationstests in `test-harness/`.
+#[cfg(feature = "test-harness")]
+pub mod harness {
+    pub use crate::services::{
+        discovery, endpoints, language, metadata_merge, metadata_provider, metadata_service, omdb,
+        rd_streams, realdebrid, tmdb, tmdb_api, tmdb_http, tvmaze,
+    };
+}
+
+use services::language::LanguagePrefs;
 use services::metadata_service::MetadataService;
 use tauri::Manager;
 
@@ -30,20 +40,34 @@ pub fn run() {
                     .await
                     .expect("Failed to initialize database");
 
+                services::tmdb_http::init_cache(pool.clone()).await;
+
                 let meta_service = MetadataService::new();
 
                 if let Ok(settings) = services::storage::load_all_settings(&pool).await {
                     if let Some(api_key) = settings.get("tmdb_api_key") {
                         if !api_key.is_empty() {
-                            meta_service.set_tmdb_key(api_key.clone()).await;
+                            meta_service
+                                .set_tmdb_key(api_key.clone(), LanguagePrefs::from_settings(&settings))
+                                .await;
                         }
                     }
+                    if let Some(api_key) = settings.get("omdb_api_key") {
+                        meta_service.set_omdb_key(api_key.clone()).await;
+                    }
+                    let tvmaze_enabled = settings
+                        .get("tvmaze_enabled")
+                        .map(|v| v != "false")
+                        .unwrap_or(true);
+                    meta_service.set_tvmaze_enabled(tvmaze_enabled).await;
                 }
 
                 app_handle.manage(pool);
                 app_handle.manage(meta_service);
             });
 
+            commands::metadata_refresh::spawn_scheduler(app_handle);
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -55,6 +79,8 @@ pub fn run() {
             commands::library::get_media_item,
             commands::library::delete_media_item,
             commands::library::improve_classifications_with_perplexity,
+            commands::library::get_media_kinds,
+            commands::library::set_media_kind,
             commands::settings::save_settings,
             commands::settings::load_settings,
             commands::settings::check_quickconnect,
@@ -69,18 +95,35 @@ pub fn run() {
             commands::metadata::fetch_metadata_by_tmdb_id,
             commands::metadata::fetch_metadata_batch,
             commands::metadata::get_metadata,
+            commands::metadata::get_metadata_translations,
+            commands::metadata::get_metadata_images,
+            commands::metadata::get_episode_metadata,
+            commands::metadata::fetch_episode_metadata,
             commands::metadata::search_metadata,
+            commands::metadata::get_match_review_queue,
+            commands::metadata::confirm_match,
             commands::metadata::get_person_details,
             commands::metadata::get_media_extended_info,
             commands::metadata::get_actor_movie_suggestions,
+            commands::metadata::clear_tmdb_cache,
+            commands::metadata::set_metadata_locked_fields,
+            commands::metadata::lookup_external_id,
+            commands::metadata_refresh::refresh_stale_metadata,
             commands::player::detect_players,
             commands::player::open_in_player,
             commands::player::open_in_default_player,
             commands::discover::search_streams,
+            commands::discover::search_tmdb,
+            commands::discover::discover_query,
             commands::discover::get_tmdb_genres,
+            commands::discover::get_watch_providers,
             commands::discover::refresh_discover_lists,
             commands::discover::get_discover_lists,
+            commands::discover::save_discover_query,
+            commands::discover::get_discover_queries,
+            commands::discover::delete_discover_query,
             commands::discover::get_ai_recommendations_list,
+            commands::discover::get_similar_titles,
             commands::rd_streams::search_rd_streams,
             commands::rd_streams::search_debrid_streams,
             commands::music::check_music_path,
@@ -101,6 +144,24 @@ pub fn run() {
             commands::music::remove_track_from_music_playlist,
             commands::music::create_music_radio_playlist,
             commands::music::set_music_track_rating,
+            commands::images::get_cached_image,
+            commands::images::get_cached_image_paths,
+            commands::images::cache_library_images,
+            commands::images::get_image_cache_stats,
+            commands::images::clear_image_cache,
+            commands::parental::get_kids_mode_status,
+            commands::parental::set_kids_pin,
+            commands::parental::set_kids_mode,
+            commands::collections::refresh_collections,
+            commands::collections::get_collection_completeness,
+            commands::watchlist::get_watchlist,
+            commands::watchlist::add_to_watchlist,
+            commands::watchlist::remove_from_watchlist,
+            commands::watchlist::set_watched,
+            commands::people::get_person_filmography,
+            commands::people::get_person_library_titles,
+            commands::people::get_person_collaborators,
+            commands::people::get_library_people,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -1,23 +1,66 @@
-use reqwest::Client;
-use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 
-const TMDB_BASE: &str = "https://api.themoviedb.org/3";
+use super::endpoints;
+use super::media_kind::tmdb_media_type;
+use super::tmdb_http::{self, CacheMode};
 const TMDB_IMG: &str = "https://image.tmdb.org/t/p/w342";
 
+/// Streaming-Anbieter laut TMDb `/watch/providers`.
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct DiscoveryProvider {
-    pub id: &'static str,
-    pub name: &'static str,
     pub tmdb_provider_id: u32,
+    pub name: String,
+    pub logo_url: Option<String>,
 }
 
-pub const PROVIDERS: &[DiscoveryProvider] = &[
-    DiscoveryProvider { id: "netflix",  name: "Netflix",            tmdb_provider_id: 8 },
-    DiscoveryProvider { id: "prime",    name: "Prime Video",        tmdb_provider_id: 9 },
-    DiscoveryProvider { id: "disney",   name: "Disney+",            tmdb_provider_id: 337 },
-    DiscoveryProvider { id: "apple",    name: "Apple TV+",          tmdb_provider_id: 350 },
+/// Auswahl ohne Einstellung `discover_providers`: Netflix, Prime Video, Disney+, Apple TV+.
+pub const DEFAULT_PROVIDER_IDS: &[u32] = &[8, 9, 337, 350];
+
+const TMDB_LOGO: &str = "https://image.tmdb.org/t/p/w92";
+
+/// Gleichzeitige Listen-Abrufe beim Aktualisieren.
+const FETCH_CONCURRENCY: usize = 4;
+
+/// Namen für [`fallback_providers`], wenn TMDb nicht erreichbar ist.
+const KNOWN_PROVIDERS: &[(u32, &str)] = &[
+    (8, "Netflix"),
+    (9, "Amazon Prime Video"),
+    (337, "Disney Plus"),
+    (350, "Apple TV Plus"),
+    (531, "Paramount Plus"),
+    (1899, "Max"),
 ];
 
+/// Ersatz für `/watch/providers`, wenn der Abruf fehlschlägt: die gewählten IDs mit
+/// bekanntem Namen (sonst „Anbieter <ID>“) und den Logos aus `logos`.
+pub fn fallback_providers(ids: &[u32], logos: &HashMap<u32, String>) -> Vec<DiscoveryProvider> {
+    ids.iter()
+        .map(|&id| DiscoveryProvider {
+            tmdb_provider_id: id,
+            name: KNOWN_PROVIDERS
+                .iter()
+                .find(|(known, _)| *known == id)
+                .map(|(_, name)| name.to_string())
+                .unwrap_or_else(|| format!("Anbieter {}", id)),
+            logo_url: logos.get(&id).cloned(),
+        })
+        .collect()
+}
+
+/// Gewählte Anbieter aus `discover_providers` (kommagetrennte TMDb-IDs).
+pub fn selected_provider_ids(setting: Option<&str>) -> Vec<u32> {
+    match setting {
+        Some(s) => s.split(',').filter_map(|id| id.trim().parse().ok()).collect(),
+        None => DEFAULT_PROVIDER_IDS.to_vec(),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DiscoverItem {
     pub tmdb_id: i64,
@@ -32,11 +75,102 @@ pub struct DiscoverItem {
 
 #[derive(Debug, Clone)]
 pub struct DiscoverListResult {
+    /// Stabiler Schlüssel (`imdb_top`, `movie:8`, `tv:8`), über den die Liste ersetzt wird.
+    pub key: String,
     pub list_type: String,
+    /// TMDb-Anbieter-ID als Text.
     pub provider: Option<String>,
+    pub provider_logo: Option<String>,
     pub country: String,
     pub title: String,
     pub items: Vec<DiscoverItem>,
+    /// Fehler beim Abruf; `items` ist dann leer.
+    pub error: Option<String>,
+}
+
+/// Eine der Discover-Listen, unabhängig vom Abruf.
+#[derive(Debug, Clone)]
+enum ListSpec {
+    TopRated,
+    TrendingDay,
+    TrendingWeek,
+    NowPlaying,
+    Upcoming,
+    AiringThisWeek,
+    TopRatedSeries,
+    ProviderMovies(DiscoveryProvider),
+    ProviderSeries(DiscoveryProvider),
+    Custom(Box<SavedDiscoverQuery>),
+}
+
+impl ListSpec {
+    /// Liste ohne Einträge.
+    fn empty(&self, country: &str) -> DiscoverListResult {
+        let (key, list_type, provider, title) = match self {
+            ListSpec::TopRated => ("imdb_top".to_string(), "imdb_top", None, "Top IMDb Filme".to_string()),
+            ListSpec::TrendingDay => ("trending_day".to_string(), "trending_day", None, "Heute im Trend".to_string()),
+            ListSpec::TrendingWeek => ("trending_week".to_string(), "trending_week", None, "Diese Woche im Trend".to_string()),
+            ListSpec::NowPlaying => ("now_playing".to_string(), "now_playing", None, "Neu im Kino".to_string()),
+            ListSpec::Upcoming => ("upcoming".to_string(), "upcoming", None, "Demnächst im Kino".to_string()),
+            ListSpec::AiringThisWeek => ("airing_week".to_string(), "airing_week", None, "Diese Woche im TV".to_string()),
+            ListSpec::TopRatedSeries => ("top_rated_tv".to_string(), "top_rated_tv", None, "Top bewertete Serien".to_string()),
+            ListSpec::ProviderMovies(p) => (
+                format!("movie:{}", p.tmdb_provider_id),
+                "streaming_popular",
+                Some(p),
+                format!("Top Filme auf {}", p.name),
+            ),
+            ListSpec::ProviderSeries(p) => (
+                format!("tv:{}", p.tmdb_provider_id),
+                "streaming_popular",
+                Some(p),
+                format!("Top Serien auf {}", p.name),
+            ),
+            ListSpec::Custom(q) => (format!("custom:{}", q.id), "custom", None, q.name.clone()),
+        };
+        DiscoverListResult {
+            key,
+            list_type: list_type.into(),
+            provider: provider.map(|p| p.tmdb_provider_id.to_string()),
+            provider_logo: provider.and_then(|p| p.logo_url.clone()),
+            country: country.into(),
+            title,
+            items: Vec::new(),
+            error: None,
+        }
+    }
+
+    async fn fetch(
+        &self,
+        api_key: &str,
+        language: &str,
+        country: &str,
+        mode: CacheMode,
+    ) -> Result<DiscoverListResult, String> {
+        match self {
+            ListSpec::TopRated => fetch_top_rated_movies(api_key, language, country, mode).await,
+            ListSpec::TrendingDay => fetch_trending(self, "trending/all/day", api_key, language, country, mode).await,
+            ListSpec::TrendingWeek => fetch_trending(self, "trending/all/week", api_key, language, country, mode).await,
+            ListSpec::NowPlaying => fetch_chart(self, "movie/now_playing", parse_movie, api_key, language, country, mode).await,
+            ListSpec::Upcoming => fetch_chart(self, "movie/upcoming", parse_movie, api_key, language, country, mode).await,
+            ListSpec::AiringThisWeek => {
+                let today = chrono::Local::now().date_naive();
+                let filter = format!(
+                    "air_date.gte={}&air_date.lte={}&sort_by=popularity.desc",
+                    today,
+                    today + chrono::Duration::days(6)
+                );
+                fetch_regional_series(self, &filter, api_key, language, country, mode).await
+            }
+            ListSpec::TopRatedSeries => {
+                let filter = "sort_by=vote_average.desc&vote_count.gte=300";
+                fetch_regional_series(self, filter, api_key, language, country, mode).await
+            }
+            ListSpec::ProviderMovies(p) => fetch_provider_movies(api_key, language, p, country, mode).await,
+            ListSpec::ProviderSeries(p) => fetch_provider_series(api_key, language, p, country, mode).await,
+            ListSpec::Custom(q) => fetch_custom_list(self, &q.query, api_key, language, country, mode).await,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -84,106 +218,517 @@ fn parse_tv(v: &Value) -> Option<DiscoverItem> {
     })
 }
 
-pub async fn fetch_top_rated_movies(api_key: &str, country: &str) -> Result<DiscoverListResult, String> {
-    let client = Client::new();
-    let lang = language_for_country(country);
-    let mut all_items = Vec::new();
+/// Trends enthalten Filme, Serien und Personen gemischt.
+fn parse_trending(v: &Value) -> Option<DiscoverItem> {
+    match v.get("media_type")?.as_str()? {
+        "movie" => parse_movie(v),
+        "tv" => parse_tv(v),
+        _ => None,
+    }
+}
+
+/// Zwei Seiten eines festen TMDb-Endpunkts, `region` schränkt auf das Land ein.
+async fn fetch_chart(
+    spec: &ListSpec,
+    path: &str,
+    parse: fn(&Value) -> Option<DiscoverItem>,
+    api_key: &str,
+    language: &str,
+    country: &str,
+    mode: CacheMode,
+) -> Result<DiscoverListResult, String> {
+    let mut list = spec.empty(country);
+
+    for page in 1..=2 {
+        let url = format!(
+            "{}/{}?api_key={}&language={}&region={}&page={}",
+            endpoints::tmdb(), path, api_key, language, country, page
+        );
+        let data: TmdbPage = tmdb_http::get_json_with(&url, mode).await?;
+        list.items.extend(data.results.iter().filter_map(parse));
+    }
+
+    Ok(list)
+}
+
+/// Irgendwie im Land verfügbar (Abo, Gratis, Werbung, Leihen, Kauf).
+const ANY_MONETIZATION: &str = "flatrate%7Cfree%7Cads%7Crent%7Cbuy";
+
+/// TMDb-Trends kennen keine Region: die Einträge werden danach auf Titel
+/// eingeschränkt, die im Land bei einem Anbieter verfügbar sind.
+async fn fetch_trending(
+    spec: &ListSpec,
+    path: &str,
+    api_key: &str,
+    language: &str,
+    country: &str,
+    mode: CacheMode,
+) -> Result<DiscoverListResult, String> {
+    let mut list = fetch_chart(spec, path, parse_trending, api_key, language, country, mode).await?;
+    let available = available_in_country(&list.items, api_key, country, mode).await;
+    list.items.retain(|item| available.contains(&(item.tmdb_id, item.media_type.clone())));
+    Ok(list)
+}
+
+/// Titel mit Anbietern im Land laut `/{movie|tv}/{id}/watch/providers`.
+/// Schlägt eine Abfrage fehl, bleibt der Titel drin.
+async fn available_in_country(
+    items: &[DiscoverItem],
+    api_key: &str,
+    country: &str,
+    mode: CacheMode,
+) -> HashSet<(i64, String)> {
+    let keys: HashSet<(i64, String)> = items.iter().map(|i| (i.tmdb_id, i.media_type.clone())).collect();
+    let semaphore = Arc::new(Semaphore::new(FETCH_CONCURRENCY));
+    let mut tasks = JoinSet::new();
+    for (tmdb_id, media_type) in keys {
+        let semaphore = semaphore.clone();
+        let url = format!(
+            "{}/{}/{}/watch/providers?api_key={}",
+            endpoints::tmdb(), media_type, tmdb_id, api_key
+        );
+        let country = country.to_string();
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let available = match tmdb_http::get_json_with::<Value>(&url, mode).await {
+                Ok(data) => data.get("results").and_then(|r| r.get(&country)).is_some(),
+                Err(e) => {
+                    log::warn!("Verfügbarkeit für {} {} unbekannt: {}", media_type, tmdb_id, e);
+                    true
+                }
+            };
+            available.then_some((tmdb_id, media_type))
+        });
+    }
+
+    let mut available = HashSet::new();
+    while let Some(joined) = tasks.join_next().await {
+        if let Ok(Some(key)) = joined {
+            available.insert(key);
+        }
+    }
+    available
+}
+
+/// Zwei Seiten `/discover/tv` mit Serien, die im Land verfügbar sind.
+async fn fetch_regional_series(
+    spec: &ListSpec,
+    filter: &str,
+    api_key: &str,
+    language: &str,
+    country: &str,
+    mode: CacheMode,
+) -> Result<DiscoverListResult, String> {
+    let mut list = spec.empty(country);
+
+    for page in 1..=2 {
+        let url = format!(
+            "{}/discover/tv?api_key={}&language={}&watch_region={}&with_watch_monetization_types={}&{}&page={}",
+            endpoints::tmdb(), api_key, language, country, ANY_MONETIZATION, filter, page
+        );
+        let data: TmdbPage = tmdb_http::get_json_with(&url, mode).await?;
+        list.items.extend(data.results.iter().filter_map(parse_tv));
+    }
+
+    Ok(list)
+}
+
+pub async fn fetch_top_rated_movies(
+    api_key: &str,
+    language: &str,
+    country: &str,
+    mode: CacheMode,
+) -> Result<DiscoverListResult, String> {
+    let mut list = ListSpec::TopRated.empty(country);
 
     for page in 1..=3 {
         let url = format!(
             "{}/movie/top_rated?api_key={}&language={}&region={}&page={}",
-            TMDB_BASE, api_key, lang, country, page
+            endpoints::tmdb(), api_key, language, country, page
         );
-        let resp = client.get(&url).send().await.map_err(|e| e.to_string())?;
-        let data: TmdbPage = resp.json().await.map_err(|e| e.to_string())?;
-        all_items.extend(data.results.iter().filter_map(parse_movie));
+        let data: TmdbPage = tmdb_http::get_json_with(&url, mode).await?;
+        list.items.extend(data.results.iter().filter_map(parse_movie));
     }
 
-    Ok(DiscoverListResult {
-        list_type: "imdb_top".into(),
-        provider: None,
-        country: country.into(),
-        title: "Top IMDb Filme".into(),
-        items: all_items,
-    })
+    Ok(list)
 }
 
 pub async fn fetch_provider_movies(
     api_key: &str,
+    language: &str,
     provider: &DiscoveryProvider,
     country: &str,
+    mode: CacheMode,
 ) -> Result<DiscoverListResult, String> {
-    let client = Client::new();
-    let lang = language_for_country(country);
-    let mut all_items = Vec::new();
+    let mut list = ListSpec::ProviderMovies(provider.clone()).empty(country);
 
     for page in 1..=2 {
         let url = format!(
             "{}/discover/movie?api_key={}&language={}&watch_region={}&with_watch_providers={}&sort_by=popularity.desc&page={}",
-            TMDB_BASE, api_key, lang, country, provider.tmdb_provider_id, page
+            endpoints::tmdb(), api_key, language, country, provider.tmdb_provider_id, page
         );
-        let resp = client.get(&url).send().await.map_err(|e| e.to_string())?;
-        let data: TmdbPage = resp.json().await.map_err(|e| e.to_string())?;
-        all_items.extend(data.results.iter().filter_map(parse_movie));
+        let data: TmdbPage = tmdb_http::get_json_with(&url, mode).await?;
+        list.items.extend(data.results.iter().filter_map(parse_movie));
     }
 
-    Ok(DiscoverListResult {
-        list_type: "streaming_popular".into(),
-        provider: Some(provider.id.into()),
-        country: country.into(),
-        title: format!("Top Filme auf {}", provider.name),
-        items: all_items,
-    })
+    Ok(list)
 }
 
 pub async fn fetch_provider_series(
     api_key: &str,
+    language: &str,
     provider: &DiscoveryProvider,
     country: &str,
+    mode: CacheMode,
 ) -> Result<DiscoverListResult, String> {
-    let client = Client::new();
-    let lang = language_for_country(country);
-    let mut all_items = Vec::new();
+    let mut list = ListSpec::ProviderSeries(provider.clone()).empty(country);
 
     for page in 1..=2 {
         let url = format!(
             "{}/discover/tv?api_key={}&language={}&watch_region={}&with_watch_providers={}&sort_by=popularity.desc&page={}",
-            TMDB_BASE, api_key, lang, country, provider.tmdb_provider_id, page
+            endpoints::tmdb(), api_key, language, country, provider.tmdb_provider_id, page
         );
-        let resp = client.get(&url).send().await.map_err(|e| e.to_string())?;
-        let data: TmdbPage = resp.json().await.map_err(|e| e.to_string())?;
-        all_items.extend(data.results.iter().filter_map(parse_tv));
+        let data: TmdbPage = tmdb_http::get_json_with(&url, mode).await?;
+        list.items.extend(data.results.iter().filter_map(parse_tv));
     }
 
-    Ok(DiscoverListResult {
-        list_type: "streaming_popular".into(),
-        provider: Some(provider.id.into()),
-        country: country.into(),
-        title: format!("Top Serien auf {}", provider.name),
-        items: all_items,
-    })
+    Ok(list)
 }
 
-pub async fn fetch_all_lists(api_key: &str, country: &str) -> Result<Vec<DiscoverListResult>, String> {
-    let mut results = Vec::new();
+/// Lädt alle Listen parallel (höchstens `FETCH_CONCURRENCY` gleichzeitig).
+/// Fehlgeschlagene Listen kommen leer mit `error` zurück, die Reihenfolge bleibt.
+pub async fn fetch_all_lists(
+    api_key: &str,
+    language: &str,
+    country: &str,
+    providers: &[DiscoveryProvider],
+    queries: &[SavedDiscoverQuery],
+    mode: CacheMode,
+) -> Vec<DiscoverListResult> {
+    let mut specs = vec![
+        ListSpec::TrendingDay,
+        ListSpec::TrendingWeek,
+        ListSpec::NowPlaying,
+        ListSpec::Upcoming,
+        ListSpec::AiringThisWeek,
+        ListSpec::TopRated,
+        ListSpec::TopRatedSeries,
+    ];
+    for provider in providers {
+        specs.push(ListSpec::ProviderMovies(provider.clone()));
+        specs.push(ListSpec::ProviderSeries(provider.clone()));
+    }
+    specs.extend(queries.iter().map(|q| ListSpec::Custom(Box::new(q.clone()))));
 
-    results.push(fetch_top_rated_movies(api_key, country).await?);
+    let semaphore = Arc::new(Semaphore::new(FETCH_CONCURRENCY));
+    let mut tasks = JoinSet::new();
+    for (index, spec) in specs.iter().cloned().enumerate() {
+        let semaphore = semaphore.clone();
+        let (api_key, language, country) = (api_key.to_string(), language.to_string(), country.to_string());
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            (index, spec.fetch(&api_key, &language, &country, mode).await)
+        });
+    }
 
-    for provider in PROVIDERS {
-        results.push(fetch_provider_movies(api_key, provider, country).await?);
-        results.push(fetch_provider_series(api_key, provider, country).await?);
+    let mut fetched: Vec<Option<Result<DiscoverListResult, String>>> = vec![None; specs.len()];
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok((index, result)) => fetched[index] = Some(result),
+            Err(e) => log::warn!("Discover-Abruf abgebrochen: {}", e),
+        }
     }
 
-    Ok(results)
+    specs
+        .iter()
+        .zip(fetched)
+        .map(|(spec, result)| {
+            result
+                .unwrap_or_else(|| Err("Abruf abgebrochen".to_string()))
+                .unwrap_or_else(|error| {
+                    let empty = spec.empty(country);
+                    log::warn!("Discover-Liste {} fehlgeschlagen: {}", empty.key, error);
+                    DiscoverListResult { error: Some(error), ..empty }
+                })
+        })
+        .collect()
+}
+
+// --- Discover Queries ---
+
+/// Filter für TMDb `/discover/movie` bzw. `/discover/tv`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiscoverQuery {
+    /// `movie` oder `tv` (`series` gilt als `tv`).
+    pub media_type: String,
+    pub genre_ids: Vec<i64>,
+    /// Alle Genres verlangen statt mindestens eines.
+    pub match_all_genres: bool,
+    pub year_from: Option<i32>,
+    pub year_to: Option<i32>,
+    pub min_rating: Option<f64>,
+    pub min_votes: Option<i64>,
+    /// Laufzeit in Minuten.
+    pub runtime_min: Option<i64>,
+    pub runtime_max: Option<i64>,
+    /// ISO-639-1, z.B. `ko`.
+    pub original_language: Option<String>,
+    /// TMDb-Anbieter-IDs, gelten im Discover-Land.
+    pub provider_ids: Vec<u32>,
+    /// `flatrate`, `free`, `ads`, `rent`, `buy`.
+    pub monetization_types: Vec<String>,
+    /// z.B. `vote_average.desc`; Standard `popularity.desc`.
+    pub sort_by: Option<String>,
+}
+
+/// Gespeicherte Abfrage, erscheint als eigene Discover-Liste.
+#[derive(Debug, Clone, Serialize)]
+pub struct SavedDiscoverQuery {
+    pub id: String,
+    pub name: String,
+    pub query: DiscoverQuery,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiscoverQueryPage {
+    pub page: u32,
+    pub total_pages: u32,
+    pub total_results: u32,
+    pub results: Vec<StreamSearchResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TmdbResultPage {
+    #[serde(default)]
+    total_pages: u32,
+    #[serde(default)]
+    total_results: u32,
+    results: Vec<Value>,
 }
 
-fn language_for_country(country: &str) -> &str {
-    match country {
-        "CH" | "DE" | "AT" => "de-DE",
-        "FR" => "fr-FR",
-        "IT" => "it-IT",
-        _ => "en-US",
+/// TMDb liefert höchstens 500 Seiten.
+const MAX_TMDB_PAGE: u32 = 500;
+
+const MONETIZATION_TYPES: &[&str] = &["flatrate", "free", "ads", "rent", "buy"];
+
+fn is_param_token(value: &str) -> bool {
+    !value.is_empty() && value.chars().all(|c| c.is_ascii_alphanumeric() || "._-".contains(c))
+}
+
+fn join_ids<T: ToString>(ids: &[T], separator: &str) -> String {
+    ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(separator)
+}
+
+impl DiscoverQuery {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(sort_by) = self.sort_by.as_deref().filter(|s| !is_param_token(s)) {
+            return Err(format!("Ungültige Sortierung: {}", sort_by));
+        }
+        if let Some(lang) = self.original_language.as_deref().filter(|l| !is_param_token(l)) {
+            return Err(format!("Ungültige Originalsprache: {}", lang));
+        }
+        if let Some(m) = self.monetization_types.iter().find(|m| !MONETIZATION_TYPES.contains(&m.as_str())) {
+            return Err(format!("Unbekannte Verfügbarkeit: {}", m));
+        }
+        if let (Some(from), Some(to)) = (self.year_from, self.year_to) {
+            if from > to {
+                return Err(format!("Jahr von {} liegt nach {}", from, to));
+            }
+        }
+        if let (Some(min), Some(max)) = (self.runtime_min, self.runtime_max) {
+            if min > max {
+                return Err(format!("Mindestlaufzeit {} liegt über {}", min, max));
+            }
+        }
+        Ok(())
+    }
+
+    fn tmdb_type(&self) -> &'static str {
+        tmdb_media_type(&self.media_type)
+    }
+
+    fn parser(&self) -> fn(&Value) -> Option<DiscoverItem> {
+        if self.tmdb_type() == "tv" { parse_tv } else { parse_movie }
     }
+
+    /// Query-Parameter ohne `api_key`, `language` und `page`; setzt `validate` voraus.
+    fn params(&self, country: &str) -> String {
+        let mut params = vec![format!("sort_by={}", self.sort_by.as_deref().unwrap_or("popularity.desc"))];
+        if !self.genre_ids.is_empty() {
+            // Komma = alle Genres, Pipe = eines davon.
+            let separator = if self.match_all_genres { "%2C" } else { "%7C" };
+            params.push(format!("with_genres={}", join_ids(&self.genre_ids, separator)));
+        }
+        let date_field = if self.tmdb_type() == "tv" { "first_air_date" } else { "primary_release_date" };
+        if let Some(year) = self.year_from {
+            params.push(format!("{}.gte={}-01-01", date_field, year));
+        }
+        if let Some(year) = self.year_to {
+            params.push(format!("{}.lte={}-12-31", date_field, year));
+        }
+        if let Some(rating) = self.min_rating {
+            params.push(format!("vote_average.gte={}", rating));
+        }
+        if let Some(votes) = self.min_votes {
+            params.push(format!("vote_count.gte={}", votes));
+        }
+        if let Some(minutes) = self.runtime_min {
+            params.push(format!("with_runtime.gte={}", minutes));
+        }
+        if let Some(minutes) = self.runtime_max {
+            params.push(format!("with_runtime.lte={}", minutes));
+        }
+        if let Some(lang) = self.original_language.as_deref() {
+            params.push(format!("with_original_language={}", lang));
+        }
+        if !self.provider_ids.is_empty() || !self.monetization_types.is_empty() {
+            params.push(format!("watch_region={}", country));
+        }
+        if !self.provider_ids.is_empty() {
+            params.push(format!("with_watch_providers={}", join_ids(&self.provider_ids, "%7C")));
+        }
+        if !self.monetization_types.is_empty() {
+            params.push(format!("with_watch_monetization_types={}", self.monetization_types.join("%7C")));
+        }
+        params.join("&")
+    }
+}
+
+async fn fetch_discover_page(
+    api_key: &str,
+    language: &str,
+    country: &str,
+    query: &DiscoverQuery,
+    page: u32,
+    mode: CacheMode,
+) -> Result<TmdbResultPage, String> {
+    query.validate()?;
+    let url = format!(
+        "{}/discover/{}?api_key={}&language={}&{}&page={}",
+        endpoints::tmdb(), query.tmdb_type(), api_key, language, query.params(country), page
+    );
+    tmdb_http::get_json_with(&url, mode).await
+}
+
+/// Eine Ergebnisseite der Abfrage.
+pub async fn discover_query(
+    api_key: &str,
+    language: &str,
+    country: &str,
+    query: &DiscoverQuery,
+    page: u32,
+) -> Result<DiscoverQueryPage, String> {
+    let page = page.clamp(1, MAX_TMDB_PAGE);
+    let data = fetch_discover_page(api_key, language, country, query, page, CacheMode::Use).await?;
+    let parse = query.parser();
+    let results = data
+        .results
+        .iter()
+        .filter_map(|v| {
+            let item = parse(v)?;
+            Some(StreamSearchResult {
+                tmdb_id: item.tmdb_id,
+                media_type: item.media_type,
+                title: item.title,
+                year: item.year,
+                rating: item.rating,
+                poster_url: item.poster_url,
+                overview: item.overview,
+                genre_ids: v
+                    .get("genre_ids")
+                    .and_then(|g| g.as_array())
+                    .map(|g| g.iter().filter_map(|id| id.as_i64()).collect())
+                    .unwrap_or_default(),
+            })
+        })
+        .collect();
+
+    Ok(DiscoverQueryPage {
+        page,
+        total_pages: data.total_pages.min(MAX_TMDB_PAGE),
+        total_results: data.total_results,
+        results,
+    })
+}
+
+/// Zwei Seiten einer gespeicherten Abfrage.
+async fn fetch_custom_list(
+    spec: &ListSpec,
+    query: &DiscoverQuery,
+    api_key: &str,
+    language: &str,
+    country: &str,
+    mode: CacheMode,
+) -> Result<DiscoverListResult, String> {
+    let mut list = spec.empty(country);
+    let parse = query.parser();
+
+    for page in 1..=2 {
+        let data = fetch_discover_page(api_key, language, country, query, page, mode).await?;
+        list.items.extend(data.results.iter().filter_map(parse));
+        if page >= data.total_pages {
+            break;
+        }
+    }
+
+    Ok(list)
+}
+
+// --- Watch Providers ---
+
+#[derive(Debug, Deserialize)]
+struct WatchProvidersResponse {
+    #[serde(default)]
+    results: Vec<WatchProviderEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WatchProviderEntry {
+    provider_id: u32,
+    provider_name: String,
+    logo_path: Option<String>,
+    display_priority: Option<i64>,
+    #[serde(default)]
+    display_priorities: HashMap<String, i64>,
+}
+
+/// Streaming-Anbieter für Filme und Serien im Land, nach TMDb-Priorität dort sortiert.
+pub async fn fetch_watch_providers(
+    api_key: &str,
+    language: &str,
+    country: &str,
+) -> Result<Vec<DiscoveryProvider>, String> {
+    let mut entries: Vec<(i64, DiscoveryProvider)> = Vec::new();
+    for endpoint in ["movie", "tv"] {
+        let url = format!(
+            "{}/watch/providers/{}?api_key={}&language={}&watch_region={}",
+            endpoints::tmdb(), endpoint, api_key, language, country
+        );
+        let data: WatchProvidersResponse = tmdb_http::get_json(&url).await?;
+        for p in data.results {
+            if entries.iter().any(|(_, e)| e.tmdb_provider_id == p.provider_id) {
+                continue;
+            }
+            let priority = p
+                .display_priorities
+                .get(country)
+                .copied()
+                .or(p.display_priority)
+                .unwrap_or(i64::MAX);
+            entries.push((
+                priority,
+                DiscoveryProvider {
+                    tmdb_provider_id: p.provider_id,
+                    name: p.provider_name,
+                    logo_url: p.logo_path.map(|l| format!("{}{}", TMDB_LOGO, l)),
+                },
+            ));
+        }
+    }
+    entries.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.name.cmp(&b.1.name)));
+    Ok(entries.into_iter().map(|(_, p)| p).collect())
 }
 
 // --- Stream Search (TMDb multi-search) ---
@@ -222,6 +767,7 @@ pub struct StreamSearchResult {
 
 pub async fn search_streams(
     api_key: &str,
+    language: &str,
     query: &str,
     media_type: &str,
     genre_id: Option<i64>,
@@ -230,23 +776,12 @@ pub async fn search_streams(
         return Ok(Vec::new());
     }
 
-    let client = Client::new();
-    let encoded: String = query
-        .chars()
-        .map(|c| match c {
-            ' ' => "+".to_string(),
-            c if c.is_alphanumeric() || "-_.~".contains(c) => c.to_string(),
-            c => format!("%{:02X}", c as u32),
-        })
-        .collect();
-
     let url = format!(
-        "{}/search/multi?api_key={}&query={}&language=de-DE&include_adult=false",
-        TMDB_BASE, api_key, encoded
+        "{}/search/multi?api_key={}&query={}&language={}&include_adult=false",
+        endpoints::tmdb(), api_key, encode_query(query), language
     );
 
-    let resp = client.get(&url).send().await.map_err(|e| e.to_string())?;
-    let data: MultiSearchResponse = resp.json().await.map_err(|e| e.to_string())?;
+    let data: MultiSearchResponse = tmdb_http::get_json(&url).await?;
 
     let results = data.results.unwrap_or_default();
 
@@ -298,10 +833,281 @@ pub async fn search_streams(
         })
         .collect();
 
-    out.dedup_by(|a, b| a.tmdb_id == b.tmdb_id && a.media_type == b.media_type);
+    let mut seen = HashSet::new();
+    out.retain(|r| seen.insert((r.tmdb_id, r.media_type.clone())));
     Ok(out)
 }
 
+/// Prozentkodierung für Suchtexte (UTF-8, Leerzeichen als `+`).
+fn encode_query(query: &str) -> String {
+    query
+        .bytes()
+        .map(|b| match b {
+            b' ' => "+".to_string(),
+            b if b.is_ascii_alphanumeric() || b"-_.~".contains(&b) => (b as char).to_string(),
+            b => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+// --- Search ---
+
+/// Suchbare Arten; `/search/multi` kennt keine Sammlungen, daher je Art ein Endpunkt.
+pub const SEARCH_TYPES: &[&str] = &["movie", "tv", "person", "collection"];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchResult {
+    pub tmdb_id: i64,
+    /// "movie", "tv", "person" oder "collection"
+    pub media_type: String,
+    pub title: String,
+    pub year: Option<i32>,
+    pub rating: Option<f64>,
+    /// Poster, bei Personen das Profilbild.
+    pub image_url: Option<String>,
+    pub overview: String,
+    /// Personen: z.B. "Acting" oder "Directing".
+    pub known_for_department: Option<String>,
+    /// Personen: bekannteste Titel.
+    pub known_for: Vec<String>,
+    #[serde(skip)]
+    popularity: f64,
+}
+
+/// TMDb-Treffer je Art über alle Seiten.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SearchCounts {
+    pub movie: u32,
+    pub tv: u32,
+    pub person: u32,
+    pub collection: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchPage {
+    /// Suchtext ohne erkanntes Jahr.
+    pub query: String,
+    pub year: Option<i32>,
+    pub page: u32,
+    /// Größte Seitenzahl der gesuchten Arten.
+    pub total_pages: u32,
+    pub total_results: u32,
+    pub counts: SearchCounts,
+    pub results: Vec<SearchResult>,
+    /// Arten, deren Abfrage fehlgeschlagen ist (`movie`, `tv`, …); die übrigen Treffer bleiben.
+    pub failed_types: Vec<String>,
+}
+
+/// Trennt ein angehängtes Jahr ab: "Dune 1984" und "Dune (1984)" → ("Dune", 1984).
+/// Eine reine Jahreszahl bleibt Suchtext.
+pub fn split_year(query: &str) -> (String, Option<i32>) {
+    let trimmed = query.trim();
+    if let Some((rest, last)) = trimmed.rsplit_once(char::is_whitespace) {
+        let digits = last.strip_prefix('(').and_then(|l| l.strip_suffix(')')).unwrap_or(last);
+        if digits.len() == 4 {
+            if let Ok(year) = digits.parse::<i32>() {
+                if (1870..=2100).contains(&year) && !rest.trim().is_empty() {
+                    return (rest.trim().to_string(), Some(year));
+                }
+            }
+        }
+    }
+    (trimmed.to_string(), None)
+}
+
+fn parse_search_result(media_type: &str, v: &Value) -> Option<SearchResult> {
+    let popularity = v.get("popularity").and_then(|p| p.as_f64()).unwrap_or(0.0);
+    let text = |key: &str| v.get(key).and_then(|t| t.as_str()).unwrap_or("").to_string();
+    let image = |key: &str| v.get(key).and_then(|p| p.as_str()).map(|p| format!("{}{}", TMDB_IMG, p));
+
+    let item = match media_type {
+        "movie" => parse_movie(v),
+        "tv" => parse_tv(v),
+        _ => None,
+    };
+    if let Some(item) = item {
+        return Some(SearchResult {
+            tmdb_id: item.tmdb_id,
+            media_type: item.media_type,
+            title: item.title,
+            year: item.year,
+            rating: item.rating.filter(|r| *r > 0.0),
+            image_url: item.poster_url,
+            overview: item.overview,
+            known_for_department: None,
+            known_for: Vec::new(),
+            popularity,
+        });
+    }
+
+    let name = text("name");
+    if name.is_empty() || !matches!(media_type, "person" | "collection") {
+        return None;
+    }
+    let known_for = v
+        .get("known_for")
+        .and_then(|k| k.as_array())
+        .map(|k| {
+            k.iter()
+                .filter_map(|t| t.get("title").or_else(|| t.get("name")).and_then(|t| t.as_str()))
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default();
+    Some(SearchResult {
+        tmdb_id: v.get("id")?.as_i64()?,
+        media_type: media_type.to_string(),
+        title: name,
+        year: None,
+        rating: None,
+        image_url: if media_type == "person" { image("profile_path") } else { image("poster_path") },
+        overview: text("overview"),
+        known_for_department: v.get("known_for_department").and_then(|d| d.as_str()).map(String::from),
+        known_for,
+        popularity,
+    })
+}
+
+async fn search_type(
+    api_key: String,
+    language: String,
+    query: String,
+    year: Option<i32>,
+    media_type: &'static str,
+    page: u32,
+) -> Result<TmdbResultPage, String> {
+    let year_param = match (media_type, year) {
+        ("movie", Some(y)) => format!("&primary_release_year={}", y),
+        ("tv", Some(y)) => format!("&first_air_date_year={}", y),
+        _ => String::new(),
+    };
+    let url = format!(
+        "{}/search/{}?api_key={}&query={}&language={}&include_adult=false&page={}{}",
+        endpoints::tmdb(), media_type, api_key, encode_query(&query), language, page, year_param
+    );
+    tmdb_http::get_json(&url).await
+}
+
+/// Eine Seite Suchergebnisse über Filme, Serien, Personen und Sammlungen (`types` leer = alle).
+/// Die Arten werden parallel mit derselben Seitenzahl abgefragt und abwechselnd gemischt.
+/// Eine angehängte Jahreszahl ohne Klammern kann zum Titel gehören ("Blade Runner 2049"):
+/// Finden die gewählten Filme bzw. Serien damit nichts, wird ohne Jahr mit dem ganzen Text gesucht.
+pub async fn search_tmdb(
+    api_key: &str,
+    language: &str,
+    query: &str,
+    types: &[String],
+    page: u32,
+) -> Result<SearchPage, String> {
+    let mut selected: Vec<&'static str> = Vec::new();
+    for t in types {
+        let t = if t == "series" { "tv" } else { t.as_str() };
+        let known = SEARCH_TYPES
+            .iter()
+            .find(|s| **s == t)
+            .ok_or_else(|| format!("Unbekannte Suchart: {}", t))?;
+        if !selected.contains(known) {
+            selected.push(known);
+        }
+    }
+    if selected.is_empty() {
+        selected = SEARCH_TYPES.to_vec();
+    }
+    let page = page.clamp(1, MAX_TMDB_PAGE);
+
+    let (text, year) = split_year(query);
+    let result = search_all_types(api_key, language, text, year, &selected, page).await?;
+    let bare_year = year.is_some() && !query.trim_end().ends_with(')');
+    let dated = |t: &str| matches!(t, "movie" | "tv");
+    let retry = bare_year
+        && selected.iter().any(|t| dated(t))
+        && !result.failed_types.iter().any(|t| dated(t))
+        && result.counts.movie == 0
+        && result.counts.tv == 0;
+    if retry {
+        return search_all_types(api_key, language, query.trim().to_string(), None, &selected, page).await;
+    }
+    Ok(result)
+}
+
+async fn search_all_types(
+    api_key: &str,
+    language: &str,
+    text: String,
+    year: Option<i32>,
+    selected: &[&'static str],
+    page: u32,
+) -> Result<SearchPage, String> {
+    let mut result = SearchPage {
+        query: text.clone(),
+        year,
+        page,
+        total_pages: 0,
+        total_results: 0,
+        counts: SearchCounts::default(),
+        results: Vec::new(),
+        failed_types: Vec::new(),
+    };
+    if text.is_empty() {
+        return Ok(result);
+    }
+
+    let mut tasks = JoinSet::new();
+    for &media_type in selected {
+        let request = search_type(api_key.to_string(), language.to_string(), text.clone(), year, media_type, page);
+        tasks.spawn(async move { (media_type, request.await) });
+    }
+
+    let mut fetched = Vec::new();
+    let mut first_error = None;
+    while let Some(joined) = tasks.join_next().await {
+        let (media_type, data) = joined.map_err(|e| e.to_string())?;
+        match data {
+            Ok(data) => fetched.push((media_type, data)),
+            Err(e) => {
+                log::warn!("TMDb-Suche nach {} fehlgeschlagen: {}", media_type, e);
+                result.failed_types.push(media_type.to_string());
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    // Nur wenn alle Arten scheitern, scheitert die Suche.
+    if fetched.is_empty() {
+        if let Some(e) = first_error {
+            return Err(e);
+        }
+    }
+    result.failed_types.sort_by_key(|t| SEARCH_TYPES.iter().position(|s| s == t));
+    // Feste Reihenfolge, damit gleich eingestufte Treffer stabil sortiert bleiben.
+    fetched.sort_by_key(|(media_type, _)| SEARCH_TYPES.iter().position(|t| t == media_type));
+
+    let mut seen = HashSet::new();
+    let mut ranked = Vec::new();
+    for (media_type, data) in fetched {
+        match media_type {
+            "movie" => result.counts.movie = data.total_results,
+            "tv" => result.counts.tv = data.total_results,
+            "person" => result.counts.person = data.total_results,
+            _ => result.counts.collection = data.total_results,
+        }
+        result.total_results += data.total_results;
+        result.total_pages = result.total_pages.max(data.total_pages.min(MAX_TMDB_PAGE));
+        ranked.extend(
+            data.results
+                .iter()
+                .filter_map(|v| parse_search_result(media_type, v))
+                .filter(|r| seen.insert((r.tmdb_id, r.media_type.clone())))
+                .enumerate(),
+        );
+    }
+
+    // Sammlungen haben keine Popularität: nach Rang innerhalb der Art mischen,
+    // erst bei gleichem Rang entscheidet die Popularität.
+    ranked.sort_by(|(rank_a, a), (rank_b, b)| rank_a.cmp(rank_b).then(b.popularity.total_cmp(&a.popularity)));
+    result.results = ranked.into_iter().map(|(_, r)| r).collect();
+    Ok(result)
+}
+
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct TmdbGenre {
     pub id: i64,
@@ -320,17 +1126,15 @@ struct GenreEntry {
     name: String,
 }
 
-pub async fn get_tmdb_genres(api_key: &str) -> Result<Vec<TmdbGenre>, String> {
-    let client = Client::new();
+pub async fn get_tmdb_genres(api_key: &str, language: &str) -> Result<Vec<TmdbGenre>, String> {
     let mut out = Vec::new();
 
     for (endpoint, mt) in [("movie", "movie"), ("tv", "tv")] {
         let url = format!(
-            "{}/genre/{}/list?api_key={}&language=de-DE",
-            TMDB_BASE, endpoint, api_key
+            "{}/genre/{}/list?api_key={}&language={}",
+            endpoints::tmdb(), endpoint, api_key, language
         );
-        let resp = client.get(&url).send().await.map_err(|e| e.to_string())?;
-        let data: GenreListResponse = resp.json().await.map_err(|e| e.to_string())?;
+        let data: GenreListResponse = tmdb_http::get_json(&url).await?;
         for g in data.genres {
             out.push(TmdbGenre {
                 id: g.id,
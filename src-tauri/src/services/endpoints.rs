@@ -0,0 +1,88 @@
+//! Basis-URLs der externen Dienste.
+//!
+//! Standard sind die echten Adressen. Der Test-Harness (`test-harness/`) lenkt
+//! sie mit [`set`] auf lokale Ersatz-Server um. Bild-URLs (image.tmdb.org)
+//! gehören nicht dazu, sie landen so in der Datenbank und im Frontend.
+
+use std::sync::RwLock;
+
+const TMDB: &str = "https://api.themoviedb.org/3";
+const REAL_DEBRID: &str = "https://api.real-debrid.com";
+const TORRENTIO: &str = "https://torrentio.strem.fun";
+const PERPLEXITY: &str = "https://api.perplexity.ai";
+const OMDB: &str = "https://www.omdbapi.com";
+const TVMAZE: &str = "https://api.tvmaze.com";
+/// YTS-Spiegel, der Reihe nach versucht.
+const YTS: &[&str] = &["https://yts.mx/api/v2", "https://yts.lt/api/v2"];
+
+/// Ersetzte Adressen; `None` bei Feldern, die beim Standard bleiben.
+#[derive(Debug, Clone, Default)]
+pub struct Endpoints {
+    pub tmdb: Option<String>,
+    pub real_debrid: Option<String>,
+    pub torrentio: Option<String>,
+    pub perplexity: Option<String>,
+    pub omdb: Option<String>,
+    pub tvmaze: Option<String>,
+    pub yts: Option<String>,
+}
+
+static OVERRIDES: RwLock<Option<Endpoints>> = RwLock::new(None);
+
+fn resolve(pick: fn(&Endpoints) -> Option<&String>, default: &str) -> String {
+    OVERRIDES
+        .read()
+        .ok()
+        .and_then(|o| o.as_ref().and_then(pick).map(|url| url.trim_end_matches('/').to_string()))
+        .unwrap_or_else(|| default.to_string())
+}
+
+/// TMDb-API inklusive Version, z. B. `https://api.themoviedb.org/3`.
+pub fn tmdb() -> String {
+    resolve(|e| e.tmdb.as_ref(), TMDB)
+}
+
+/// RealDebrid ohne Pfad; OAuth liegt unter `/oauth/v2`, die REST-API unter `/rest/1.0`.
+pub fn real_debrid() -> String {
+    resolve(|e| e.real_debrid.as_ref(), REAL_DEBRID)
+}
+
+pub fn torrentio() -> String {
+    resolve(|e| e.torrentio.as_ref(), TORRENTIO)
+}
+
+pub fn perplexity() -> String {
+    resolve(|e| e.perplexity.as_ref(), PERPLEXITY)
+}
+
+pub fn omdb() -> String {
+    resolve(|e| e.omdb.as_ref(), OMDB)
+}
+
+pub fn tvmaze() -> String {
+    resolve(|e| e.tvmaze.as_ref(), TVMAZE)
+}
+
+/// YTS-API inklusive Version. Mit Ersatz-Adresse nur diese, sonst alle Spiegel.
+pub fn yts() -> Vec<String> {
+    match OVERRIDES.read().ok().and_then(|o| o.as_ref().and_then(|e| e.yts.clone())) {
+        Some(url) => vec![url.trim_end_matches('/').to_string()],
+        None => YTS.iter().map(|url| url.to_string()).collect(),
+    }
+}
+
+/// Ersetzt die Adressen prozessweit. Nur für den Test-Harness.
+#[cfg(feature = "test-harness")]
+pub fn set(endpoints: Endpoints) {
+    if let Ok(mut o) = OVERRIDES.write() {
+        *o = Some(endpoints);
+    }
+}
+
+/// Zurück zu den echten Adressen.
+#[cfg(feature = "test-harness")]
+pub fn reset() {
+    if let Ok(mut o) = OVERRIDES.write() {
+        *o = None;
+    }
+}
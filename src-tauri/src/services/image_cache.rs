@@ -0,0 +1,272 @@
+//! Lokaler Cache für Poster, Backdrops und Profilfotos (analog zu `music-covers`).
+//!
+//! Bilder liegen unter `<app_data_dir>/image-cache`, der Index in der Tabelle
+//! `image_cache`. Jede Größenvariante ist ein eigener Eintrag (Schlüssel ist die
+//! Variant-URL). Wird die Obergrenze überschritten, fliegen die am längsten nicht
+//! benutzten Bilder raus.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use reqwest::{Client, Url};
+use sha2::{Digest, Sha256};
+use sqlx::Row;
+
+use crate::db::DbPool;
+
+pub const CACHE_DIR_NAME: &str = "image-cache";
+pub const DEFAULT_MAX_MB: i64 = 500;
+
+const TMDB_IMG_PREFIX: &str = "https://image.tmdb.org/t/p/";
+
+/// Bildquellen der Metadaten-Provider (TMDb, TVmaze, OMDb-Poster). Andere URLs
+/// werden nicht heruntergeladen.
+const ALLOWED_HOSTS: &[&str] = &["image.tmdb.org", "static.tvmaze.com", "m.media-amazon.com"];
+
+/// Größen, die beim Massen-Download je Bildart vorgehalten werden.
+pub const POSTER_SIZES: &[&str] = &["w342", "w500"];
+pub const BACKDROP_SIZES: &[&str] = &["w780", "w1280"];
+pub const PROFILE_SIZES: &[&str] = &["w185"];
+pub const STILL_SIZES: &[&str] = &["w300"];
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ImageCacheStats {
+    pub files: i64,
+    pub bytes: i64,
+    pub max_bytes: i64,
+}
+
+pub fn cache_dir(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join(CACHE_DIR_NAME)
+}
+
+/// Obergrenze aus der Einstellung `image_cache_max_mb`.
+pub fn max_bytes(settings: &std::collections::HashMap<String, String>) -> i64 {
+    settings
+        .get("image_cache_max_mb")
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .filter(|mb| *mb > 0)
+        .unwrap_or(DEFAULT_MAX_MB)
+        * 1024
+        * 1024
+}
+
+/// Tauscht bei TMDb-URLs die Größe aus ("…/t/p/w500/abc.jpg" -> "…/t/p/w185/abc.jpg").
+/// Andere URLs kennen keine Varianten und bleiben unverändert.
+pub fn variant_url(url: &str, size: &str) -> String {
+    match url.strip_prefix(TMDB_IMG_PREFIX) {
+        Some(rest) => match rest.split_once('/') {
+            Some((_, path)) => format!("{}{}/{}", TMDB_IMG_PREFIX, size, path),
+            None => url.to_string(),
+        },
+        None => url.to_string(),
+    }
+}
+
+/// `true` für HTTPS-URLs auf einem der [`ALLOWED_HOSTS`].
+pub fn is_allowed_url(url: &str) -> bool {
+    Url::parse(url).is_ok_and(|u| {
+        u.scheme() == "https" && u.host_str().is_some_and(|host| ALLOWED_HOSTS.contains(&host))
+    })
+}
+
+fn file_name_for(url: &str) -> String {
+    let hash = format!("{:x}", Sha256::digest(url.as_bytes()));
+    let ext = url
+        .rsplit('/')
+        .next()
+        .and_then(|name| name.rsplit_once('.'))
+        .map(|(_, ext)| ext.to_lowercase())
+        .filter(|ext| matches!(ext.as_str(), "jpg" | "jpeg" | "png" | "webp"))
+        .unwrap_or_else(|| "jpg".to_string());
+    format!("{}.{}", &hash[..24], ext)
+}
+
+/// Lokaler Pfad, falls das Bild schon im Cache liegt (ohne Netzwerk).
+pub async fn lookup(pool: &DbPool, url: &str) -> Result<Option<PathBuf>, String> {
+    let row = sqlx::query("SELECT file_path FROM image_cache WHERE url = ?1")
+        .bind(url)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    let path = match row {
+        Some(r) => PathBuf::from(r.get::<String, _>("file_path")),
+        None => return Ok(None),
+    };
+    if !path.is_file() {
+        // Datei wurde von aussen gelöscht – Eintrag aufräumen.
+        sqlx::query("DELETE FROM image_cache WHERE url = ?1")
+            .bind(url)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        return Ok(None);
+    }
+    sqlx::query("UPDATE image_cache SET last_accessed = datetime('now') WHERE url = ?1")
+        .bind(url)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(Some(path))
+}
+
+/// Liefert den lokalen Pfad und lädt das Bild bei Bedarf herunter. Räumt nicht
+/// auf, dafür ist [`evict`] nach dem Abruf bzw. nach einem ganzen Stapel da.
+pub async fn get_or_fetch(
+    pool: &DbPool,
+    client: &Client,
+    dir: &Path,
+    url: &str,
+) -> Result<PathBuf, String> {
+    if let Some(path) = lookup(pool, url).await? {
+        return Ok(path);
+    }
+    if !is_allowed_url(url) {
+        return Err(format!("Bildquelle nicht erlaubt: {}", url));
+    }
+
+    let resp = client.get(url).send().await.map_err(|e| e.to_string())?;
+    if !resp.status().is_success() {
+        return Err(format!("Bild konnte nicht geladen werden ({})", resp.status()));
+    }
+    let bytes = resp.bytes().await.map_err(|e| e.to_string())?;
+
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let path = dir.join(file_name_for(url));
+    std::fs::write(&path, &bytes).map_err(|e| e.to_string())?;
+
+    sqlx::query(
+        "INSERT INTO image_cache (url, file_path, bytes, created_at, last_accessed)
+         VALUES (?1, ?2, ?3, datetime('now'), datetime('now'))
+         ON CONFLICT(url) DO UPDATE SET file_path = excluded.file_path, bytes = excluded.bytes,
+           last_accessed = datetime('now')"
+    )
+    .bind(url)
+    .bind(path.to_string_lossy().to_string())
+    .bind(bytes.len() as i64)
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(path)
+}
+
+/// Entfernt die am längsten nicht benutzten Bilder, bis der Cache unter `max_bytes` liegt.
+/// `keep` bleibt in jedem Fall erhalten, z.B. das gerade ausgelieferte Bild.
+pub async fn evict(pool: &DbPool, max_bytes: i64, keep: Option<&str>) -> Result<u32, String> {
+    let total: i64 = sqlx::query("SELECT COALESCE(SUM(bytes), 0) AS total FROM image_cache")
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .get("total");
+    if total <= max_bytes {
+        return Ok(0);
+    }
+
+    let rows = sqlx::query(
+        "SELECT url, file_path, bytes FROM image_cache
+         WHERE ?1 IS NULL OR url <> ?1
+         ORDER BY last_accessed, created_at"
+    )
+        .bind(keep)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut remaining = total;
+    let mut removed = 0u32;
+    for row in rows {
+        if remaining <= max_bytes {
+            break;
+        }
+        let url: String = row.get("url");
+        let file_path: String = row.get("file_path");
+        let bytes: i64 = row.get("bytes");
+        let _ = std::fs::remove_file(&file_path);
+        sqlx::query("DELETE FROM image_cache WHERE url = ?1")
+            .bind(&url)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        remaining -= bytes;
+        removed += 1;
+    }
+    log::info!("Image cache: evicted {} files", removed);
+    Ok(removed)
+}
+
+pub async fn stats(pool: &DbPool, max_bytes: i64) -> Result<ImageCacheStats, String> {
+    let row = sqlx::query("SELECT COUNT(*) AS files, COALESCE(SUM(bytes), 0) AS bytes FROM image_cache")
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(ImageCacheStats {
+        files: row.get("files"),
+        bytes: row.get("bytes"),
+        max_bytes,
+    })
+}
+
+pub async fn clear(pool: &DbPool, dir: &Path) -> Result<(), String> {
+    sqlx::query("DELETE FROM image_cache")
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    if dir.is_dir() {
+        std::fs::remove_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Alle Bild-URLs der Bibliothek samt der vorzuhaltenden Größen.
+pub async fn library_image_urls(pool: &DbPool) -> Result<Vec<String>, String> {
+    let mut urls = Vec::new();
+    let mut seen = HashSet::new();
+    let mut push = |url: &str, sizes: &[&str]| {
+        for size in sizes {
+            let v = variant_url(url, size);
+            if seen.insert(v.clone()) {
+                urls.push(v);
+            }
+        }
+    };
+
+    let rows = sqlx::query("SELECT poster_url, backdrop_url, cast_crew FROM metadata")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    for row in &rows {
+        if let Some(url) = row.get::<Option<String>, _>("poster_url") {
+            push(&url, POSTER_SIZES);
+        }
+        if let Some(url) = row.get::<Option<String>, _>("backdrop_url") {
+            push(&url, BACKDROP_SIZES);
+        }
+        let cast_crew: Option<String> = row.get("cast_crew");
+        let profiles = cast_crew
+            .and_then(|json| serde_json::from_str::<serde_json::Value>(&json).ok())
+            .map(|v| {
+                ["cast", "crew"]
+                    .iter()
+                    .filter_map(|k| v.get(*k).and_then(|a| a.as_array()).cloned())
+                    .flatten()
+                    .filter_map(|m| m.get("profile_url").and_then(|p| p.as_str()).map(String::from))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        for url in profiles {
+            push(&url, PROFILE_SIZES);
+        }
+    }
+
+    let stills = sqlx::query("SELECT still_url FROM episode_metadata WHERE still_url IS NOT NULL")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    for row in &stills {
+        let url: String = row.get("still_url");
+        push(&url, STILL_SIZES);
+    }
+
+    Ok(urls)
+}
@@ -0,0 +1,152 @@
+//! Bevorzugte Metadaten-Sprache und Fallback-Kette für TMDb.
+//!
+//! Einstellungen:
+//! - `metadata_language` – bevorzugte Sprache als BCP-47-Tag (Standard `de-DE`)
+//! - `metadata_language_fallbacks` – kommagetrennte Fallbacks (Standard `en-US`)
+//!
+//! Nach der Kette kommt immer noch die Originalsprache des Titels.
+
+use std::collections::HashMap;
+
+pub const DEFAULT_LANGUAGE: &str = "de-DE";
+pub const DEFAULT_FALLBACKS: &str = "en-US";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanguagePrefs {
+    chain: Vec<String>,
+}
+
+impl Default for LanguagePrefs {
+    fn default() -> Self {
+        Self::new(DEFAULT_LANGUAGE, DEFAULT_FALLBACKS)
+    }
+}
+
+impl LanguagePrefs {
+    pub fn new(preferred: &str, fallbacks: &str) -> Self {
+        let mut chain: Vec<String> = Vec::new();
+        for tag in std::iter::once(preferred).chain(fallbacks.split(',')) {
+            let tag = normalize_tag(tag);
+            if !tag.is_empty() && !chain.contains(&tag) {
+                chain.push(tag);
+            }
+        }
+        if chain.is_empty() {
+            chain.push(DEFAULT_LANGUAGE.to_string());
+        }
+        Self { chain }
+    }
+
+    pub fn from_settings(settings: &HashMap<String, String>) -> Self {
+        let preferred = settings
+            .get("metadata_language")
+            .map(|s| s.as_str())
+            .filter(|s| !s.trim().is_empty())
+            .unwrap_or(DEFAULT_LANGUAGE);
+        let fallbacks = settings
+            .get("metadata_language_fallbacks")
+            .map(|s| s.as_str())
+            .unwrap_or(DEFAULT_FALLBACKS);
+        Self::new(preferred, fallbacks)
+    }
+
+    /// Sprache für den `language`-Parameter der TMDb-Anfragen.
+    pub fn primary(&self) -> &str {
+        &self.chain[0]
+    }
+
+    pub fn chain(&self) -> &[String] {
+        &self.chain
+    }
+
+    /// Wert für `include_image_language`, z.B. `de,en,null` (null = Bilder ohne Text).
+    pub fn include_image_language(&self) -> String {
+        let mut langs: Vec<&str> = Vec::new();
+        for tag in &self.chain {
+            let lang = iso_639_1(tag);
+            if !langs.contains(&lang) {
+                langs.push(lang);
+            }
+        }
+        langs.push("null");
+        langs.join(",")
+    }
+
+    /// Rang einer Übersetzung (kleiner = besser). Innerhalb einer Sprache gewinnt
+    /// die passende Region; `original_language` kommt direkt nach der Kette.
+    pub fn rank(
+        &self,
+        iso_639_1_code: Option<&str>,
+        iso_3166_1_code: Option<&str>,
+        original_language: Option<&str>,
+    ) -> usize {
+        let code = match iso_639_1_code {
+            Some(c) => c,
+            None => return (self.chain.len() + 2) * 2,
+        };
+        if let Some(pos) = self.chain.iter().position(|t| iso_639_1(t) == code) {
+            let region_match = iso_3166_1(&self.chain[pos]) == iso_3166_1_code;
+            return pos * 2 + usize::from(!region_match);
+        }
+        if original_language == Some(code) {
+            return self.chain.len() * 2;
+        }
+        (self.chain.len() + 1) * 2
+    }
+
+    /// Ob eine Übersetzung überhaupt zur Kette (oder zur Originalsprache) gehört.
+    pub fn wants(&self, iso_639_1_code: &str, original_language: Option<&str>) -> bool {
+        original_language == Some(iso_639_1_code)
+            || self.chain.iter().any(|t| iso_639_1(t) == iso_639_1_code)
+    }
+
+    /// Bester nicht-leerer Wert von `field` aus `translations.translations` einer
+    /// TMDb-Antwort mit `append_to_response=translations`.
+    pub fn pick_translated(&self, raw: &serde_json::Value, field: &str) -> Option<String> {
+        let original = raw.get("original_language").and_then(|v| v.as_str());
+        let mut best: Option<(usize, String)> = None;
+        for t in raw
+            .pointer("/translations/translations")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+        {
+            let value = match t
+                .pointer(&format!("/data/{}", field))
+                .and_then(|v| v.as_str())
+                .filter(|v| !v.trim().is_empty())
+            {
+                Some(v) => v,
+                None => continue,
+            };
+            let rank = self.rank(
+                t.get("iso_639_1").and_then(|v| v.as_str()),
+                t.get("iso_3166_1").and_then(|v| v.as_str()),
+                original,
+            );
+            if best.as_ref().map(|(r, _)| rank < *r).unwrap_or(true) {
+                best = Some((rank, value.to_string()));
+            }
+        }
+        best.map(|(_, v)| v)
+    }
+}
+
+/// "en_us" / "EN-us" -> "en-US"
+fn normalize_tag(tag: &str) -> String {
+    let tag = tag.trim().replace('_', "-");
+    match tag.split_once('-') {
+        Some((lang, region)) => format!("{}-{}", lang.to_lowercase(), region.to_uppercase()),
+        None => tag.to_lowercase(),
+    }
+}
+
+/// "de-DE" -> "de"
+pub fn iso_639_1(tag: &str) -> &str {
+    tag.split('-').next().unwrap_or(tag)
+}
+
+/// "de-DE" -> Some("DE")
+pub fn iso_3166_1(tag: &str) -> Option<&str> {
+    tag.split_once('-').map(|(_, region)| region)
+}
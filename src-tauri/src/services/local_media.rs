@@ -1,5 +1,8 @@
+use super::media_kind;
+use regex::Regex;
 use sha2::{Sha256, Digest};
 use std::path::Path;
+use std::sync::OnceLock;
 use walkdir::WalkDir;
 
 fn normalize_path(path: &str) -> String {
@@ -41,6 +44,7 @@ pub struct ScannedFile {
     pub file_size: i64,
     pub file_hash: String,
     pub media_type: String,
+    pub media_kind: String,
 }
 
 #[derive(serde::Serialize)]
@@ -188,6 +192,7 @@ pub fn scan_directories(paths: &[String]) -> Vec<ScannedFile> {
             let file_hash = compute_partial_hash(entry_path).unwrap_or_default();
             let title = extract_title(entry_path);
             let media_type = detect_media_type(entry_path);
+            let media_kind = media_kind::detect_media_kind(entry_path, &media_type);
 
             results.push(ScannedFile {
                 file_path: entry_path.to_string_lossy().to_string(),
@@ -195,6 +200,7 @@ pub fn scan_directories(paths: &[String]) -> Vec<ScannedFile> {
                 file_size,
                 file_hash,
                 media_type,
+                media_kind,
             });
         }
     }
@@ -253,6 +259,7 @@ where
             let file_hash = compute_partial_hash(entry_path).unwrap_or_default();
             let title = extract_title(entry_path);
             let media_type = detect_media_type(entry_path);
+            let media_kind = media_kind::detect_media_kind(entry_path, &media_type);
 
             on_file(ScannedFile {
                 file_path: entry_path.to_string_lossy().to_string(),
@@ -260,6 +267,7 @@ where
                 file_size,
                 file_hash,
                 media_type,
+                media_kind,
             });
         }
     }
@@ -286,6 +294,33 @@ fn extract_title(path: &Path) -> String {
         .replace('-', " ")
 }
 
+/// Staffel und Folge aus dem Titel, z.B. "Lost S01E02" -> (1, 2).
+/// Nur die Folgennummer ("Ep 5") gilt als Staffel 1.
+pub fn parse_episode(title: &str) -> Option<(u32, u32)> {
+    static PATTERNS: OnceLock<Vec<Regex>> = OnceLock::new();
+    let patterns = PATTERNS.get_or_init(|| {
+        [
+            r"[Ss](\d{1,2})[Ee](\d{1,3})",
+            r"(?i)(?:season|staffel)\s*(\d{1,2})\s*(?:episode|folge)\s*(\d{1,3})",
+            r"\b(\d{1,2})[xX](\d{1,3})\b",
+            r"(?i)\b(?:ep|episode|folge)\s*(\d{1,3})\b",
+        ]
+        .iter()
+        .map(|pattern| Regex::new(pattern).unwrap())
+        .collect()
+    });
+    for re in patterns {
+        if let Some(caps) = re.captures(title) {
+            let first: u32 = caps.get(1)?.as_str().parse().ok()?;
+            return match caps.get(2) {
+                Some(ep) => Some((first, ep.as_str().parse().ok()?)),
+                None => Some((1, first)),
+            };
+        }
+    }
+    None
+}
+
 fn detect_media_type(path: &Path) -> String {
     let path_str = path.to_string_lossy().to_lowercase();
     // S01E01, S1E1, 1x01, 2x05, Episode 1, Season 1, Staffel 1, Folge 1
@@ -0,0 +1,295 @@
+//! Bewertet Suchtreffer der Metadaten-Provider gegen die lokale Datei.
+//!
+//! Statt blind den ersten Treffer zu nehmen, bekommt jeder Kandidat eine
+//! Konfidenz aus Titelähnlichkeit, Jahresabstand, Medientyp und bei Filmen Laufzeit
+//! (gegen die per ffprobe ermittelte Dateidauer). Unsichere Treffer landen in
+//! der Review-Queue.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use super::metadata_provider::MetadataResult;
+
+/// Ab dieser Konfidenz wird ein Treffer ohne Rückfrage übernommen.
+pub const AUTO_ACCEPT_THRESHOLD: f64 = 0.75;
+/// Liegt der Zweitplatzierte so nah am Besten, ist der Treffer mehrdeutig.
+pub const AMBIGUITY_MARGIN: f64 = 0.05;
+
+pub const STATUS_AUTO: &str = "auto";
+pub const STATUS_NEEDS_REVIEW: &str = "needs_review";
+pub const STATUS_CONFIRMED: &str = "confirmed";
+
+const WEIGHT_TITLE: f64 = 0.5;
+const WEIGHT_YEAR: f64 = 0.25;
+const WEIGHT_RUNTIME: f64 = 0.15;
+const WEIGHT_TYPE: f64 = 0.1;
+
+/// Was wir über die lokale Datei wissen.
+#[derive(Debug, Clone)]
+pub struct MatchInput {
+    pub title: String,
+    pub year: Option<u16>,
+    pub media_type: String,
+    pub duration_minutes: Option<i64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScoredCandidate {
+    pub result: MetadataResult,
+    pub score: f64,
+}
+
+/// Kleinschreibung, Satzzeichen raus, führende Artikel weg.
+fn normalize_title(title: &str) -> String {
+    let cleaned: String = title
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+    let words: Vec<&str> = cleaned.split_whitespace().collect();
+    let words = match words.first() {
+        Some(&("the" | "a" | "an" | "der" | "die" | "das")) if words.len() > 1 => &words[1..],
+        _ => &words[..],
+    };
+    words.join(" ")
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for i in 1..=a.len() {
+        cur[0] = i;
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            cur[j] = (prev[j] + 1).min(cur[j - 1] + 1).min(prev[j - 1] + cost);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+/// 0.0 – 1.0; 1.0 bei identischem (normalisiertem) Titel.
+pub fn title_similarity(a: &str, b: &str) -> f64 {
+    let a = normalize_title(a);
+    let b = normalize_title(b);
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    if a == b {
+        return 1.0;
+    }
+    let max_len = a.chars().count().max(b.chars().count()) as f64;
+    let edit = 1.0 - levenshtein(&a, &b) as f64 / max_len;
+
+    // Gemeinsame Wörter im Verhältnis zu allen Wörtern beider Titel: umgestellte
+    // Titel zählen, ein einzelnes Wort macht "Alien" aber nicht zu "Alien vs. Predator".
+    // Bleibt unter einem exakten Treffer.
+    let wa: HashSet<&str> = a.split_whitespace().collect();
+    let wb: HashSet<&str> = b.split_whitespace().collect();
+    let common = wa.intersection(&wb).count() as f64;
+    let overlap = 0.85 * common / wa.union(&wb).count() as f64;
+
+    edit.max(overlap)
+}
+
+fn year_score(expected: Option<u16>, release_date: Option<&str>) -> Option<f64> {
+    let expected = expected?;
+    let found: u16 = release_date?.get(..4)?.parse().ok()?;
+    Some(match expected.abs_diff(found) {
+        0 => 1.0,
+        1 => 0.7,
+        2 => 0.3,
+        _ => 0.0,
+    })
+}
+
+fn runtime_score(duration: Option<i64>, runtime: Option<i64>) -> Option<f64> {
+    let duration = duration.filter(|d| *d > 0)?;
+    let runtime = runtime.filter(|r| *r > 0)?;
+    let diff = (duration - runtime).abs() as f64;
+    // Bis 5 Minuten Abweichung (Abspann, Schnittfassung) voll, ab 40 % nichts mehr.
+    if diff <= 5.0 {
+        return Some(1.0);
+    }
+    Some((1.0 - diff / (runtime as f64 * 0.4)).max(0.0))
+}
+
+/// Konfidenz eines Kandidaten. Unbekannte Kriterien fliessen nicht ein, die
+/// übrigen Gewichte werden entsprechend hochskaliert.
+pub fn score_candidate(input: &MatchInput, candidate: &MetadataResult) -> f64 {
+    let mut parts = vec![(WEIGHT_TITLE, title_similarity(&input.title, &candidate.title))];
+    if let Some(s) = year_score(input.year, candidate.release_date.as_deref()) {
+        parts.push((WEIGHT_YEAR, s));
+    }
+    if let Some(s) = runtime_score(input.duration_minutes, candidate.runtime) {
+        parts.push((WEIGHT_RUNTIME, s));
+    }
+    // Provider filtern bereits nach Typ; ein Treffer mit anderem Typ kostet trotzdem.
+    let type_ok = candidate.media_type == input.media_type;
+    parts.push((WEIGHT_TYPE, if type_ok { 1.0 } else { 0.0 }));
+
+    let total: f64 = parts.iter().map(|(w, _)| w).sum();
+    parts.iter().map(|(w, s)| w * s).sum::<f64>() / total
+}
+
+/// Alle Kandidaten bewerten, bester zuerst.
+pub fn rank_candidates(input: &MatchInput, results: Vec<MetadataResult>) -> Vec<ScoredCandidate> {
+    let mut scored: Vec<ScoredCandidate> = results
+        .into_iter()
+        .map(|result| ScoredCandidate {
+            score: score_candidate(input, &result),
+            result,
+        })
+        .collect();
+    scored.sort_by(|a, b| b.score.total_cmp(&a.score));
+    scored
+}
+
+/// `true`, wenn der beste Kandidat zu unsicher oder nicht eindeutig ist.
+pub fn needs_review(ranked: &[ScoredCandidate]) -> bool {
+    match ranked {
+        [] => false,
+        [best] => best.score < AUTO_ACCEPT_THRESHOLD,
+        [best, second, ..] => {
+            best.score < AUTO_ACCEPT_THRESHOLD || best.score - second.score < AMBIGUITY_MARGIN
+        }
+    }
+}
+
+/// Dauer der Datei in Minuten über `ffprobe`. `None`, wenn ffprobe fehlt oder
+/// die Datei nicht lesbar ist.
+pub async fn probe_duration_minutes(path: &str) -> Option<i64> {
+    if !Path::new(path).is_file() {
+        return None;
+    }
+    let output = tokio::process::Command::new("ffprobe")
+        .args([
+            "-v",
+            "error",
+            "-show_entries",
+            "format=duration",
+            "-of",
+            "default=noprint_wrappers=1:nokey=1",
+        ])
+        .arg(path)
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let secs: f64 = String::from_utf8_lossy(&output.stdout).trim().parse().ok()?;
+    Some((secs / 60.0).round() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(provider: &str, title: &str, release_date: &str, runtime: Option<i64>) -> MetadataResult {
+        MetadataResult {
+            provider: provider.into(),
+            provider_id: "1".into(),
+            media_type: "movie".into(),
+            title: title.into(),
+            overview: String::new(),
+            poster_url: None,
+            backdrop_url: None,
+            release_date: Some(release_date.into()),
+            ratings: Vec::new(),
+            runtime,
+            genres: Vec::new(),
+            cast_crew: None,
+            raw_response: String::new(),
+            field_sources: Default::default(),
+            language: None,
+            translations: Vec::new(),
+            external_ids: Default::default(),
+        }
+    }
+
+    #[test]
+    fn identical_titles_ignore_case_punctuation_and_articles() {
+        assert_eq!(title_similarity("The Matrix", "matrix"), 1.0);
+        assert_eq!(title_similarity("Spider-Man: No Way Home", "spider man no way home"), 1.0);
+        assert_eq!(title_similarity("", "Alien"), 0.0);
+    }
+
+    #[test]
+    fn one_shared_word_is_no_strong_match() {
+        assert!(title_similarity("Alien", "Alien vs. Predator") < 0.5);
+        assert!(title_similarity("Alien vs. Predator", "Alien") < 0.5);
+        // Doppelte Wörter zählen nur einmal.
+        assert!(title_similarity("New York New York", "New York Stories") < 0.6);
+    }
+
+    #[test]
+    fn reordered_and_misspelled_titles_stay_close() {
+        let reordered = title_similarity("Harry Potter Deathly Hallows", "Deathly Hallows Harry Potter");
+        assert!((reordered - 0.85).abs() < 1e-9);
+        assert!(title_similarity("Interstellar", "Interstelar") > 0.9);
+        assert!(title_similarity("Dune", "Dune") > title_similarity("Dune", "Dune Part One"));
+    }
+
+    #[test]
+    fn year_and_runtime_decide_between_remakes() {
+        let input = MatchInput {
+            title: "Dune".into(),
+            year: Some(2021),
+            media_type: "movie".into(),
+            duration_minutes: Some(155),
+        };
+        let ranked = rank_candidates(
+            &input,
+            vec![
+                candidate("tmdb", "Dune", "1984-12-14", Some(137)),
+                candidate("tmdb", "Dune", "2021-09-15", Some(155)),
+            ],
+        );
+        assert_eq!(ranked[0].result.release_date.as_deref(), Some("2021-09-15"));
+        assert!((ranked[0].score - 1.0).abs() < 1e-9);
+        assert!(!needs_review(&ranked));
+    }
+
+    #[test]
+    fn close_or_weak_candidates_need_review() {
+        let input = MatchInput {
+            title: "Alien".into(),
+            year: None,
+            media_type: "movie".into(),
+            duration_minutes: None,
+        };
+        let weak = rank_candidates(&input, vec![candidate("tmdb", "Alien vs. Predator", "2004-08-12", None)]);
+        assert!(needs_review(&weak));
+
+        let tied = rank_candidates(
+            &input,
+            vec![candidate("tmdb", "Alien", "1979-05-25", None), candidate("tmdb", "Alien", "2003-10-31", None)],
+        );
+        assert!(needs_review(&tied));
+        assert!(!needs_review(&[]));
+    }
+
+    #[test]
+    fn wrong_media_type_costs_for_every_provider() {
+        let movie = MatchInput {
+            title: "Fargo".into(),
+            year: None,
+            media_type: "movie".into(),
+            duration_minutes: None,
+        };
+        let series = MatchInput { media_type: "series".into(), ..movie.clone() };
+        for provider in ["tmdb", "omdb", "tvmaze"] {
+            let show = MetadataResult {
+                media_type: "series".into(),
+                ..candidate(provider, "Fargo", "2014-04-15", None)
+            };
+            assert!(score_candidate(&movie, &show) < 1.0, "{provider}");
+            assert!((score_candidate(&series, &show) - 1.0).abs() < 1e-9, "{provider}");
+        }
+        let film = candidate("omdb", "Fargo", "1996-03-08", None);
+        assert!((score_candidate(&movie, &film) - 1.0).abs() < 1e-9);
+    }
+}
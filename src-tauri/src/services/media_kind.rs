@@ -0,0 +1,63 @@
+//! Medienarten über `movie`/`series` hinaus (Doku, Anime, Konzert, Stand-up, Heimvideo).
+//!
+//! `media_type` bleibt die strukturelle Art (Film oder Episode), `media_kind` die inhaltliche.
+//! Die verfügbaren Arten stehen in der Tabelle `media_kinds`.
+
+use std::path::Path;
+
+pub const MOVIE: &str = "movie";
+pub const SERIES: &str = "series";
+pub const DOCUMENTARY: &str = "documentary";
+pub const ANIME: &str = "anime";
+pub const CONCERT: &str = "concert";
+pub const STANDUP: &str = "standup";
+pub const HOME_VIDEO: &str = "home_video";
+
+/// TMDb-Schreibweise der Art: Serien heißen dort "tv".
+pub fn tmdb_media_type(media_type: &str) -> &'static str {
+    if media_type == SERIES || media_type == "tv" { "tv" } else { MOVIE }
+}
+
+/// Schlüsselwörter in Pfad/Dateiname, Reihenfolge = Priorität.
+const KIND_INDICATORS: &[(&str, &[&str])] = &[
+    (HOME_VIDEO, &["heimvideo", "homevideo", "home video", "home_video", "familienvideo", "privatvideo"]),
+    (CONCERT, &["konzert", "concert", "unplugged", "live at ", "live in "]),
+    (STANDUP, &["stand-up", "standup", "stand up", "comedy special"]),
+    (DOCUMENTARY, &["doku", "documentary", "documentaries"]),
+    (ANIME, &["anime"]),
+];
+
+/// Erkennt die Medienart anhand von Ordner- und Dateinamen.
+/// Fällt auf `media_type` zurück, wenn nichts Spezifisches gefunden wird.
+pub fn detect_media_kind(path: &Path, media_type: &str) -> String {
+    let path_str = path.to_string_lossy().to_lowercase().replace(['.', '_'], " ");
+    KIND_INDICATORS
+        .iter()
+        .find(|(_, indicators)| indicators.iter().any(|ind| path_str.contains(ind)))
+        .map(|(kind, _)| kind.to_string())
+        .unwrap_or_else(|| default_kind(media_type).to_string())
+}
+
+/// Bildet freie Angaben (z.B. aus Perplexity) auf eine bekannte Medienart ab.
+pub fn normalize_media_kind(raw: Option<&str>, media_type: &str) -> String {
+    let raw = raw.unwrap_or("").trim().to_lowercase().replace(['-', ' '], "_");
+    match raw.as_str() {
+        "documentary" | "docu" | "doku" | "dokumentation" => DOCUMENTARY,
+        "anime" => ANIME,
+        "concert" | "konzert" | "live_concert" => CONCERT,
+        "standup" | "stand_up" | "standup_special" | "comedy_special" => STANDUP,
+        "home_video" | "homevideo" | "heimvideo" => HOME_VIDEO,
+        "series" | "tv" => SERIES,
+        "movie" | "film" => MOVIE,
+        _ => default_kind(media_type),
+    }
+    .to_string()
+}
+
+fn default_kind(media_type: &str) -> &'static str {
+    if media_type == SERIES {
+        SERIES
+    } else {
+        MOVIE
+    }
+}
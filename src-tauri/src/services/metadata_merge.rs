@@ -0,0 +1,216 @@
+//! Feldweises Zusammenführen von Metadaten mehrerer Provider.
+//!
+//! Pro Feld legt eine Prioritätsliste fest, welcher Provider gewinnt (z.B. Poster von
+//! TMDb, Handlung notfalls von OMDb). Bewertungen werden nicht ersetzt, sondern als
+//! Liste aller Quellen gesammelt.
+
+use std::collections::{BTreeMap, HashMap};
+
+use super::metadata_provider::{MetadataResult, RatingSource};
+
+pub const FIELD_TITLE: &str = "title";
+pub const FIELD_OVERVIEW: &str = "overview";
+pub const FIELD_POSTER: &str = "poster_url";
+pub const FIELD_BACKDROP: &str = "backdrop_url";
+pub const FIELD_RELEASE_DATE: &str = "release_date";
+pub const FIELD_RUNTIME: &str = "runtime";
+pub const FIELD_GENRES: &str = "genres";
+pub const FIELD_CAST_CREW: &str = "cast_crew";
+
+const ALL_FIELDS: &[&str] = &[
+    FIELD_TITLE,
+    FIELD_OVERVIEW,
+    FIELD_POSTER,
+    FIELD_BACKDROP,
+    FIELD_RELEASE_DATE,
+    FIELD_RUNTIME,
+    FIELD_GENRES,
+    FIELD_CAST_CREW,
+];
+
+#[derive(Debug, Clone)]
+pub struct MergePolicy {
+    precedence: HashMap<String, Vec<String>>,
+}
+
+impl Default for MergePolicy {
+    fn default() -> Self {
+        let images = ["tmdb", "tvmaze", "omdb"];
+        let text = ["tmdb", "tvmaze", "omdb"];
+        let facts = ["tmdb", "omdb", "tvmaze"];
+
+        let mut precedence = HashMap::new();
+        for (field, order) in [
+            (FIELD_TITLE, text),
+            (FIELD_OVERVIEW, text),
+            (FIELD_POSTER, images),
+            (FIELD_BACKDROP, images),
+            (FIELD_RELEASE_DATE, facts),
+            (FIELD_RUNTIME, facts),
+            (FIELD_GENRES, facts),
+            (FIELD_CAST_CREW, text),
+        ] {
+            precedence.insert(
+                field.to_string(),
+                order.iter().map(|p| p.to_string()).collect(),
+            );
+        }
+        Self { precedence }
+    }
+}
+
+impl MergePolicy {
+    /// Liest die Einstellung `metadata_merge_policy`, z.B.
+    /// `{"overview": ["omdb", "tmdb"]}`. Nicht genannte Felder behalten die Standardreihenfolge.
+    pub fn from_settings(settings: &HashMap<String, String>) -> Self {
+        let mut policy = Self::default();
+        let overrides = settings
+            .get("metadata_merge_policy")
+            .filter(|s| !s.trim().is_empty())
+            .and_then(|s| serde_json::from_str::<HashMap<String, Vec<String>>>(s).ok());
+        if let Some(overrides) = overrides {
+            for (field, order) in overrides {
+                if ALL_FIELDS.contains(&field.as_str()) {
+                    policy.precedence.insert(field, order);
+                }
+            }
+        }
+        policy
+    }
+
+    /// Provider in der Reihenfolge, in der sie für `field` gefragt werden.
+    /// Provider ohne Eintrag kommen hinten dran.
+    fn ordered<'a>(&self, field: &str, results: &'a [MetadataResult]) -> Vec<&'a MetadataResult> {
+        let order = self.precedence.get(field);
+        let rank = |provider: &str| {
+            order
+                .and_then(|o| o.iter().position(|p| p == provider))
+                .unwrap_or(usize::MAX)
+        };
+        let mut sorted: Vec<&MetadataResult> = results.iter().collect();
+        sorted.sort_by_key(|r| rank(&r.provider));
+        sorted
+    }
+}
+
+fn pick<'a, T, F>(
+    policy: &MergePolicy,
+    field: &str,
+    results: &'a [MetadataResult],
+    sources: &mut BTreeMap<String, String>,
+    get: F,
+) -> Option<T>
+where
+    F: Fn(&'a MetadataResult) -> Option<T>,
+{
+    for r in policy.ordered(field, results) {
+        if let Some(value) = get(r) {
+            sources.insert(field.to_string(), r.provider.clone());
+            return Some(value);
+        }
+    }
+    None
+}
+
+fn non_empty(s: &str) -> Option<String> {
+    let t = s.trim();
+    if t.is_empty() {
+        None
+    } else {
+        Some(s.to_string())
+    }
+}
+
+fn has_field(r: &MetadataResult, field: &str) -> bool {
+    match field {
+        FIELD_TITLE => non_empty(&r.title).is_some(),
+        FIELD_OVERVIEW => non_empty(&r.overview).is_some(),
+        FIELD_POSTER => r.poster_url.is_some(),
+        FIELD_BACKDROP => r.backdrop_url.is_some(),
+        FIELD_RELEASE_DATE => r.release_date.is_some(),
+        FIELD_RUNTIME => r.runtime.is_some(),
+        FIELD_GENRES => !r.genres.is_empty(),
+        FIELD_CAST_CREW => r
+            .cast_crew
+            .as_ref()
+            .is_some_and(|cc| !cc.cast.is_empty() || !cc.crew.is_empty()),
+        _ => false,
+    }
+}
+
+/// Felder, die keines der Ergebnisse liefert. Nur dafür lohnt es sich, weitere
+/// Provider zu fragen.
+pub fn missing_fields(results: &[MetadataResult]) -> Vec<&'static str> {
+    ALL_FIELDS
+        .iter()
+        .copied()
+        .filter(|field| !results.iter().any(|r| has_field(r, field)))
+        .collect()
+}
+
+/// Führt die Ergebnisse zusammen. Das erste Ergebnis ist das primäre: es bestimmt
+/// Provider und ID des zusammengeführten Datensatzes. `field_sources` nennt nur
+/// Felder, die tatsächlich einen Wert haben.
+pub fn merge(results: Vec<MetadataResult>, policy: &MergePolicy) -> Option<MetadataResult> {
+    let primary = results.first()?.clone();
+
+    let mut sources = BTreeMap::new();
+    let title = pick(policy, FIELD_TITLE, &results, &mut sources, |r| non_empty(&r.title));
+    let overview = pick(policy, FIELD_OVERVIEW, &results, &mut sources, |r| non_empty(&r.overview));
+    let poster_url = pick(policy, FIELD_POSTER, &results, &mut sources, |r| r.poster_url.clone());
+    let backdrop_url =
+        pick(policy, FIELD_BACKDROP, &results, &mut sources, |r| r.backdrop_url.clone());
+    let release_date =
+        pick(policy, FIELD_RELEASE_DATE, &results, &mut sources, |r| r.release_date.clone());
+    let runtime = pick(policy, FIELD_RUNTIME, &results, &mut sources, |r| r.runtime);
+    let genres = pick(policy, FIELD_GENRES, &results, &mut sources, |r| {
+        (!r.genres.is_empty()).then(|| r.genres.clone())
+    });
+    let cast_crew = pick(policy, FIELD_CAST_CREW, &results, &mut sources, |r| {
+        r.cast_crew
+            .clone()
+            .filter(|cc| !cc.cast.is_empty() || !cc.crew.is_empty())
+    });
+
+    // Bewertungen: alle Quellen sammeln, die erste Angabe je Quelle gewinnt.
+    let mut ratings = Vec::new();
+    for r in &results {
+        for rating in &r.ratings {
+            if !ratings.iter().any(|x: &RatingSource| x.source == rating.source) {
+                ratings.push(rating.clone());
+            }
+        }
+    }
+
+    // Die Sprache folgt der Handlung, da diese am ehesten gelesen wird.
+    let language = sources
+        .get(FIELD_OVERVIEW)
+        .and_then(|p| results.iter().find(|r| &r.provider == p))
+        .and_then(|r| r.language.clone())
+        .or(primary.language);
+
+    let mut external_ids = primary.external_ids.clone();
+    for r in &results {
+        external_ids.fill_from(&r.external_ids);
+    }
+
+    Some(MetadataResult {
+        provider: primary.provider,
+        provider_id: primary.provider_id,
+        media_type: primary.media_type,
+        title: title.unwrap_or_default(),
+        overview: overview.unwrap_or_default(),
+        poster_url,
+        backdrop_url,
+        release_date,
+        ratings,
+        runtime,
+        genres: genres.unwrap_or_default(),
+        cast_crew,
+        raw_response: primary.raw_response,
+        field_sources: sources,
+        language,
+        translations: primary.translations,
+        external_ids,
+    })
+}
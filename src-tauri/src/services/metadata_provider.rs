@@ -1,5 +1,7 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::ops::BitOr;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CastMember {
@@ -11,6 +13,8 @@ pub struct CastMember {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CrewMember {
+    /// TMDb-Personen-ID; fehlt bei anderen Providern und in älteren Einträgen.
+    pub id: Option<i64>,
     pub name: String,
     pub job: String,
     pub department: String,
@@ -23,19 +27,103 @@ pub struct CastCrew {
     pub crew: Vec<CrewMember>,
 }
 
+/// Eine Bewertung aus einer Quelle (TMDb, IMDb, Rotten Tomatoes, ...).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RatingSource {
+    pub source: String,
+    /// Auf 0–10 normiert, damit Quellen vergleichbar sind.
+    pub value: f64,
+    /// Originalanzeige der Quelle, z.B. "87%" oder "74/100".
+    pub display: String,
+    pub votes: Option<i64>,
+}
+
+impl RatingSource {
+    pub fn new(source: &str, value: f64, votes: Option<i64>) -> Self {
+        Self {
+            source: source.to_string(),
+            value,
+            display: format!("{:.1}", value),
+            votes,
+        }
+    }
+}
+
+/// Titel/Handlung in einer bestimmten Sprache, damit mehrere Sprachen gecacht werden können.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetadataTranslation {
+    /// BCP-47-Tag, z.B. "de-DE"; ohne Region nur "de".
+    pub language: String,
+    pub title: Option<String>,
+    pub overview: Option<String>,
+    pub tagline: Option<String>,
+}
+
+/// IDs desselben Titels bei anderen Diensten.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExternalIds {
+    /// Mit Präfix, z.B. "tt0111161".
+    pub imdb_id: Option<String>,
+    pub tvdb_id: Option<i64>,
+    /// Wikidata-Item, z.B. "Q172241".
+    pub wikidata_id: Option<String>,
+}
+
+impl ExternalIds {
+    /// Ergänzt fehlende IDs aus `other`.
+    pub fn fill_from(&mut self, other: &ExternalIds) {
+        if self.imdb_id.is_none() {
+            self.imdb_id = other.imdb_id.clone();
+        }
+        if self.tvdb_id.is_none() {
+            self.tvdb_id = other.tvdb_id;
+        }
+        if self.wikidata_id.is_none() {
+            self.wikidata_id = other.wikidata_id.clone();
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct MetadataResult {
-    pub provider_id: i64,
+    /// Name des Providers, der das Ergebnis geliefert hat (z.B. "tmdb").
+    pub provider: String,
+    /// ID im ID-Raum des Providers, z.B. "603" (TMDb) oder "tt0133093" (OMDb).
+    pub provider_id: String,
+    /// Medientyp laut Provider, "movie" oder "series".
+    pub media_type: String,
     pub title: String,
     pub overview: String,
     pub poster_url: Option<String>,
     pub backdrop_url: Option<String>,
     pub release_date: Option<String>,
-    pub rating: Option<f64>,
+    pub ratings: Vec<RatingSource>,
     pub runtime: Option<i64>,
     pub genres: Vec<String>,
     pub cast_crew: Option<CastCrew>,
     pub raw_response: String,
+    /// Feldname -> Provider, der den Wert geliefert hat (nur nach dem Zusammenführen gefüllt).
+    pub field_sources: BTreeMap<String, String>,
+    /// Sprache von Titel/Handlung, soweit bekannt.
+    pub language: Option<String>,
+    pub translations: Vec<MetadataTranslation>,
+    pub external_ids: ExternalIds,
+}
+
+impl MetadataResult {
+    /// Die erste Bewertung, d.h. die des primären Providers.
+    pub fn primary_rating(&self) -> Option<f64> {
+        self.ratings.first().map(|r| r.value)
+    }
+
+    /// TMDb-ID, falls das Ergebnis von TMDb stammt.
+    pub fn tmdb_id(&self) -> Option<i64> {
+        if self.provider == "tmdb" {
+            self.provider_id.parse().ok()
+        } else {
+            None
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -45,10 +133,99 @@ pub struct SearchQuery {
     pub media_type: String,
 }
 
+/// Was ein Provider liefern kann. Der `MetadataService` fragt nur Provider,
+/// die die nötige Fähigkeit haben.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Capabilities(u8);
+
+impl Capabilities {
+    pub const MOVIES: Self = Self(1);
+    pub const SERIES: Self = Self(1 << 1);
+    pub const EPISODES: Self = Self(1 << 2);
+    pub const PEOPLE: Self = Self(1 << 3);
+    pub const IMAGES: Self = Self(1 << 4);
+    pub const MUSIC: Self = Self(1 << 5);
+    /// Liefert eigene Bewertungsquellen (z.B. IMDb, Rotten Tomatoes, Metacritic).
+    pub const RATINGS: Self = Self(1 << 6);
+
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Fähigkeit, die Suche und Details für einen Medientyp voraussetzen.
+    pub fn for_media_type(media_type: &str) -> Self {
+        match media_type {
+            "series" => Self::SERIES,
+            "music" => Self::MUSIC,
+            _ => Self::MOVIES,
+        }
+    }
+}
+
+impl BitOr for Capabilities {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// Eine Folge einer Serie.
+#[derive(Debug, Clone)]
+pub struct EpisodeDetails {
+    pub provider_id: String,
+    pub name: Option<String>,
+    pub overview: Option<String>,
+    pub air_date: Option<String>,
+    pub still_url: Option<String>,
+    pub runtime: Option<i64>,
+    pub rating: Option<f64>,
+    pub guest_stars: Vec<CastMember>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderImage {
+    pub url: String,
+    /// ISO 639-1; `None` bei Bildern ohne Text.
+    pub language: Option<String>,
+    pub width: Option<i64>,
+    pub height: Option<i64>,
+}
+
+/// Alle Bilder eines Titels, jeweils nach Eignung sortiert.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImageSet {
+    pub posters: Vec<ProviderImage>,
+    pub backdrops: Vec<ProviderImage>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PersonInfo {
+    pub provider_id: String,
+    pub name: String,
+    pub biography: Option<String>,
+    pub profile_url: Option<String>,
+    pub birthday: Option<String>,
+    pub deathday: Option<String>,
+    pub place_of_birth: Option<String>,
+    pub known_for_department: Option<String>,
+}
+
+fn unsupported(provider: &str, what: &str) -> String {
+    format!("Provider '{}' unterstützt keine {}", provider, what)
+}
+
 #[async_trait]
 pub trait MetadataProvider: Send + Sync {
     fn name(&self) -> &str;
 
+    /// Reihenfolge beim Durchfragen der Provider (kleiner = zuerst).
+    fn priority(&self) -> i32 {
+        100
+    }
+
+    fn capabilities(&self) -> Capabilities;
+
     async fn search(
         &self,
         query: &SearchQuery,
@@ -56,7 +233,31 @@ pub trait MetadataProvider: Send + Sync {
 
     async fn fetch_details(
         &self,
-        provider_id: i64,
+        provider_id: &str,
         media_type: &str,
     ) -> Result<Option<MetadataResult>, String>;
+
+    /// Nur mit [`Capabilities::EPISODES`]. `show_id` ist die Serien-ID dieses Providers.
+    async fn fetch_episode(
+        &self,
+        _show_id: &str,
+        _season: u32,
+        _episode: u32,
+    ) -> Result<Option<EpisodeDetails>, String> {
+        Err(unsupported(self.name(), "Folgen"))
+    }
+
+    /// Nur mit [`Capabilities::IMAGES`].
+    async fn fetch_images(
+        &self,
+        _provider_id: &str,
+        _media_type: &str,
+    ) -> Result<ImageSet, String> {
+        Err(unsupported(self.name(), "Bilder"))
+    }
+
+    /// Nur mit [`Capabilities::PEOPLE`].
+    async fn fetch_person(&self, _person_id: &str) -> Result<Option<PersonInfo>, String> {
+        Err(unsupported(self.name(), "Personen"))
+    }
 }
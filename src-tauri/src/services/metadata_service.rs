@@ -1,8 +1,15 @@
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
-use super::metadata_provider::{MetadataProvider, MetadataResult, SearchQuery};
+use super::language::LanguagePrefs;
+use super::metadata_merge::{self, MergePolicy};
+use super::metadata_provider::{
+    Capabilities, EpisodeDetails, ImageSet, MetadataProvider, MetadataResult, PersonInfo,
+    SearchQuery,
+};
+use super::omdb::OmdbProvider;
 use super::tmdb::TmdbProvider;
+use super::tvmaze::TvmazeProvider;
 
 pub struct MetadataService {
     providers: RwLock<Vec<Arc<dyn MetadataProvider>>>,
@@ -18,19 +25,78 @@ impl MetadataService {
     pub async fn register_provider(&self, provider: Arc<dyn MetadataProvider>) {
         let mut providers = self.providers.write().await;
         providers.retain(|p| p.name() != provider.name());
+        log::info!("Registered metadata provider: {}", provider.name());
         providers.push(provider);
-        log::info!("Registered metadata provider: {}", providers.last().unwrap().name());
+        providers.sort_by_key(|p| p.priority());
     }
 
-    pub async fn set_tmdb_key(&self, api_key: String) {
-        self.register_provider(Arc::new(TmdbProvider::new(api_key))).await;
+    pub async fn unregister_provider(&self, name: &str) {
+        let mut providers = self.providers.write().await;
+        let before = providers.len();
+        providers.retain(|p| p.name() != name);
+        if providers.len() != before {
+            log::info!("Unregistered metadata provider: {}", name);
+        }
+    }
+
+    pub async fn set_tmdb_key(&self, api_key: String, languages: LanguagePrefs) {
+        self.register_provider(Arc::new(TmdbProvider::new(api_key, languages)))
+            .await;
     }
 
+    /// Leerer Key entfernt den OMDb-Provider wieder.
+    pub async fn set_omdb_key(&self, api_key: String) {
+        if api_key.trim().is_empty() {
+            self.unregister_provider("omdb").await;
+        } else {
+            self.register_provider(Arc::new(OmdbProvider::new(api_key.trim().to_string())))
+                .await;
+        }
+    }
+
+    pub async fn set_tvmaze_enabled(&self, enabled: bool) {
+        if enabled {
+            self.register_provider(Arc::new(TvmazeProvider::new())).await;
+        } else {
+            self.unregister_provider("tvmaze").await;
+        }
+    }
+
+    /// Provider mit der Fähigkeit `cap`, nach Priorität. Mit `provider_name` nur
+    /// dieser; Fehler, wenn er nicht registriert ist oder die Fähigkeit fehlt.
+    async fn capable(
+        &self,
+        cap: Capabilities,
+        provider_name: Option<&str>,
+    ) -> Result<Vec<Arc<dyn MetadataProvider>>, String> {
+        let providers = self.providers.read().await;
+        match provider_name {
+            Some(name) => {
+                let provider = providers
+                    .iter()
+                    .find(|p| p.name() == name)
+                    .ok_or_else(|| format!("Provider '{}' not registered", name))?;
+                if !provider.capabilities().contains(cap) {
+                    return Err(format!("Provider '{}' does not support this request", name));
+                }
+                Ok(vec![provider.clone()])
+            }
+            None => Ok(providers
+                .iter()
+                .filter(|p| p.capabilities().contains(cap))
+                .cloned()
+                .collect()),
+        }
+    }
+
+    /// Sucht bei `provider_name` oder – ohne Angabe – der Reihe nach bei allen
+    /// Providern für den Medientyp, bis einer Treffer liefert.
     pub async fn search(
         &self,
         title: &str,
         year: Option<u16>,
         media_type: &str,
+        provider_name: Option<&str>,
     ) -> Result<Vec<MetadataResult>, String> {
         let query = SearchQuery {
             title: title.to_string(),
@@ -38,8 +104,14 @@ impl MetadataService {
             media_type: media_type.to_string(),
         };
 
-        let providers = self.providers.read().await;
-        for provider in providers.iter() {
+        let providers = self
+            .capable(Capabilities::for_media_type(media_type), provider_name)
+            .await?;
+        if provider_name.is_some() {
+            return providers[0].search(&query).await;
+        }
+
+        for provider in providers {
             match provider.search(&query).await {
                 Ok(results) if !results.is_empty() => return Ok(results),
                 Ok(_) => continue,
@@ -55,20 +127,18 @@ impl MetadataService {
 
     pub async fn fetch_details(
         &self,
-        provider_id: i64,
+        provider_id: &str,
         media_type: &str,
         provider_name: Option<&str>,
     ) -> Result<Option<MetadataResult>, String> {
-        let providers = self.providers.read().await;
-
-        if let Some(name) = provider_name {
-            if let Some(provider) = providers.iter().find(|p| p.name() == name) {
-                return provider.fetch_details(provider_id, media_type).await;
-            }
-            return Err(format!("Provider '{}' not registered", name));
+        let providers = self
+            .capable(Capabilities::for_media_type(media_type), provider_name)
+            .await?;
+        if provider_name.is_some() {
+            return providers[0].fetch_details(provider_id, media_type).await;
         }
 
-        for provider in providers.iter() {
+        for provider in providers {
             match provider.fetch_details(provider_id, media_type).await {
                 Ok(Some(result)) => return Ok(Some(result)),
                 Ok(None) => continue,
@@ -82,7 +152,133 @@ impl MetadataService {
         Ok(None)
     }
 
+    /// Folge einer Serie. `show_id` gehört zum ID-Raum von `provider_name`.
+    pub async fn fetch_episode(
+        &self,
+        provider_name: &str,
+        show_id: &str,
+        season: u32,
+        episode: u32,
+    ) -> Result<Option<EpisodeDetails>, String> {
+        let providers = self.capable(Capabilities::EPISODES, Some(provider_name)).await?;
+        providers[0].fetch_episode(show_id, season, episode).await
+    }
+
+    pub async fn fetch_images(
+        &self,
+        provider_name: &str,
+        provider_id: &str,
+        media_type: &str,
+    ) -> Result<ImageSet, String> {
+        let providers = self.capable(Capabilities::IMAGES, Some(provider_name)).await?;
+        providers[0].fetch_images(provider_id, media_type).await
+    }
+
+    pub async fn fetch_person(
+        &self,
+        provider_name: &str,
+        person_id: &str,
+    ) -> Result<Option<PersonInfo>, String> {
+        let providers = self.capable(Capabilities::PEOPLE, Some(provider_name)).await?;
+        providers[0].fetch_person(person_id).await
+    }
+
+    /// Holt die Details beim primären Provider und ergänzt sie feldweise um die
+    /// Angaben der übrigen Provider (gemäss `policy`). Die übrigen Provider werden
+    /// nur gefragt, solange Felder fehlen oder sie eigene Bewertungen liefern.
+    pub async fn fetch_details_merged(
+        &self,
+        provider_id: &str,
+        media_type: &str,
+        provider_name: &str,
+        policy: &MergePolicy,
+    ) -> Result<Option<MetadataResult>, String> {
+        let primary = match self
+            .fetch_details(provider_id, media_type, Some(provider_name))
+            .await?
+        {
+            Some(p) => p,
+            None => return Ok(None),
+        };
+
+        let others: Vec<Arc<dyn MetadataProvider>> = self
+            .capable(Capabilities::for_media_type(media_type), None)
+            .await?
+            .into_iter()
+            .filter(|p| p.name() != provider_name)
+            .collect();
+
+        let mut results = vec![primary];
+        for provider in others {
+            let rates = provider.capabilities().contains(Capabilities::RATINGS);
+            if !rates && metadata_merge::missing_fields(&results).is_empty() {
+                continue;
+            }
+            match find_counterpart(provider.as_ref(), &results[0], media_type).await {
+                Ok(Some(r)) => results.push(r),
+                Ok(None) => {}
+                Err(e) => log::warn!("Provider {} merge lookup failed: {}", provider.name(), e),
+            }
+        }
+
+        Ok(metadata_merge::merge(results, policy))
+    }
+
     pub async fn has_providers(&self) -> bool {
         !self.providers.read().await.is_empty()
     }
 }
+
+/// Sucht beim Provider den Datensatz, der zum primären Ergebnis gehört.
+/// OMDb wird direkt über die IMDb-ID des primären Ergebnisses angesprochen,
+/// sonst per Suche mit Originaltitel und Jahr.
+async fn find_counterpart(
+    provider: &dyn MetadataProvider,
+    primary: &MetadataResult,
+    media_type: &str,
+) -> Result<Option<MetadataResult>, String> {
+    let raw: serde_json::Value = serde_json::from_str(&primary.raw_response).unwrap_or_default();
+
+    if provider.name() == "omdb" {
+        if let Some(imdb_id) = primary.external_ids.imdb_id.as_deref() {
+            return provider.fetch_details(imdb_id, media_type).await;
+        }
+    }
+
+    let title = raw
+        .get("original_title")
+        .or_else(|| raw.get("original_name"))
+        .and_then(|v| v.as_str())
+        .filter(|t| !t.is_empty())
+        .unwrap_or(&primary.title)
+        .to_string();
+    let year: Option<u16> = primary
+        .release_date
+        .as_deref()
+        .and_then(|d| d.get(..4))
+        .and_then(|y| y.parse().ok());
+
+    let candidates = provider
+        .search(&SearchQuery {
+            title,
+            year,
+            media_type: media_type.to_string(),
+        })
+        .await?;
+    let matching = candidates.into_iter().find(|c| match year {
+        Some(y) => c
+            .release_date
+            .as_deref()
+            .map(|d| d.starts_with(&y.to_string()))
+            .unwrap_or(false),
+        None => true,
+    });
+
+    match matching {
+        Some(c) => Ok(provider
+            .fetch_details(&c.provider_id, media_type)
+            .await?
+            .or(Some(c))),
+        None => Ok(None),
+    }
+}
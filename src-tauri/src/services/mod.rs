@@ -1,11 +1,21 @@
 pub mod quickconnect;
 pub mod storage;
 pub mod local_media;
+pub mod media_kind;
 pub mod perplexity;
 pub mod metadata_provider;
+pub mod language;
+pub mod endpoints;
+pub mod tmdb_http;
 pub mod tmdb;
+pub mod omdb;
+pub mod tvmaze;
 pub mod tmdb_api;
+pub mod image_cache;
+pub mod parental;
 pub mod metadata_service;
+pub mod metadata_merge;
+pub mod match_scoring;
 pub mod realdebrid;
 pub mod discovery;
 pub mod rd_streams;
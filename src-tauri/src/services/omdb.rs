@@ -0,0 +1,318 @@
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::endpoints;
+use super::metadata_provider::{
+    Capabilities, CastCrew, CastMember, CrewMember, ExternalIds, MetadataProvider, MetadataResult,
+    RatingSource, SearchQuery,
+};
+
+pub struct OmdbProvider {
+    api_key: String,
+    client: Client,
+}
+
+impl OmdbProvider {
+    pub fn new(api_key: String) -> Self {
+        Self {
+            api_key,
+            client: Client::new(),
+        }
+    }
+}
+
+/// OMDb liefert fehlende Werte als "N/A".
+fn present(value: Option<String>) -> Option<String> {
+    value.filter(|v| !v.trim().is_empty() && v != "N/A")
+}
+
+fn split_list(value: Option<String>) -> Vec<String> {
+    present(value)
+        .map(|v| {
+            v.split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// OMDb-Typ ("movie", "series", "episode", "game"); fehlt er, gilt der gesuchte Typ.
+fn omdb_media_type(kind: Option<String>, requested: &str) -> String {
+    present(kind).unwrap_or_else(|| requested.to_string())
+}
+
+/// "8.8/10" -> 8.8, "87%" -> 8.7, "74/100" -> 7.4
+fn normalize_rating(value: &str) -> Option<f64> {
+    let value = value.trim();
+    if let Some(pct) = value.strip_suffix('%') {
+        return pct.trim().parse::<f64>().ok().map(|v| v / 10.0);
+    }
+    let (num, max) = value.split_once('/')?;
+    let num: f64 = num.trim().parse().ok()?;
+    let max: f64 = max.trim().parse().ok()?;
+    (max > 0.0).then(|| num / max * 10.0)
+}
+
+fn rating_source_key(source: &str) -> String {
+    match source {
+        "Internet Movie Database" => "imdb".to_string(),
+        "Rotten Tomatoes" => "rotten_tomatoes".to_string(),
+        "Metacritic" => "metacritic".to_string(),
+        other => other.to_lowercase().replace(' ', "_"),
+    }
+}
+
+// --- OMDb response types ---
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    #[serde(rename = "Search")]
+    search: Option<Vec<SearchEntry>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchEntry {
+    #[serde(rename = "Title")]
+    title: Option<String>,
+    #[serde(rename = "Year")]
+    year: Option<String>,
+    #[serde(rename = "imdbID")]
+    imdb_id: String,
+    #[serde(rename = "Poster")]
+    poster: Option<String>,
+    #[serde(rename = "Type")]
+    kind: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DetailResponse {
+    #[serde(rename = "Response")]
+    response: Option<String>,
+    #[serde(rename = "Title")]
+    title: Option<String>,
+    #[serde(rename = "Released")]
+    released: Option<String>,
+    #[serde(rename = "Year")]
+    year: Option<String>,
+    #[serde(rename = "Runtime")]
+    runtime: Option<String>,
+    #[serde(rename = "Genre")]
+    genre: Option<String>,
+    #[serde(rename = "Director")]
+    director: Option<String>,
+    #[serde(rename = "Writer")]
+    writer: Option<String>,
+    #[serde(rename = "Actors")]
+    actors: Option<String>,
+    #[serde(rename = "Plot")]
+    plot: Option<String>,
+    #[serde(rename = "Poster")]
+    poster: Option<String>,
+    #[serde(rename = "imdbVotes")]
+    imdb_votes: Option<String>,
+    #[serde(rename = "Ratings")]
+    ratings: Option<Vec<OmdbRating>>,
+    #[serde(rename = "imdbID")]
+    imdb_id: Option<String>,
+    #[serde(rename = "Type")]
+    kind: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OmdbRating {
+    #[serde(rename = "Source")]
+    source: String,
+    #[serde(rename = "Value")]
+    value: String,
+}
+
+// --- trait implementation ---
+
+#[async_trait]
+impl MetadataProvider for OmdbProvider {
+    fn name(&self) -> &str {
+        "omdb"
+    }
+
+    fn priority(&self) -> i32 {
+        10
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::MOVIES | Capabilities::SERIES | Capabilities::RATINGS
+    }
+
+    async fn search(
+        &self,
+        query: &SearchQuery,
+    ) -> Result<Vec<MetadataResult>, String> {
+        let omdb_type = if query.media_type == "series" { "series" } else { "movie" };
+        let mut params = vec![
+            ("apikey", self.api_key.clone()),
+            ("s", query.title.clone()),
+            ("type", omdb_type.to_string()),
+        ];
+        if let Some(year) = query.year {
+            params.push(("y", year.to_string()));
+        }
+
+        let resp = self
+            .client
+            .get(format!("{}/", endpoints::omdb()))
+            .query(&params)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        let data: SearchResponse = resp.json().await.map_err(|e| e.to_string())?;
+
+        let results = data
+            .search
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|entry| {
+                // Provider-ID ist die IMDb-ID ("tt1375666").
+                let provider_id = entry.imdb_id.trim().to_string();
+                if provider_id.is_empty() {
+                    return None;
+                }
+                let external_ids = ExternalIds {
+                    imdb_id: Some(provider_id.clone()),
+                    ..Default::default()
+                };
+                Some(MetadataResult {
+                    provider: self.name().to_string(),
+                    provider_id,
+                    media_type: omdb_media_type(entry.kind, omdb_type),
+                    title: entry.title.unwrap_or_default(),
+                    overview: String::new(),
+                    poster_url: present(entry.poster),
+                    backdrop_url: None,
+                    release_date: present(entry.year).and_then(|y| y.get(..4).map(String::from)),
+                    ratings: Vec::new(),
+                    runtime: None,
+                    genres: Vec::new(),
+                    cast_crew: None,
+                    raw_response: String::new(),
+                    field_sources: Default::default(),
+                    language: Some("en".to_string()),
+                    translations: Vec::new(),
+                    external_ids,
+                })
+            })
+            .take(5)
+            .collect();
+
+        Ok(results)
+    }
+
+    async fn fetch_details(
+        &self,
+        provider_id: &str,
+        media_type: &str,
+    ) -> Result<Option<MetadataResult>, String> {
+        let resp = self
+            .client
+            .get(format!("{}/", endpoints::omdb()))
+            .query(&[
+                ("apikey", self.api_key.as_str()),
+                ("i", provider_id.trim()),
+                ("plot", "full"),
+            ])
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        let raw_text = resp.text().await.map_err(|e| e.to_string())?;
+        let raw: Value = serde_json::from_str(&raw_text).map_err(|e| e.to_string())?;
+        let detail: DetailResponse =
+            serde_json::from_value(raw.clone()).map_err(|e| e.to_string())?;
+
+        if detail.response.as_deref() == Some("False") {
+            return Ok(None);
+        }
+
+        let release_date = present(detail.released)
+            .and_then(|d| chrono::NaiveDate::parse_from_str(&d, "%d %b %Y").ok())
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .or_else(|| present(detail.year).and_then(|y| y.get(..4).map(String::from)));
+        let runtime = present(detail.runtime)
+            .and_then(|r| r.split_whitespace().next().and_then(|n| n.parse().ok()));
+
+        let cast: Vec<CastMember> = split_list(detail.actors)
+            .into_iter()
+            .map(|name| CastMember {
+                id: None,
+                name,
+                character: None,
+                profile_url: None,
+            })
+            .collect();
+        let crew: Vec<CrewMember> = split_list(detail.director)
+            .into_iter()
+            .map(|name| (name, "Director", "Directing"))
+            .chain(
+                split_list(detail.writer)
+                    .into_iter()
+                    .map(|name| (name, "Writer", "Writing")),
+            )
+            .map(|(name, job, department)| CrewMember {
+                id: None,
+                // "Jonathan Nolan (screenplay)" -> "Jonathan Nolan"
+                name: name.split(" (").next().unwrap_or(&name).to_string(),
+                job: job.to_string(),
+                department: department.to_string(),
+                profile_url: None,
+            })
+            .collect();
+        let imdb_votes = present(detail.imdb_votes)
+            .and_then(|v| v.replace(',', "").parse::<i64>().ok());
+        let ratings: Vec<RatingSource> = detail
+            .ratings
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|r| {
+                let value = normalize_rating(&r.value)?;
+                let source = rating_source_key(&r.source);
+                let votes = if source == "imdb" { imdb_votes } else { None };
+                Some(RatingSource {
+                    source,
+                    value,
+                    display: r.value,
+                    votes,
+                })
+            })
+            .collect();
+
+        let cast_crew = if cast.is_empty() && crew.is_empty() {
+            None
+        } else {
+            Some(CastCrew { cast, crew })
+        };
+
+        let provider_id = present(detail.imdb_id).unwrap_or_else(|| provider_id.trim().to_string());
+        Ok(Some(MetadataResult {
+            provider: self.name().to_string(),
+            provider_id: provider_id.clone(),
+            media_type: omdb_media_type(detail.kind, media_type),
+            title: detail.title.unwrap_or_default(),
+            overview: present(detail.plot).unwrap_or_default(),
+            poster_url: present(detail.poster),
+            backdrop_url: None,
+            release_date,
+            ratings,
+            runtime,
+            genres: split_list(detail.genre),
+            cast_crew,
+            raw_response: raw.to_string(),
+            field_sources: Default::default(),
+            language: Some("en".to_string()),
+            translations: Vec::new(),
+            external_ids: ExternalIds {
+                imdb_id: Some(provider_id),
+                ..Default::default()
+            },
+        }))
+    }
+}
@@ -0,0 +1,229 @@
+//! Kindermodus: blendet Titel oberhalb einer Altersfreigabe aus.
+//!
+//! Einstellungen (nur über die Kindermodus-Commands änderbar, nicht über `save_settings`):
+//! - `kids_mode_enabled`
+//! - `kids_max_certification` – höchste erlaubte Freigabe im Format des Landes (z. B. `12` oder `PG`)
+//! - `kids_country` – Land der Freigaben (ISO 3166-1, Standard aus der Metadaten-Sprache)
+//! - `kids_pin_hash` – gesalzener SHA-256 der PIN
+//!
+//! Grundlage sind die in `media_certifications` gespeicherten TMDb-Freigaben.
+//! Titel ohne bekannte Freigabe für das Land gelten als gesperrt.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use sha2::{Digest, Sha256};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use crate::db::DbPool;
+use crate::services::language::{self, LanguagePrefs};
+use crate::services::storage;
+use crate::services::tmdb_api;
+
+pub const DEFAULT_COUNTRY: &str = "DE";
+
+/// Gleichzeitige TMDb-Abrufe fehlender Freigaben.
+const FETCH_CONCURRENCY: usize = 4;
+
+/// Diese Schlüssel liefert `load_settings` nicht aus und `save_settings` nimmt sie nicht an.
+pub fn is_protected_setting(key: &str) -> bool {
+    key.starts_with("kids_")
+}
+
+/// Mindestalter zu einer Freigabe; `None`, wenn sie nicht einzuordnen ist (z. B. "NR").
+pub fn min_age(country: &str, certification: &str) -> Option<u8> {
+    let c = certification.trim().to_ascii_uppercase();
+    let known = match (country, c.as_str()) {
+        ("US", "G" | "TV-Y" | "TV-G") => Some(0),
+        ("US", "TV-Y7") => Some(7),
+        ("US", "PG" | "TV-PG") => Some(10),
+        ("US", "PG-13") => Some(13),
+        ("US", "TV-14") => Some(14),
+        ("US", "R" | "TV-MA") => Some(17),
+        ("US", "NC-17") => Some(18),
+        ("GB" | "IE", "PG") => Some(8),
+        ("GB", "R18") => Some(18),
+        ("AU", "P" | "C") => Some(0),
+        ("AU", "PG") => Some(8),
+        ("AU", "M") => Some(15),
+        (_, "0" | "U" | "G" | "L" | "AL" | "ALL" | "TP") => Some(0),
+        _ => None,
+    };
+    // Sonst zählt die erste Zahl: "12A", "16+", "-12", "K-7", "MA15+".
+    known.or_else(|| {
+        let digits: String = c
+            .chars()
+            .skip_while(|ch| !ch.is_ascii_digit())
+            .take_while(|ch| ch.is_ascii_digit())
+            .collect();
+        digits.parse::<u8>().ok().filter(|age| *age <= 21)
+    })
+}
+
+/// Land der Freigaben: Einstellung, sonst Region der Metadaten-Sprache, sonst Discover-Land.
+pub fn country(settings: &HashMap<String, String>) -> String {
+    settings
+        .get("kids_country")
+        .map(|c| c.trim().to_ascii_uppercase())
+        .filter(|c| c.len() == 2)
+        .or_else(|| {
+            LanguagePrefs::from_settings(settings)
+                .chain()
+                .iter()
+                .find_map(|t| language::iso_3166_1(t).map(|r| r.to_ascii_uppercase()))
+        })
+        .or_else(|| settings.get("discover_country").map(|c| c.trim().to_ascii_uppercase()))
+        .filter(|c| c.len() == 2)
+        .unwrap_or_else(|| DEFAULT_COUNTRY.to_string())
+}
+
+#[derive(Debug, Clone)]
+pub struct KidsPolicy {
+    pub country: String,
+    max_age: u8,
+}
+
+impl KidsPolicy {
+    /// `None`, wenn der Kindermodus aus ist. Eine unlesbare Höchstfreigabe
+    /// lässt nur Titel ab 0 zu.
+    pub fn from_settings(settings: &HashMap<String, String>) -> Option<Self> {
+        if settings.get("kids_mode_enabled").map(|v| v.as_str()) != Some("true") {
+            return None;
+        }
+        let country = country(settings);
+        let max_age = settings
+            .get("kids_max_certification")
+            .and_then(|c| min_age(&country, c))
+            .unwrap_or(0);
+        Some(Self { country, max_age })
+    }
+
+    pub fn allows(&self, certification: Option<&str>) -> bool {
+        certification
+            .and_then(|c| min_age(&self.country, c))
+            .is_some_and(|age| age <= self.max_age)
+    }
+}
+
+/// Freigaben des Landes für den Abgleich vieler Titel auf einmal.
+pub struct KidsFilter {
+    policy: KidsPolicy,
+    certifications: HashMap<(i64, String), String>,
+}
+
+/// TMDb-Listen nennen Serien "tv", die Bibliothek und `media_extras` "series".
+fn normalize_media_type(media_type: &str) -> &str {
+    if media_type == "tv" { "series" } else { media_type }
+}
+
+impl KidsFilter {
+    /// `None`, wenn der Kindermodus aus ist.
+    pub async fn load(pool: &DbPool, settings: &HashMap<String, String>) -> Result<Option<Self>, String> {
+        let policy = match KidsPolicy::from_settings(settings) {
+            Some(p) => p,
+            None => return Ok(None),
+        };
+        let certifications = storage::get_certifications_for_country(pool, &policy.country).await?;
+        Ok(Some(Self {
+            policy,
+            certifications,
+        }))
+    }
+
+    /// Wie [`KidsFilter::load`], holt vorher aber fehlende Freigaben der Titel bei TMDb.
+    pub async fn prepare(
+        pool: &DbPool,
+        settings: &HashMap<String, String>,
+        titles: &[(i64, String)],
+    ) -> Result<Option<Self>, String> {
+        if KidsPolicy::from_settings(settings).is_none() {
+            return Ok(None);
+        }
+        fetch_missing_extras(pool, settings, titles).await;
+        Self::load(pool, settings).await
+    }
+
+    pub fn allows(&self, tmdb_id: Option<i64>, media_type: &str) -> bool {
+        let tmdb_id = match tmdb_id {
+            Some(id) => id,
+            None => return false,
+        };
+        let key = (tmdb_id, normalize_media_type(media_type).to_string());
+        self.policy.allows(self.certifications.get(&key).map(|c| c.as_str()))
+    }
+}
+
+/// Holt TMDb-Zusätze (inkl. Freigaben) für Titel, die noch keine haben, höchstens
+/// `FETCH_CONCURRENCY` gleichzeitig. Fehler werden nur geloggt; solche Titel bleiben
+/// im Kindermodus gesperrt.
+pub async fn fetch_missing_extras(pool: &DbPool, settings: &HashMap<String, String>, titles: &[(i64, String)]) {
+    let api_key = match settings.get("tmdb_api_key").filter(|k| !k.is_empty()) {
+        Some(k) => k,
+        None => return,
+    };
+    let languages = LanguagePrefs::from_settings(settings);
+    let mut seen = HashSet::new();
+    let mut missing = Vec::new();
+    for (tmdb_id, media_type) in titles {
+        let media_type = normalize_media_type(media_type);
+        if !(media_type == "movie" || media_type == "series") || !seen.insert((*tmdb_id, media_type)) {
+            continue;
+        }
+        if let Ok(false) = storage::has_media_extras(pool, *tmdb_id, media_type).await {
+            missing.push((*tmdb_id, media_type.to_string()));
+        }
+    }
+
+    let semaphore = Arc::new(Semaphore::new(FETCH_CONCURRENCY));
+    let mut tasks = JoinSet::new();
+    for (tmdb_id, media_type) in missing {
+        let semaphore = semaphore.clone();
+        let (pool, api_key, languages) = (pool.clone(), api_key.clone(), languages.clone());
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            match tmdb_api::fetch_media_extended(&api_key, &languages, tmdb_id, &media_type).await {
+                Ok(info) => {
+                    if let Err(e) = storage::replace_media_extras(&pool, tmdb_id, &media_type, &info).await {
+                        log::warn!("Storing extras for TMDb {} failed: {}", tmdb_id, e);
+                    }
+                }
+                Err(e) => log::warn!("Fetching certifications for TMDb {} failed: {}", tmdb_id, e),
+            }
+        });
+    }
+    while let Some(joined) = tasks.join_next().await {
+        if let Err(e) = joined {
+            log::warn!("Fetching certifications aborted: {}", e);
+        }
+    }
+}
+
+/// PIN aus 4 bis 8 Ziffern.
+pub fn validate_pin(pin: &str) -> Result<(), String> {
+    if (4..=8).contains(&pin.len()) && pin.chars().all(|c| c.is_ascii_digit()) {
+        Ok(())
+    } else {
+        Err("Die PIN muss aus 4 bis 8 Ziffern bestehen.".into())
+    }
+}
+
+fn digest(salt: &str, pin: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(salt.as_bytes());
+    hasher.update(pin.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Format `salt$hash`.
+pub fn hash_pin(pin: &str) -> String {
+    let salt = uuid::Uuid::new_v4().simple().to_string();
+    format!("{}${}", salt, digest(&salt, pin))
+}
+
+pub fn verify_pin(pin: &str, stored: &str) -> bool {
+    match stored.split_once('$') {
+        Some((salt, hash)) => digest(salt, pin) == hash,
+        None => false,
+    }
+}
@@ -1,7 +1,11 @@
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
-const API_URL: &str = "https://api.perplexity.ai/chat/completions";
+use super::endpoints;
+
+fn chat_url() -> String {
+    format!("{}/chat/completions", endpoints::perplexity())
+}
 
 #[derive(Debug, Serialize)]
 struct ChatRequest {
@@ -37,6 +41,8 @@ pub struct MediaClassification {
     pub title: String,
     pub media_type: String,
     pub series_name: Option<String>,
+    #[serde(default)]
+    pub media_kind: Option<String>,
 }
 
 pub async fn classify_media_titles(
@@ -58,14 +64,16 @@ pub async fn classify_media_titles(
         r#"Classify these media file titles (from filenames). For each title, determine:
 1. media_type: "movie" or "series" (use "series" if it's a TV show episode)
 2. series_name: If it's a series episode, the canonical series name for grouping (e.g. "Breaking Bad", "The Office"). If movie, use null.
+3. media_kind: one of "movie", "series", "documentary", "anime", "concert", "standup", "home_video"
 
 Rules:
 - S01E01, 1x01, Episode X, Season X = series
 - Standalone film titles = movie
 - "The Office" US vs UK are different series - include country if needed (e.g. "The Office (US)")
+- media_kind describes the content: documentaries (film or series) = documentary, Japanese animation = anime, recorded concerts/live shows = concert, stand-up comedy specials = standup, private recordings (holidays, birthdays, family) = home_video, otherwise movie or series
 - Return ONLY valid JSON, no markdown or explanation
 
-Return a JSON array with one object per title, in the same order. Each object: {{"title":"original title","media_type":"movie|series","series_name":"Name or null"}}
+Return a JSON array with one object per title, in the same order. Each object: {{"title":"original title","media_type":"movie|series","series_name":"Name or null","media_kind":"movie|series|documentary|anime|concert|standup|home_video"}}
 
 Titles:
 {}"#,
@@ -74,7 +82,7 @@ Titles:
 
     let client = Client::new();
     let resp = client
-        .post(API_URL)
+        .post(chat_url())
         .header("Authorization", format!("Bearer {}", api_key))
         .header("Content-Type", "application/json")
         .json(&ChatRequest {
@@ -165,7 +173,7 @@ Beispiel: [{{"title":"Inception","year":2010}},{{"title":"Interstellar","year":2
 
     let client = Client::new();
     let resp = client
-        .post(API_URL)
+        .post(chat_url())
         .header("Authorization", format!("Bearer {}", api_key))
         .header("Content-Type", "application/json")
         .json(&ChatRequest {
@@ -231,7 +239,7 @@ WICHTIG: Gib immer "height" (Körpergröße in m, z.B. "1.78 m") und "partner_st
 
     let client = Client::new();
     let resp = client
-        .post(API_URL)
+        .post(chat_url())
         .header("Authorization", format!("Bearer {}", api_key))
         .header("Content-Type", "application/json")
         .json(&ChatRequest {
@@ -306,7 +314,7 @@ Jeder Fact 1-2 Sätze. Hintergrundinfos, Dreharbeiten, Easter Eggs, etc."#,
 
     let client = Client::new();
     let resp = client
-        .post(API_URL)
+        .post(chat_url())
         .header("Authorization", format!("Bearer {}", api_key))
         .header("Content-Type", "application/json")
         .json(&ChatRequest {
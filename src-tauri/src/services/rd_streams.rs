@@ -2,9 +2,12 @@ use reqwest::Client;
 use serde::Deserialize;
 use serde_json::Value;
 
-const RD_REST: &str = "https://api.real-debrid.com/rest/1.0";
-const TORRENTIO_BASE: &str = "https://torrentio.strem.fun";
-const TMDB_BASE: &str = "https://api.themoviedb.org/3";
+use super::endpoints;
+use super::tmdb_http;
+
+fn rd_rest() -> String {
+    format!("{}/rest/1.0", endpoints::real_debrid())
+}
 
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct RdStreamLink {
@@ -225,11 +228,12 @@ pub async fn fetch_debridio_streams(
 
 pub async fn search_tmdb_for_id(
     api_key: &str,
+    language: &str,
     title: &str,
     year: Option<u32>,
     media_type: &str,
 ) -> Result<Option<i64>, String> {
-    search_tmdb_for_match(api_key, title, year, media_type)
+    search_tmdb_for_match(api_key, language, title, year, media_type)
         .await
         .map(|o| o.map(|(id, _, _)| id))
 }
@@ -237,6 +241,7 @@ pub async fn search_tmdb_for_id(
 /// Returns (tmdb_id, title, year) for the first TMDb search match.
 pub async fn search_tmdb_for_match(
     api_key: &str,
+    language: &str,
     title: &str,
     year: Option<u32>,
     media_type: &str,
@@ -244,8 +249,8 @@ pub async fn search_tmdb_for_match(
     let endpoint = if media_type == "series" { "tv" } else { "movie" };
     let query = urlencode_query(title);
     let mut url = format!(
-        "{}/search/{}?api_key={}&query={}&language=de-DE",
-        TMDB_BASE, endpoint, api_key, query
+        "{}/search/{}?api_key={}&query={}&language={}",
+        endpoints::tmdb(), endpoint, api_key, query, language
     );
     if let Some(y) = year {
         let param = if media_type == "series" {
@@ -255,9 +260,7 @@ pub async fn search_tmdb_for_match(
         };
         url.push_str(&format!("&{}={}", param, y));
     }
-    let client = Client::new();
-    let resp = client.get(&url).send().await.map_err(|e| e.to_string())?;
-    let data: TmdbSearchResponse = resp.json().await.map_err(|e| e.to_string())?;
+    let data: TmdbSearchResponse = tmdb_http::get_json(&url).await?;
     Ok(data.results.and_then(|r| {
         r.into_iter().next().map(|r| {
             let id = r.id;
@@ -276,11 +279,9 @@ pub async fn get_imdb_id(api_key: &str, tmdb_id: i64, media_type: &str) -> Resul
     let endpoint = if media_type == "series" { "tv" } else { "movie" };
     let url = format!(
         "{}/{}/{}/external_ids?api_key={}",
-        TMDB_BASE, endpoint, tmdb_id, api_key
+        endpoints::tmdb(), endpoint, tmdb_id, api_key
     );
-    let client = Client::new();
-    let resp = client.get(&url).send().await.map_err(|e| e.to_string())?;
-    let data: TmdbExternalIds = resp.json().await.map_err(|e| e.to_string())?;
+    let data: TmdbExternalIds = tmdb_http::get_json(&url).await?;
     Ok(data.imdb_id)
 }
 
@@ -298,7 +299,7 @@ pub async fn fetch_torrentio_streams(
     } else {
         imdb_id.to_string()
     };
-    let url = format!("{}/stream/{}/{}.json", TORRENTIO_BASE, stream_type, path);
+    let url = format!("{}/stream/{}/{}.json", endpoints::torrentio(), stream_type, path);
     let client = Client::new();
     let resp = client.get(&url).send().await.map_err(|e| e.to_string())?;
     let data: TorrentioResponse = resp.json().await.map_err(|e| e.to_string())?;
@@ -408,11 +409,6 @@ fn extract_size_from_title(title: &str) -> String {
     "?".to_string()
 }
 
-const YTS_APIS: &[&str] = &[
-    "https://yts.mx/api/v2/list_movies.json",
-    "https://yts.lt/api/v2/list_movies.json",
-];
-
 pub async fn search_movie_torrents(title: &str, _year: Option<u32>) -> Result<Vec<(String, String, String)>, String> {
     let client = Client::new();
     let mut results = Vec::new();
@@ -420,8 +416,8 @@ pub async fn search_movie_torrents(title: &str, _year: Option<u32>) -> Result<Ve
 
     for query_term in variants.iter().take(3) {
         let query = query_term.replace(' ', "+");
-        for &base in YTS_APIS {
-            let url = format!("{}?query_term={}&limit=10", base, query);
+        for base in endpoints::yts() {
+            let url = format!("{}/list_movies.json?query_term={}&limit=10", base, query);
             if let Ok(resp) = client.get(&url).send().await {
                 if let Ok(data) = resp.json::<YtsResponse>().await {
                     if let Some(d) = data.data {
@@ -463,7 +459,7 @@ pub async fn info_hash_to_stream(
     let magnet = format!("magnet:?xt=urn:btih:{}", hash_lower);
 
     let client = Client::new();
-    let add_url = format!("{}/torrents/addMagnet", RD_REST);
+    let add_url = format!("{}/torrents/addMagnet", rd_rest());
     let add_resp = client
         .post(&add_url)
         .header("Authorization", format!("Bearer {}", access_token))
@@ -482,7 +478,7 @@ pub async fn info_hash_to_stream(
     for _ in 0..45 {
         tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
 
-        let info_url = format!("{}/torrents/info/{}", RD_REST, add_data.id);
+        let info_url = format!("{}/torrents/info/{}", rd_rest(), add_data.id);
         let info_resp = client
             .get(&info_url)
             .header("Authorization", format!("Bearer {}", access_token))
@@ -519,7 +515,7 @@ pub async fn info_hash_to_stream(
                     } else {
                         files[0].id
                     };
-                    let select_url = format!("{}/torrents/selectFiles/{}", RD_REST, add_data.id);
+                    let select_url = format!("{}/torrents/selectFiles/{}", rd_rest(), add_data.id);
                     let _ = client
                         .post(&select_url)
                         .header("Authorization", format!("Bearer {}", access_token))
@@ -545,7 +541,7 @@ pub async fn magnet_to_stream(
 ) -> Result<Option<RdStreamLink>, String> {
     let client = Client::new();
 
-    let add_url = format!("{}/torrents/addMagnet", RD_REST);
+    let add_url = format!("{}/torrents/addMagnet", rd_rest());
     let add_resp = client
         .post(&add_url)
         .header("Authorization", format!("Bearer {}", access_token))
@@ -561,7 +557,7 @@ pub async fn magnet_to_stream(
 
     let add_data: RdAddMagnetResponse = add_resp.json().await.map_err(|e| e.to_string())?;
 
-    let select_url = format!("{}/torrents/selectFiles/{}", RD_REST, add_data.id);
+    let select_url = format!("{}/torrents/selectFiles/{}", rd_rest(), add_data.id);
     let _ = client
         .post(&select_url)
         .header("Authorization", format!("Bearer {}", access_token))
@@ -573,7 +569,7 @@ pub async fn magnet_to_stream(
     for _ in 0..30 {
         tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
 
-        let info_url = format!("{}/torrents/info/{}", RD_REST, add_data.id);
+        let info_url = format!("{}/torrents/info/{}", rd_rest(), add_data.id);
         let info_resp = client
             .get(&info_url)
             .header("Authorization", format!("Bearer {}", access_token))
@@ -611,7 +607,7 @@ struct UnrestrictResponse {
 
 async fn unrestrict_rd_link(access_token: &str, link: &str) -> Result<UnrestrictResponse, String> {
     let client = Client::new();
-    let url = format!("{}/unrestrict/link", RD_REST);
+    let url = format!("{}/unrestrict/link", rd_rest());
 
     let resp = client
         .post(&url)
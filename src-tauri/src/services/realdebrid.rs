@@ -1,7 +1,8 @@
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
-const RD_BASE_URL: &str = "https://api.real-debrid.com";
+use super::endpoints;
+
 const RD_OPEN_CLIENT_ID: &str = "X245A4XAIBGVM";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,7 +42,7 @@ pub struct UnrestrictedLink {
 
 pub async fn get_device_code() -> Result<DeviceCodeResponse, String> {
     let client = Client::new();
-    let url = format!("{}/oauth/v2/device/code?client_id={}&new_credentials=yes", RD_BASE_URL, RD_OPEN_CLIENT_ID);
+    let url = format!("{}/oauth/v2/device/code?client_id={}&new_credentials=yes", endpoints::real_debrid(), RD_OPEN_CLIENT_ID);
 
     let response = client
         .get(&url)
@@ -59,7 +60,7 @@ pub async fn poll_credentials(device_code: &str) -> Result<Option<CredentialsRes
     let client = Client::new();
     let url = format!(
         "{}/oauth/v2/device/credentials?client_id={}&code={}",
-        RD_BASE_URL, RD_OPEN_CLIENT_ID, device_code
+        endpoints::real_debrid(), RD_OPEN_CLIENT_ID, device_code
     );
 
     let response = client
@@ -82,7 +83,7 @@ pub async fn poll_credentials(device_code: &str) -> Result<Option<CredentialsRes
 
 pub async fn get_token(client_id: &str, client_secret: &str, device_code: &str) -> Result<TokenResponse, String> {
     let client = Client::new();
-    let url = format!("{}/oauth/v2/token", RD_BASE_URL);
+    let url = format!("{}/oauth/v2/token", endpoints::real_debrid());
 
     let params = [
         ("client_id", client_id),
@@ -115,7 +116,7 @@ pub struct RdUser {
 
 pub async fn get_user(access_token: &str) -> Result<RdUser, String> {
     let client = Client::new();
-    let url = format!("{}/rest/1.0/user", RD_BASE_URL);
+    let url = format!("{}/rest/1.0/user", endpoints::real_debrid());
 
     let response = client
         .get(&url)
@@ -136,7 +137,7 @@ pub async fn get_user(access_token: &str) -> Result<RdUser, String> {
 
 pub async fn unrestrict_link(access_token: &str, link: &str) -> Result<UnrestrictedLink, String> {
     let client = Client::new();
-    let url = format!("{}/rest/1.0/unrestrict/link", RD_BASE_URL);
+    let url = format!("{}/rest/1.0/unrestrict/link", endpoints::real_debrid());
 
     let response = client
         .post(&url)
@@ -1,4 +1,11 @@
 use crate::db::DbPool;
+use crate::services::discovery::{DiscoverListResult, DiscoverQuery, SavedDiscoverQuery};
+use crate::services::media_kind::tmdb_media_type;
+use crate::services::metadata_provider::MetadataTranslation;
+use crate::services::tmdb_api::{
+    video_url, Certification, CollectionPart, Keyword, MediaExtendedInfo, MediaVideo, SimilarTitle,
+    TmdbCollection,
+};
 use serde::{Deserialize, Serialize};
 use sqlx::Row;
 
@@ -12,6 +19,8 @@ pub struct MediaItem {
     pub file_size: Option<i64>,
     #[serde(default)]
     pub series_name: Option<String>,
+    #[serde(default)]
+    pub media_kind: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -31,6 +40,29 @@ pub struct MediaMetadata {
     pub genres: Option<String>,
     pub cast_crew: Option<String>,
     pub raw_response: Option<String>,
+    /// JSON-Liste aller Bewertungsquellen (`RatingSource`).
+    #[serde(default)]
+    pub ratings: Option<String>,
+    /// JSON-Objekt Feld -> Provider, aus dem der Wert stammt.
+    #[serde(default)]
+    pub field_sources: Option<String>,
+    #[serde(default)]
+    pub match_confidence: Option<f64>,
+    /// "auto", "needs_review" oder "confirmed".
+    #[serde(default)]
+    pub match_status: Option<String>,
+    /// Sprache von Titel/Handlung (BCP-47).
+    #[serde(default)]
+    pub language: Option<String>,
+    /// JSON-Liste gesperrter Felder, die beim Aktualisieren erhalten bleiben.
+    #[serde(default)]
+    pub locked_fields: Option<String>,
+    #[serde(default)]
+    pub imdb_id: Option<String>,
+    #[serde(default)]
+    pub tvdb_id: Option<i64>,
+    #[serde(default)]
+    pub wikidata_id: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -46,15 +78,18 @@ pub struct RealDebridToken {
     pub created_at: String,
 }
 
+/// Beim erneuten Scan wird die Medienart neu erkannt, außer sie wurde von Hand gesetzt.
 pub async fn upsert_media_item(pool: &DbPool, item: &MediaItem) -> Result<(), String> {
     sqlx::query(
-        "INSERT INTO media_items (id, title, file_path, file_hash, media_type, file_size, series_name, created_at, updated_at)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, datetime('now'), datetime('now'))
+        "INSERT INTO media_items (id, title, file_path, file_hash, media_type, file_size, series_name, media_kind, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, datetime('now'), datetime('now'))
          ON CONFLICT(file_path) DO UPDATE SET
            title = excluded.title,
            file_hash = excluded.file_hash,
            media_type = excluded.media_type,
            file_size = excluded.file_size,
+           media_kind = CASE WHEN media_items.media_kind_manual = 1
+             THEN media_items.media_kind ELSE COALESCE(excluded.media_kind, media_items.media_kind) END,
            updated_at = datetime('now')"
     )
     .bind(&item.id)
@@ -64,6 +99,7 @@ pub async fn upsert_media_item(pool: &DbPool, item: &MediaItem) -> Result<(), St
     .bind(&item.media_type)
     .bind(&item.file_size)
     .bind(&item.series_name)
+    .bind(&item.media_kind)
     .execute(pool)
     .await
     .map_err(|e| e.to_string())?;
@@ -72,10 +108,11 @@ pub async fn upsert_media_item(pool: &DbPool, item: &MediaItem) -> Result<(), St
 
 pub async fn get_items_without_metadata(pool: &DbPool) -> Result<Vec<MediaItem>, String> {
     let rows = sqlx::query(
-        "SELECT m.id, m.title, m.file_path, m.file_hash, m.media_type, m.file_size, m.series_name, m.created_at, m.updated_at
+        "SELECT m.id, m.title, m.file_path, m.file_hash, m.media_type, m.file_size, m.series_name, m.media_kind, m.created_at, m.updated_at
          FROM media_items m
          LEFT JOIN metadata meta ON meta.media_item_id = m.id
-         WHERE meta.id IS NULL
+         LEFT JOIN media_kinds k ON k.id = m.media_kind
+         WHERE meta.id IS NULL AND COALESCE(k.metadata_lookup, 1) = 1
          ORDER BY m.title"
     )
     .fetch_all(pool)
@@ -90,6 +127,7 @@ pub async fn get_items_without_metadata(pool: &DbPool) -> Result<Vec<MediaItem>,
         media_type: row.get("media_type"),
         file_size: row.get("file_size"),
         series_name: row.get("series_name"),
+        media_kind: row.get("media_kind"),
         created_at: row.get("created_at"),
         updated_at: row.get("updated_at"),
     }).collect();
@@ -97,11 +135,17 @@ pub async fn get_items_without_metadata(pool: &DbPool) -> Result<Vec<MediaItem>,
     Ok(items)
 }
 
-pub async fn get_all_media_items(pool: &DbPool) -> Result<Vec<MediaItem>, String> {
+pub async fn get_all_media_items(
+    pool: &DbPool,
+    media_kind: Option<&str>,
+) -> Result<Vec<MediaItem>, String> {
     let rows = sqlx::query(
-        "SELECT id, title, file_path, file_hash, media_type, file_size, series_name, created_at, updated_at
-         FROM media_items ORDER BY title"
+        "SELECT id, title, file_path, file_hash, media_type, file_size, series_name, media_kind, created_at, updated_at
+         FROM media_items
+         WHERE ?1 IS NULL OR media_kind = ?1
+         ORDER BY title"
     )
+    .bind(media_kind)
     .fetch_all(pool)
     .await
     .map_err(|e| e.to_string())?;
@@ -114,6 +158,7 @@ pub async fn get_all_media_items(pool: &DbPool) -> Result<Vec<MediaItem>, String
         media_type: row.get("media_type"),
         file_size: row.get("file_size"),
         series_name: row.get("series_name"),
+        media_kind: row.get("media_kind"),
         created_at: row.get("created_at"),
         updated_at: row.get("updated_at"),
     }).collect();
@@ -124,26 +169,36 @@ pub async fn get_all_media_items(pool: &DbPool) -> Result<Vec<MediaItem>, String
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LibraryItemWithMeta {
     pub item: MediaItem,
+    /// Bei Serien die Metadaten der Serie, nicht der Folge.
     pub metadata: Option<MediaMetadata>,
+    #[serde(default)]
+    pub episode: Option<EpisodeMetadata>,
 }
 
-pub async fn get_all_media_items_with_metadata(pool: &DbPool) -> Result<Vec<LibraryItemWithMeta>, String> {
+pub async fn get_all_media_items_with_metadata(
+    pool: &DbPool,
+    media_kind: Option<&str>,
+) -> Result<Vec<LibraryItemWithMeta>, String> {
     let rows = sqlx::query(
         "SELECT m.id, m.title, m.file_path, m.file_hash, m.media_type, m.file_size, m.series_name,
-                m.created_at, m.updated_at,
+                m.media_kind, m.created_at, m.updated_at,
                 meta.id as meta_id, meta.media_item_id, meta.tmdb_id, meta.title as meta_title,
                 meta.overview, meta.poster_url, meta.backdrop_url, meta.release_date,
                 meta.rating, meta.runtime, meta.genres, meta.cast_crew, meta.raw_response,
+                meta.ratings, meta.field_sources, meta.match_confidence, meta.match_status,
+                meta.language, meta.locked_fields, meta.imdb_id, meta.tvdb_id, meta.wikidata_id,
                 meta.created_at as meta_created, meta.updated_at as meta_updated
          FROM media_items m
          LEFT JOIN metadata meta ON meta.media_item_id = m.id
+         WHERE ?1 IS NULL OR m.media_kind = ?1
          ORDER BY m.title"
     )
+    .bind(media_kind)
     .fetch_all(pool)
     .await
     .map_err(|e| e.to_string())?;
 
-    let items = rows.iter().map(|row| {
+    let mut items: Vec<LibraryItemWithMeta> = rows.iter().map(|row| {
         let item = MediaItem {
             id: row.get("id"),
             title: row.get("title"),
@@ -152,6 +207,7 @@ pub async fn get_all_media_items_with_metadata(pool: &DbPool) -> Result<Vec<Libr
             media_type: row.get("media_type"),
             file_size: row.get("file_size"),
             series_name: row.get("series_name"),
+            media_kind: row.get("media_kind"),
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
         };
@@ -170,18 +226,32 @@ pub async fn get_all_media_items_with_metadata(pool: &DbPool) -> Result<Vec<Libr
             genres: row.get("genres"),
             cast_crew: row.get("cast_crew"),
             raw_response: row.get("raw_response"),
+            ratings: row.get("ratings"),
+            field_sources: row.get("field_sources"),
+            match_confidence: row.get("match_confidence"),
+            match_status: row.get("match_status"),
+            language: row.get("language"),
+            locked_fields: row.get("locked_fields"),
+            imdb_id: row.get("imdb_id"),
+            tvdb_id: row.get("tvdb_id"),
+            wikidata_id: row.get("wikidata_id"),
             created_at: row.get("meta_created"),
             updated_at: row.get("meta_updated"),
         });
-        LibraryItemWithMeta { item, metadata }
+        LibraryItemWithMeta { item, metadata, episode: None }
     }).collect();
 
+    let mut episodes = get_all_episode_metadata(pool).await?;
+    for entry in &mut items {
+        entry.episode = episodes.remove(&entry.item.id);
+    }
+
     Ok(items)
 }
 
 pub async fn get_media_item_by_file_path(pool: &DbPool, file_path: &str) -> Result<Option<MediaItem>, String> {
     let row = sqlx::query(
-        "SELECT id, title, file_path, file_hash, media_type, file_size, series_name, created_at, updated_at
+        "SELECT id, title, file_path, file_hash, media_type, file_size, series_name, media_kind, created_at, updated_at
          FROM media_items WHERE file_path = ?1"
     )
     .bind(file_path)
@@ -197,6 +267,7 @@ pub async fn get_media_item_by_file_path(pool: &DbPool, file_path: &str) -> Resu
         media_type: row.get("media_type"),
         file_size: row.get("file_size"),
         series_name: row.get("series_name"),
+        media_kind: row.get("media_kind"),
         created_at: row.get("created_at"),
         updated_at: row.get("updated_at"),
     }))
@@ -204,7 +275,7 @@ pub async fn get_media_item_by_file_path(pool: &DbPool, file_path: &str) -> Resu
 
 pub async fn get_media_item_by_id(pool: &DbPool, id: &str) -> Result<Option<MediaItem>, String> {
     let row = sqlx::query(
-        "SELECT id, title, file_path, file_hash, media_type, file_size, series_name, created_at, updated_at
+        "SELECT id, title, file_path, file_hash, media_type, file_size, series_name, media_kind, created_at, updated_at
          FROM media_items WHERE id = ?1"
     )
     .bind(id)
@@ -220,6 +291,7 @@ pub async fn get_media_item_by_id(pool: &DbPool, id: &str) -> Result<Option<Medi
         media_type: row.get("media_type"),
         file_size: row.get("file_size"),
         series_name: row.get("series_name"),
+        media_kind: row.get("media_kind"),
         created_at: row.get("created_at"),
         updated_at: row.get("updated_at"),
     }))
@@ -230,13 +302,30 @@ pub async fn update_media_classification(
     id: &str,
     media_type: &str,
     series_name: Option<&str>,
+    media_kind: &str,
 ) -> Result<(), String> {
     sqlx::query(
-        "UPDATE media_items SET media_type = ?1, series_name = ?2, updated_at = datetime('now')
-         WHERE id = ?3"
+        "UPDATE media_items SET media_type = ?1, series_name = ?2,
+           media_kind = CASE WHEN media_kind_manual = 1 THEN media_kind ELSE ?3 END,
+           updated_at = datetime('now')
+         WHERE id = ?4"
     )
     .bind(media_type)
     .bind(series_name)
+    .bind(media_kind)
+    .bind(id)
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Von Hand gesetzte Medienart; bleibt bei späteren Scans und Klassifizierungen erhalten.
+pub async fn update_media_kind(pool: &DbPool, id: &str, media_kind: &str) -> Result<(), String> {
+    sqlx::query(
+        "UPDATE media_items SET media_kind = ?1, media_kind_manual = 1, updated_at = datetime('now') WHERE id = ?2"
+    )
+    .bind(media_kind)
     .bind(id)
     .execute(pool)
     .await
@@ -253,15 +342,120 @@ pub async fn delete_media_item(pool: &DbPool, id: &str) -> Result<(), String> {
     Ok(())
 }
 
+// ── Media Kinds ──
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaKind {
+    pub id: String,
+    pub label: String,
+    pub metadata_lookup: bool,
+    pub sort_order: i32,
+}
+
+pub async fn get_media_kinds(pool: &DbPool) -> Result<Vec<MediaKind>, String> {
+    let rows = sqlx::query(
+        "SELECT id, label, metadata_lookup, sort_order FROM media_kinds ORDER BY sort_order, label"
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows.iter().map(|row| MediaKind {
+        id: row.get("id"),
+        label: row.get("label"),
+        metadata_lookup: row.get::<i64, _>("metadata_lookup") != 0,
+        sort_order: row.get("sort_order"),
+    }).collect())
+}
+
+pub async fn get_media_kind(pool: &DbPool, id: &str) -> Result<Option<MediaKind>, String> {
+    Ok(get_media_kinds(pool).await?.into_iter().find(|k| k.id == id))
+}
+
+/// Ob für die Medienart des Items externe Metadaten (TMDb etc.) gesucht werden dürfen.
+pub async fn allows_metadata_lookup(pool: &DbPool, item: &MediaItem) -> Result<bool, String> {
+    match item.media_kind.as_deref() {
+        Some(kind) => Ok(get_media_kind(pool, kind)
+            .await?
+            .map(|k| k.metadata_lookup)
+            .unwrap_or(true)),
+        None => Ok(true),
+    }
+}
+
+/// Felder, die über `locked_fields` gegen Überschreiben gesperrt werden können.
+/// "rating" sperrt auch die Liste aller Bewertungen (`ratings`).
+pub const LOCKABLE_FIELDS: &[&str] = &[
+    "title", "overview", "poster_url", "backdrop_url", "release_date",
+    "rating", "runtime", "genres", "cast_crew",
+];
+
+pub fn parse_locked_fields(locked_fields: Option<&str>) -> Vec<String> {
+    locked_fields
+        .and_then(|json| serde_json::from_str(json).ok())
+        .unwrap_or_default()
+}
+
+/// Übernimmt gesperrte Felder (samt Quelle in `field_sources`) aus dem gespeicherten Stand.
+fn keep_locked_fields(existing: &MediaMetadata, meta: &MediaMetadata) -> MediaMetadata {
+    let locked = parse_locked_fields(existing.locked_fields.as_deref());
+    if locked.is_empty() {
+        return meta.clone();
+    }
+    let mut merged = meta.clone();
+    let mut sources: serde_json::Map<String, serde_json::Value> = merged
+        .field_sources
+        .as_deref()
+        .and_then(|json| serde_json::from_str(json).ok())
+        .unwrap_or_default();
+    let old_sources: serde_json::Map<String, serde_json::Value> = existing
+        .field_sources
+        .as_deref()
+        .and_then(|json| serde_json::from_str(json).ok())
+        .unwrap_or_default();
+    for field in &locked {
+        match field.as_str() {
+            "title" => merged.title = existing.title.clone(),
+            "overview" => merged.overview = existing.overview.clone(),
+            "poster_url" => merged.poster_url = existing.poster_url.clone(),
+            "backdrop_url" => merged.backdrop_url = existing.backdrop_url.clone(),
+            "release_date" => merged.release_date = existing.release_date.clone(),
+            "rating" => {
+                merged.rating = existing.rating;
+                merged.ratings = existing.ratings.clone();
+            }
+            "runtime" => merged.runtime = existing.runtime,
+            "genres" => merged.genres = existing.genres.clone(),
+            "cast_crew" => merged.cast_crew = existing.cast_crew.clone(),
+            _ => continue,
+        }
+        match old_sources.get(field) {
+            Some(source) => sources.insert(field.clone(), source.clone()),
+            None => sources.remove(field),
+        };
+    }
+    merged.field_sources = (!sources.is_empty())
+        .then(|| serde_json::Value::Object(sources).to_string());
+    merged
+}
+
+/// Gesperrte Felder bleiben beim Update unverändert; die Sperrliste selbst wird
+/// nur über `set_locked_fields` geändert. `collection_id` kommt aus
+/// `belongs_to_collection` der TMDb-Rohantwort, `media_credits` aus `cast_crew`.
 pub async fn upsert_metadata(pool: &DbPool, meta: &MediaMetadata) -> Result<(), String> {
     let existing = get_metadata_for_item(pool, &meta.media_item_id).await?;
-    if let Some(_existing_meta) = existing {
+    if let Some(existing_meta) = existing {
+        let meta = &keep_locked_fields(&existing_meta, meta);
         sqlx::query(
             "UPDATE metadata SET
                tmdb_id = ?1, title = ?2, overview = ?3, poster_url = ?4, backdrop_url = ?5,
                release_date = ?6, rating = ?7, runtime = ?8, genres = ?9, cast_crew = ?10,
-               raw_response = ?11, updated_at = datetime('now')
-             WHERE media_item_id = ?12"
+               raw_response = ?11, ratings = ?12, field_sources = ?13,
+               match_confidence = ?14, match_status = ?15, language = ?16,
+               imdb_id = ?17, tvdb_id = ?18, wikidata_id = ?19,
+               collection_id = CASE WHEN json_valid(?11) THEN json_extract(?11, '$.belongs_to_collection.id') END,
+               updated_at = datetime('now')
+             WHERE media_item_id = ?20"
         )
         .bind(meta.tmdb_id)
         .bind(&meta.title)
@@ -274,14 +468,24 @@ pub async fn upsert_metadata(pool: &DbPool, meta: &MediaMetadata) -> Result<(),
         .bind(&meta.genres)
         .bind(&meta.cast_crew)
         .bind(&meta.raw_response)
+        .bind(&meta.ratings)
+        .bind(&meta.field_sources)
+        .bind(meta.match_confidence)
+        .bind(&meta.match_status)
+        .bind(&meta.language)
+        .bind(&meta.imdb_id)
+        .bind(meta.tvdb_id)
+        .bind(&meta.wikidata_id)
         .bind(&meta.media_item_id)
         .execute(pool)
         .await
         .map_err(|e| e.to_string())?;
     } else {
         sqlx::query(
-            "INSERT INTO metadata (id, media_item_id, tmdb_id, title, overview, poster_url, backdrop_url, release_date, rating, runtime, genres, cast_crew, raw_response, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, datetime('now'), datetime('now'))"
+            "INSERT INTO metadata (id, media_item_id, tmdb_id, title, overview, poster_url, backdrop_url, release_date, rating, runtime, genres, cast_crew, raw_response, ratings, field_sources, match_confidence, match_status, language, imdb_id, tvdb_id, wikidata_id, collection_id, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21,
+                     CASE WHEN json_valid(?13) THEN json_extract(?13, '$.belongs_to_collection.id') END,
+                     datetime('now'), datetime('now'))"
         )
         .bind(&meta.id)
         .bind(&meta.media_item_id)
@@ -296,18 +500,27 @@ pub async fn upsert_metadata(pool: &DbPool, meta: &MediaMetadata) -> Result<(),
         .bind(&meta.genres)
         .bind(&meta.cast_crew)
         .bind(&meta.raw_response)
+        .bind(&meta.ratings)
+        .bind(&meta.field_sources)
+        .bind(meta.match_confidence)
+        .bind(&meta.match_status)
+        .bind(&meta.language)
+        .bind(&meta.imdb_id)
+        .bind(meta.tvdb_id)
+        .bind(&meta.wikidata_id)
         .execute(pool)
         .await
         .map_err(|e| e.to_string())?;
     }
-    Ok(())
+    sync_media_credits(pool, &meta.media_item_id).await
 }
 
 pub async fn get_metadata_for_item(pool: &DbPool, media_item_id: &str) -> Result<Option<MediaMetadata>, String> {
     let row = sqlx::query(
         "SELECT id, media_item_id, tmdb_id, title, overview, poster_url, backdrop_url,
                 release_date, rating, runtime, genres, cast_crew, raw_response,
-                created_at, updated_at
+                ratings, field_sources, match_confidence, match_status, language,
+                locked_fields, imdb_id, tvdb_id, wikidata_id, created_at, updated_at
          FROM metadata WHERE media_item_id = ?1
          ORDER BY updated_at DESC LIMIT 1"
     )
@@ -330,204 +543,1058 @@ pub async fn get_metadata_for_item(pool: &DbPool, media_item_id: &str) -> Result
         genres: row.get("genres"),
         cast_crew: row.get("cast_crew"),
         raw_response: row.get("raw_response"),
+        ratings: row.get("ratings"),
+        field_sources: row.get("field_sources"),
+        match_confidence: row.get("match_confidence"),
+        match_status: row.get("match_status"),
+        language: row.get("language"),
+        locked_fields: row.get("locked_fields"),
+        imdb_id: row.get("imdb_id"),
+        tvdb_id: row.get("tvdb_id"),
+        wikidata_id: row.get("wikidata_id"),
         created_at: row.get("created_at"),
         updated_at: row.get("updated_at"),
     }))
 }
 
-pub async fn save_setting(pool: &DbPool, key: &str, value: &str) -> Result<(), String> {
+// ── Episode Metadata ──
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EpisodeMetadata {
+    pub media_item_id: String,
+    pub tmdb_show_id: i64,
+    pub season_number: i64,
+    pub episode_number: i64,
+    pub tmdb_episode_id: Option<i64>,
+    pub name: Option<String>,
+    pub overview: Option<String>,
+    pub air_date: Option<String>,
+    pub still_url: Option<String>,
+    pub runtime: Option<i64>,
+    pub rating: Option<f64>,
+    /// JSON-Liste von `CastMember`.
+    pub guest_stars: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+const EPISODE_COLUMNS: &str =
+    "media_item_id, tmdb_show_id, season_number, episode_number, tmdb_episode_id, name, overview,
+     air_date, still_url, runtime, rating, guest_stars, created_at, updated_at";
+
+fn episode_from_row(r: &sqlx::sqlite::SqliteRow) -> EpisodeMetadata {
+    EpisodeMetadata {
+        media_item_id: r.get("media_item_id"),
+        tmdb_show_id: r.get("tmdb_show_id"),
+        season_number: r.get("season_number"),
+        episode_number: r.get("episode_number"),
+        tmdb_episode_id: r.get("tmdb_episode_id"),
+        name: r.get("name"),
+        overview: r.get("overview"),
+        air_date: r.get("air_date"),
+        still_url: r.get("still_url"),
+        runtime: r.get("runtime"),
+        rating: r.get("rating"),
+        guest_stars: r.get("guest_stars"),
+        created_at: r.get("created_at"),
+        updated_at: r.get("updated_at"),
+    }
+}
+
+pub async fn upsert_episode_metadata(pool: &DbPool, ep: &EpisodeMetadata) -> Result<(), String> {
     sqlx::query(
-        "INSERT INTO settings (key, value) VALUES (?1, ?2)
-         ON CONFLICT(key) DO UPDATE SET value = excluded.value"
+        "INSERT INTO episode_metadata (media_item_id, tmdb_show_id, season_number, episode_number, tmdb_episode_id,
+                                       name, overview, air_date, still_url, runtime, rating, guest_stars,
+                                       created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, datetime('now'), datetime('now'))
+         ON CONFLICT(media_item_id) DO UPDATE SET
+           tmdb_show_id = excluded.tmdb_show_id,
+           season_number = excluded.season_number,
+           episode_number = excluded.episode_number,
+           tmdb_episode_id = excluded.tmdb_episode_id,
+           name = excluded.name,
+           overview = excluded.overview,
+           air_date = excluded.air_date,
+           still_url = excluded.still_url,
+           runtime = excluded.runtime,
+           rating = excluded.rating,
+           guest_stars = excluded.guest_stars,
+           updated_at = datetime('now')"
     )
-    .bind(key)
-    .bind(value)
+    .bind(&ep.media_item_id)
+    .bind(ep.tmdb_show_id)
+    .bind(ep.season_number)
+    .bind(ep.episode_number)
+    .bind(ep.tmdb_episode_id)
+    .bind(&ep.name)
+    .bind(&ep.overview)
+    .bind(&ep.air_date)
+    .bind(&ep.still_url)
+    .bind(ep.runtime)
+    .bind(ep.rating)
+    .bind(&ep.guest_stars)
     .execute(pool)
     .await
     .map_err(|e| e.to_string())?;
     Ok(())
 }
 
-pub async fn load_all_settings(pool: &DbPool) -> Result<std::collections::HashMap<String, String>, String> {
-    let rows = sqlx::query("SELECT key, value FROM settings")
-        .fetch_all(pool)
+pub async fn get_episode_metadata(
+    pool: &DbPool,
+    media_item_id: &str,
+) -> Result<Option<EpisodeMetadata>, String> {
+    let sql = format!("SELECT {} FROM episode_metadata WHERE media_item_id = ?1", EPISODE_COLUMNS);
+    let row = sqlx::query(&sql)
+        .bind(media_item_id)
+        .fetch_optional(pool)
         .await
         .map_err(|e| e.to_string())?;
-
-    let mut map = std::collections::HashMap::new();
-    for row in rows {
-        map.insert(row.get("key"), row.get("value"));
-    }
-    Ok(map)
+    Ok(row.as_ref().map(episode_from_row))
 }
 
-pub async fn save_rd_token(pool: &DbPool, token: &RealDebridToken) -> Result<(), String> {
-    sqlx::query("DELETE FROM realdebrid_tokens")
-        .execute(pool)
+async fn get_all_episode_metadata(
+    pool: &DbPool,
+) -> Result<std::collections::HashMap<String, EpisodeMetadata>, String> {
+    let sql = format!("SELECT {} FROM episode_metadata", EPISODE_COLUMNS);
+    let rows = sqlx::query(&sql)
+        .fetch_all(pool)
         .await
         .map_err(|e| e.to_string())?;
+    Ok(rows
+        .iter()
+        .map(episode_from_row)
+        .map(|ep| (ep.media_item_id.clone(), ep))
+        .collect())
+}
 
-    sqlx::query(
-        "INSERT INTO realdebrid_tokens (client_id, client_secret, access_token, refresh_token, expires_at)
-         VALUES (?1, ?2, ?3, ?4, ?5)"
-    )
-    .bind(&token.client_id)
-    .bind(&token.client_secret)
-    .bind(&token.access_token)
-    .bind(&token.refresh_token)
-    .bind(&token.expires_at)
-    .execute(pool)
-    .await
-    .map_err(|e| e.to_string())?;
+// ── Metadata Translations ──
+
+/// Ersetzt die gecachten Übersetzungen eines Items.
+pub async fn replace_metadata_translations(
+    pool: &DbPool,
+    media_item_id: &str,
+    translations: &[MetadataTranslation],
+) -> Result<(), String> {
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    sqlx::query("DELETE FROM metadata_translations WHERE media_item_id = ?1")
+        .bind(media_item_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    for t in translations {
+        sqlx::query(
+            "INSERT OR REPLACE INTO metadata_translations (media_item_id, language, title, overview, tagline, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, datetime('now'))"
+        )
+        .bind(media_item_id)
+        .bind(&t.language)
+        .bind(&t.title)
+        .bind(&t.overview)
+        .bind(&t.tagline)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+    tx.commit().await.map_err(|e| e.to_string())?;
     Ok(())
 }
 
-pub async fn load_rd_token(pool: &DbPool) -> Result<Option<RealDebridToken>, String> {
-    let row = sqlx::query(
-        "SELECT id, client_id, client_secret, access_token, refresh_token, expires_at, created_at
-         FROM realdebrid_tokens ORDER BY id DESC LIMIT 1"
+pub async fn get_metadata_translations(
+    pool: &DbPool,
+    media_item_id: &str,
+) -> Result<Vec<MetadataTranslation>, String> {
+    let rows = sqlx::query(
+        "SELECT language, title, overview, tagline
+         FROM metadata_translations WHERE media_item_id = ?1
+         ORDER BY language"
     )
-    .fetch_optional(pool)
+    .bind(media_item_id)
+    .fetch_all(pool)
     .await
     .map_err(|e| e.to_string())?;
 
-    Ok(row.map(|row| RealDebridToken {
-        id: row.get("id"),
-        client_id: row.get("client_id"),
-        client_secret: row.get("client_secret"),
-        access_token: row.get("access_token"),
-        refresh_token: row.get("refresh_token"),
-        expires_at: row.get("expires_at"),
-        created_at: row.get("created_at"),
-    }))
+    Ok(rows.iter().map(|r| MetadataTranslation {
+        language: r.get("language"),
+        title: r.get("title"),
+        overview: r.get("overview"),
+        tagline: r.get("tagline"),
+    }).collect())
 }
 
-// ── Discover ──
+// ── Match Review ──
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DiscoverList {
-    pub id: String,
-    pub list_type: String,
-    pub provider: Option<String>,
-    pub country: Option<String>,
+pub struct MatchCandidate {
+    pub media_item_id: String,
+    pub provider: String,
+    pub provider_id: String,
     pub title: String,
-    pub created_at: String,
-    pub items: Vec<DiscoverItemRow>,
+    pub overview: Option<String>,
+    pub poster_url: Option<String>,
+    pub release_date: Option<String>,
+    pub media_type: String,
+    pub score: f64,
+    pub rank: i64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DiscoverItemRow {
-    pub id: String,
-    pub list_id: String,
-    pub media_type: String,
-    pub external_id: Option<String>,
-    pub tmdb_id: Option<i64>,
-    pub title: String,
-    pub year: Option<i32>,
-    pub rating: Option<f64>,
-    pub poster_url: Option<String>,
-    pub overview: Option<String>,
-    pub provider: Option<String>,
-    pub sort_order: i32,
+/// Ersetzt die gespeicherten Kandidaten eines Items (leere Liste = löschen).
+pub async fn replace_match_candidates(
+    pool: &DbPool,
+    media_item_id: &str,
+    candidates: &[MatchCandidate],
+) -> Result<(), String> {
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    sqlx::query("DELETE FROM match_candidates WHERE media_item_id = ?1")
+        .bind(media_item_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    for c in candidates {
+        sqlx::query(
+            "INSERT OR REPLACE INTO match_candidates
+               (media_item_id, provider, provider_id, title, overview, poster_url, release_date, media_type, score, rank)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"
+        )
+        .bind(media_item_id)
+        .bind(&c.provider)
+        .bind(&c.provider_id)
+        .bind(&c.title)
+        .bind(&c.overview)
+        .bind(&c.poster_url)
+        .bind(&c.release_date)
+        .bind(&c.media_type)
+        .bind(c.score)
+        .bind(c.rank)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+    tx.commit().await.map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub async fn get_match_candidates(
+    pool: &DbPool,
+    media_item_id: &str,
+) -> Result<Vec<MatchCandidate>, String> {
+    let rows = sqlx::query(
+        "SELECT media_item_id, provider, provider_id, title, overview, poster_url, release_date,
+                media_type, score, rank
+         FROM match_candidates WHERE media_item_id = ?1
+         ORDER BY rank"
+    )
+    .bind(media_item_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows.iter().map(|r| MatchCandidate {
+        media_item_id: r.get("media_item_id"),
+        provider: r.get("provider"),
+        provider_id: r.get("provider_id"),
+        title: r.get("title"),
+        overview: r.get("overview"),
+        poster_url: r.get("poster_url"),
+        release_date: r.get("release_date"),
+        media_type: r.get("media_type"),
+        score: r.get("score"),
+        rank: r.get("rank"),
+    }).collect())
 }
 
-pub async fn clear_discover_data(pool: &DbPool) -> Result<(), String> {
-    sqlx::query("DELETE FROM discover_items")
-        .execute(pool).await.map_err(|e| e.to_string())?;
-    sqlx::query("DELETE FROM discover_lists")
-        .execute(pool).await.map_err(|e| e.to_string())?;
+pub async fn get_media_item_ids_needing_review(pool: &DbPool) -> Result<Vec<String>, String> {
+    let rows = sqlx::query(
+        "SELECT meta.media_item_id
+         FROM metadata meta
+         JOIN media_items m ON m.id = meta.media_item_id
+         WHERE meta.match_status = 'needs_review'
+         ORDER BY meta.match_confidence, m.title"
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(|r| r.get("media_item_id")).collect())
+}
+
+pub async fn set_locked_fields(
+    pool: &DbPool,
+    media_item_id: &str,
+    fields: &[String],
+) -> Result<(), String> {
+    let value = (!fields.is_empty()).then(|| serde_json::to_string(fields).unwrap_or_default());
+    sqlx::query("UPDATE metadata SET locked_fields = ?1 WHERE media_item_id = ?2")
+        .bind(value)
+        .bind(media_item_id)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
     Ok(())
 }
 
-pub async fn insert_discover_list(
+pub async fn set_match_status(
     pool: &DbPool,
-    id: &str,
-    list_type: &str,
-    provider: Option<&str>,
-    country: &str,
-    title: &str,
+    media_item_id: &str,
+    status: &str,
 ) -> Result<(), String> {
     sqlx::query(
-        "INSERT INTO discover_lists (id, list_type, provider, country, title)
-         VALUES (?1, ?2, ?3, ?4, ?5)"
+        "UPDATE metadata SET match_status = ?1, updated_at = datetime('now') WHERE media_item_id = ?2"
     )
-    .bind(id)
-    .bind(list_type)
-    .bind(provider)
-    .bind(country)
-    .bind(title)
+    .bind(status)
+    .bind(media_item_id)
     .execute(pool)
     .await
     .map_err(|e| e.to_string())?;
     Ok(())
 }
 
-pub async fn insert_discover_item(
+// ── Media Extras (Videos, Keywords, Freigaben) ──
+
+/// Ersetzt Tagline, Videos, Keywords und Freigaben eines TMDb-Titels.
+pub async fn replace_media_extras(
     pool: &DbPool,
-    id: &str,
-    list_id: &str,
+    tmdb_id: i64,
     media_type: &str,
-    external_id: Option<&str>,
-    tmdb_id: Option<i64>,
-    title: &str,
-    year: Option<i32>,
-    rating: Option<f64>,
-    poster_url: Option<&str>,
-    overview: Option<&str>,
-    provider: Option<&str>,
-    raw_json: &str,
-    sort_order: i32,
+    info: &MediaExtendedInfo,
 ) -> Result<(), String> {
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    // Löscht per ON DELETE CASCADE auch Videos, Keywords und Freigaben.
+    sqlx::query("DELETE FROM media_extras WHERE tmdb_id = ?1 AND media_type = ?2")
+        .bind(tmdb_id)
+        .bind(media_type)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
     sqlx::query(
-        "INSERT INTO discover_items (id, list_id, media_type, external_id, tmdb_id, title, year, rating, poster_url, overview, provider, raw_json, sort_order)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)"
+        "INSERT INTO media_extras (tmdb_id, media_type, tagline, fetched_at)
+         VALUES (?1, ?2, ?3, datetime('now'))"
     )
-    .bind(id)
-    .bind(list_id)
-    .bind(media_type)
-    .bind(external_id)
     .bind(tmdb_id)
-    .bind(title)
-    .bind(year)
-    .bind(rating)
-    .bind(poster_url)
-    .bind(overview)
-    .bind(provider)
-    .bind(raw_json)
-    .bind(sort_order)
-    .execute(pool)
+    .bind(media_type)
+    .bind(&info.tagline)
+    .execute(&mut *tx)
     .await
     .map_err(|e| e.to_string())?;
+
+    for (order, v) in info.videos.iter().enumerate() {
+        sqlx::query(
+            "INSERT OR IGNORE INTO media_videos
+               (tmdb_id, media_type, site, video_key, name, video_type, language, official, published_at, sort_order)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"
+        )
+        .bind(tmdb_id)
+        .bind(media_type)
+        .bind(&v.site)
+        .bind(&v.key)
+        .bind(&v.name)
+        .bind(&v.video_type)
+        .bind(&v.language)
+        .bind(v.official)
+        .bind(&v.published_at)
+        .bind(order as i64)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+    for k in &info.keywords {
+        sqlx::query(
+            "INSERT OR IGNORE INTO media_keywords (tmdb_id, media_type, keyword_id, name)
+             VALUES (?1, ?2, ?3, ?4)"
+        )
+        .bind(tmdb_id)
+        .bind(media_type)
+        .bind(k.id)
+        .bind(&k.name)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+    for c in &info.certifications {
+        sqlx::query(
+            "INSERT OR IGNORE INTO media_certifications (tmdb_id, media_type, country, certification, release_date)
+             VALUES (?1, ?2, ?3, ?4, ?5)"
+        )
+        .bind(tmdb_id)
+        .bind(media_type)
+        .bind(&c.country)
+        .bind(&c.certification)
+        .bind(&c.release_date)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+    tx.commit().await.map_err(|e| e.to_string())?;
     Ok(())
 }
 
-pub async fn get_discover_lists(pool: &DbPool) -> Result<Vec<DiscoverList>, String> {
-    let list_rows = sqlx::query(
-        "SELECT id, list_type, provider, country, title, created_at
-         FROM discover_lists ORDER BY rowid"
+/// Gespeicherte Zusätze; `None`, wenn der Titel noch nie abgefragt wurde.
+pub async fn get_media_extras(
+    pool: &DbPool,
+    tmdb_id: i64,
+    media_type: &str,
+) -> Result<Option<MediaExtendedInfo>, String> {
+    let head = sqlx::query("SELECT tagline FROM media_extras WHERE tmdb_id = ?1 AND media_type = ?2")
+        .bind(tmdb_id)
+        .bind(media_type)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    let tagline: Option<String> = match head {
+        Some(row) => row.get("tagline"),
+        None => return Ok(None),
+    };
+
+    let videos = sqlx::query(
+        "SELECT site, video_key, name, video_type, language, official, published_at
+         FROM media_videos WHERE tmdb_id = ?1 AND media_type = ?2
+         ORDER BY sort_order"
     )
+    .bind(tmdb_id)
+    .bind(media_type)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    let keywords = sqlx::query(
+        "SELECT keyword_id, name FROM media_keywords WHERE tmdb_id = ?1 AND media_type = ?2 ORDER BY name"
+    )
+    .bind(tmdb_id)
+    .bind(media_type)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    let certifications = sqlx::query(
+        "SELECT country, certification, release_date
+         FROM media_certifications WHERE tmdb_id = ?1 AND media_type = ?2 ORDER BY country"
+    )
+    .bind(tmdb_id)
+    .bind(media_type)
     .fetch_all(pool)
     .await
     .map_err(|e| e.to_string())?;
 
-    let mut lists = Vec::new();
-
-    for lr in &list_rows {
-        let list_id: String = lr.get("id");
+    Ok(Some(MediaExtendedInfo {
+        tagline,
+        trivia_facts: Vec::new(),
+        videos: videos
+            .iter()
+            .filter_map(|r| {
+                let site: String = r.get("site");
+                let key: String = r.get("video_key");
+                Some(MediaVideo {
+                    url: video_url(&site, &key)?,
+                    site,
+                    key,
+                    name: r.get("name"),
+                    video_type: r.get("video_type"),
+                    language: r.get("language"),
+                    official: r.get("official"),
+                    published_at: r.get("published_at"),
+                })
+            })
+            .collect(),
+        keywords: keywords
+            .iter()
+            .map(|r| Keyword {
+                id: r.get("keyword_id"),
+                name: r.get("name"),
+            })
+            .collect(),
+        certifications: certifications
+            .iter()
+            .map(|r| Certification::new(r.get("country"), r.get("certification"), r.get("release_date")))
+            .collect(),
+    }))
+}
 
-        let item_rows = sqlx::query(
-            "SELECT id, list_id, media_type, external_id, tmdb_id, title, year, rating, poster_url, overview, provider, sort_order
-             FROM discover_items WHERE list_id = ?1 ORDER BY sort_order"
-        )
-        .bind(&list_id)
-        .fetch_all(pool)
+pub async fn has_media_extras(pool: &DbPool, tmdb_id: i64, media_type: &str) -> Result<bool, String> {
+    let row = sqlx::query("SELECT 1 FROM media_extras WHERE tmdb_id = ?1 AND media_type = ?2")
+        .bind(tmdb_id)
+        .bind(media_type)
+        .fetch_optional(pool)
         .await
         .map_err(|e| e.to_string())?;
+    Ok(row.is_some())
+}
 
-        let items: Vec<DiscoverItemRow> = item_rows.iter().map(|r| DiscoverItemRow {
-            id: r.get("id"),
-            list_id: r.get("list_id"),
-            media_type: r.get("media_type"),
+/// Alle gespeicherten Freigaben eines Landes, Schlüssel (tmdb_id, media_type).
+pub async fn get_certifications_for_country(
+    pool: &DbPool,
+    country: &str,
+) -> Result<std::collections::HashMap<(i64, String), String>, String> {
+    let rows = sqlx::query(
+        "SELECT tmdb_id, media_type, certification FROM media_certifications WHERE country = ?1"
+    )
+    .bind(country)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(rows
+        .iter()
+        .map(|r| ((r.get("tmdb_id"), r.get("media_type")), r.get("certification")))
+        .collect())
+}
+
+/// TMDb-Titel der Bibliothek, für die noch keine Zusätze gespeichert sind.
+pub async fn get_library_titles_without_extras(pool: &DbPool) -> Result<Vec<(i64, String)>, String> {
+    let rows = sqlx::query(
+        "SELECT DISTINCT meta.tmdb_id, m.media_type
+         FROM metadata meta
+         JOIN media_items m ON m.id = meta.media_item_id
+         LEFT JOIN media_extras e ON e.tmdb_id = meta.tmdb_id AND e.media_type = m.media_type
+         WHERE meta.tmdb_id IS NOT NULL AND m.media_type IN ('movie', 'series') AND e.tmdb_id IS NULL"
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(|r| (r.get("tmdb_id"), r.get("media_type"))).collect())
+}
+
+// ── Collections ──
+
+/// Filmreihen der Bibliotheksfilme, die noch nie oder vor mehr als
+/// `max_age_days` Tagen geladen wurden; ohne Frist alle.
+pub async fn get_collection_ids_to_fetch(
+    pool: &DbPool,
+    max_age_days: Option<i64>,
+) -> Result<Vec<i64>, String> {
+    let rows = sqlx::query(
+        "SELECT DISTINCT meta.collection_id
+         FROM metadata meta
+         JOIN media_items m ON m.id = meta.media_item_id
+         LEFT JOIN collections c ON c.id = meta.collection_id
+         WHERE meta.collection_id IS NOT NULL AND m.media_type = 'movie'
+           AND (?1 IS NULL OR c.id IS NULL OR c.fetched_at < datetime('now', '-' || ?1 || ' days'))"
+    )
+    .bind(max_age_days)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(|r| r.get("collection_id")).collect())
+}
+
+pub async fn replace_collection(pool: &DbPool, collection: &TmdbCollection) -> Result<(), String> {
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    sqlx::query(
+        "INSERT INTO collections (id, name, overview, poster_url, backdrop_url, fetched_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, datetime('now'))
+         ON CONFLICT(id) DO UPDATE SET name = excluded.name, overview = excluded.overview,
+           poster_url = excluded.poster_url, backdrop_url = excluded.backdrop_url,
+           fetched_at = excluded.fetched_at"
+    )
+    .bind(collection.id)
+    .bind(&collection.name)
+    .bind(&collection.overview)
+    .bind(&collection.poster_url)
+    .bind(&collection.backdrop_url)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+    sqlx::query("DELETE FROM collection_parts WHERE collection_id = ?1")
+        .bind(collection.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    for (order, part) in collection.parts.iter().enumerate() {
+        sqlx::query(
+            "INSERT OR IGNORE INTO collection_parts
+               (collection_id, tmdb_id, title, overview, poster_url, release_date, sort_order)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)"
+        )
+        .bind(collection.id)
+        .bind(part.tmdb_id)
+        .bind(&part.title)
+        .bind(&part.overview)
+        .bind(&part.poster_url)
+        .bind(&part.release_date)
+        .bind(order as i64)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+    tx.commit().await.map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Geladene Filmreihen, zu denen mindestens ein Bibliotheksfilm gehört.
+pub async fn get_library_collections(pool: &DbPool) -> Result<Vec<TmdbCollection>, String> {
+    let rows = sqlx::query(
+        "SELECT id, name, overview, poster_url, backdrop_url FROM collections
+         WHERE id IN (SELECT collection_id FROM metadata WHERE collection_id IS NOT NULL)
+         ORDER BY name"
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    let part_rows = sqlx::query(
+        "SELECT collection_id, tmdb_id, title, overview, poster_url, release_date
+         FROM collection_parts ORDER BY collection_id, sort_order"
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut parts: std::collections::HashMap<i64, Vec<CollectionPart>> = std::collections::HashMap::new();
+    for r in &part_rows {
+        parts.entry(r.get("collection_id")).or_default().push(CollectionPart {
+            tmdb_id: r.get("tmdb_id"),
+            title: r.get("title"),
+            overview: r.get("overview"),
+            poster_url: r.get("poster_url"),
+            release_date: r.get("release_date"),
+        });
+    }
+    Ok(rows
+        .iter()
+        .map(|r| {
+            let id: i64 = r.get("id");
+            TmdbCollection {
+                id,
+                name: r.get("name"),
+                overview: r.get("overview"),
+                poster_url: r.get("poster_url"),
+                backdrop_url: r.get("backdrop_url"),
+                parts: parts.remove(&id).unwrap_or_default(),
+            }
+        })
+        .collect())
+}
+
+/// Bibliotheksfilme nach TMDb-ID (TMDb-ID -> media_item_id).
+pub async fn get_owned_movie_ids(pool: &DbPool) -> Result<std::collections::HashMap<i64, String>, String> {
+    let rows = sqlx::query(
+        "SELECT meta.tmdb_id, m.id
+         FROM metadata meta
+         JOIN media_items m ON m.id = meta.media_item_id
+         WHERE meta.tmdb_id IS NOT NULL AND m.media_type = 'movie'
+         ORDER BY m.title"
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    let mut owned = std::collections::HashMap::new();
+    for r in &rows {
+        owned.entry(r.get::<i64, _>("tmdb_id")).or_insert_with(|| r.get::<String, _>("id"));
+    }
+    Ok(owned)
+}
+
+/// TMDb-Titel der Bibliothek: (TMDb-ID, "movie"/"tv") -> media_item_id.
+/// Bei Serien die erste Episode nach Titel.
+pub async fn get_library_tmdb_items(
+    pool: &DbPool,
+) -> Result<std::collections::HashMap<(i64, String), String>, String> {
+    let rows = sqlx::query(
+        "SELECT meta.tmdb_id, m.media_type, m.id
+         FROM metadata meta
+         JOIN media_items m ON m.id = meta.media_item_id
+         WHERE meta.tmdb_id IS NOT NULL AND m.media_type IN ('movie', 'series')
+         ORDER BY m.title"
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    let mut owned = std::collections::HashMap::new();
+    for r in &rows {
+        let media_type: String = r.get("media_type");
+        let key = (r.get::<i64, _>("tmdb_id"), tmdb_media_type(&media_type).to_string());
+        owned.entry(key).or_insert_with(|| r.get::<String, _>("id"));
+    }
+    Ok(owned)
+}
+
+/// TMDb-ID je Bibliothekseintrag (media_item_id -> TMDb-ID), für den Kindermodus.
+pub async fn get_item_tmdb_ids(pool: &DbPool) -> Result<std::collections::HashMap<String, i64>, String> {
+    let rows = sqlx::query("SELECT media_item_id, tmdb_id FROM metadata WHERE tmdb_id IS NOT NULL")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows
+        .iter()
+        .map(|r| (r.get::<String, _>("media_item_id"), r.get::<i64, _>("tmdb_id")))
+        .collect())
+}
+
+// ── External IDs ──
+
+/// Gespeicherte IMDb-ID eines TMDb-Titels, falls ein Bibliothekseintrag ihn kennt.
+pub async fn get_imdb_id_for_tmdb(
+    pool: &DbPool,
+    tmdb_id: i64,
+    media_type: &str,
+) -> Result<Option<String>, String> {
+    let row = sqlx::query(
+        "SELECT meta.imdb_id
+         FROM metadata meta
+         JOIN media_items m ON m.id = meta.media_item_id
+         WHERE meta.tmdb_id = ?1 AND m.media_type = ?2 AND meta.imdb_id IS NOT NULL
+         LIMIT 1"
+    )
+    .bind(tmdb_id)
+    .bind(media_type)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(row.map(|r| r.get("imdb_id")))
+}
+
+/// Trägt eine nachträglich ermittelte IMDb-ID bei allen Einträgen des TMDb-Titels ein.
+pub async fn set_imdb_id_for_tmdb(
+    pool: &DbPool,
+    tmdb_id: i64,
+    media_type: &str,
+    imdb_id: &str,
+) -> Result<(), String> {
+    sqlx::query(
+        "UPDATE metadata SET imdb_id = ?3
+         WHERE tmdb_id = ?1 AND imdb_id IS NULL
+           AND media_item_id IN (SELECT id FROM media_items WHERE media_type = ?2)"
+    )
+    .bind(tmdb_id)
+    .bind(media_type)
+    .bind(imdb_id)
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Bibliothekseinträge, deren Metadaten die IMDb- bzw. TVDB-ID tragen.
+pub async fn find_media_item_ids_by_external_id(
+    pool: &DbPool,
+    imdb_id: Option<&str>,
+    tvdb_id: Option<i64>,
+) -> Result<Vec<String>, String> {
+    let rows = sqlx::query(
+        "SELECT meta.media_item_id
+         FROM metadata meta
+         JOIN media_items m ON m.id = meta.media_item_id
+         WHERE (?1 IS NOT NULL AND meta.imdb_id = ?1) OR (?2 IS NOT NULL AND meta.tvdb_id = ?2)
+         ORDER BY m.title"
+    )
+    .bind(imdb_id)
+    .bind(tvdb_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(|r| r.get("media_item_id")).collect())
+}
+
+// ── Metadata Refresh ──
+
+fn media_item_from_row(row: &sqlx::sqlite::SqliteRow) -> MediaItem {
+    MediaItem {
+        id: row.get("id"),
+        title: row.get("title"),
+        file_path: row.get("file_path"),
+        file_hash: row.get("file_hash"),
+        media_type: row.get("media_type"),
+        file_size: row.get("file_size"),
+        series_name: row.get("series_name"),
+        media_kind: row.get("media_kind"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+/// Items ohne Metadaten, deren letzter Suchversuch mindestens `retry_hours` zurückliegt.
+pub async fn get_items_pending_metadata(
+    pool: &DbPool,
+    retry_hours: i64,
+    limit: i64,
+) -> Result<Vec<MediaItem>, String> {
+    let rows = sqlx::query(
+        "SELECT m.id, m.title, m.file_path, m.file_hash, m.media_type, m.file_size, m.series_name, m.media_kind, m.created_at, m.updated_at
+         FROM media_items m
+         LEFT JOIN metadata meta ON meta.media_item_id = m.id
+         LEFT JOIN media_kinds k ON k.id = m.media_kind
+         LEFT JOIN metadata_refresh_log log ON log.media_item_id = m.id
+         WHERE meta.id IS NULL AND COALESCE(k.metadata_lookup, 1) = 1
+           AND (log.attempted_at IS NULL OR log.attempted_at < datetime('now', ?1))
+         ORDER BY log.attempted_at IS NOT NULL, log.attempted_at, m.title
+         LIMIT ?2"
+    )
+    .bind(format!("-{} hours", retry_hours))
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(media_item_from_row).collect())
+}
+
+/// Items, deren Metadaten (und letzter Aktualisierungsversuch) älter als
+/// `max_age_days` sind. Offene Reviews und bestätigte Treffer ohne TMDb-ID
+/// werden ausgelassen, weil sie sich nur per neuer Suche aktualisieren liessen.
+pub async fn get_items_with_stale_metadata(
+    pool: &DbPool,
+    max_age_days: i64,
+    limit: i64,
+) -> Result<Vec<MediaItem>, String> {
+    let rows = sqlx::query(
+        "SELECT m.id, m.title, m.file_path, m.file_hash, m.media_type, m.file_size, m.series_name, m.media_kind, m.created_at, m.updated_at
+         FROM metadata meta
+         JOIN media_items m ON m.id = meta.media_item_id
+         LEFT JOIN media_kinds k ON k.id = m.media_kind
+         LEFT JOIN metadata_refresh_log log ON log.media_item_id = m.id
+         WHERE COALESCE(k.metadata_lookup, 1) = 1
+           AND meta.updated_at < datetime('now', ?1)
+           AND (log.attempted_at IS NULL OR log.attempted_at < datetime('now', ?1))
+           AND COALESCE(meta.match_status, 'auto') != 'needs_review'
+           AND (meta.tmdb_id IS NOT NULL OR COALESCE(meta.match_status, 'auto') = 'auto')
+         ORDER BY meta.updated_at
+         LIMIT ?2"
+    )
+    .bind(format!("-{} days", max_age_days))
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(media_item_from_row).collect())
+}
+
+/// Merkt sich einen Aktualisierungsversuch, damit fehlschlagende Items die
+/// übrigen nicht dauerhaft verdrängen.
+pub async fn record_refresh_attempt(
+    pool: &DbPool,
+    media_item_id: &str,
+    error: Option<&str>,
+) -> Result<(), String> {
+    sqlx::query(
+        "INSERT INTO metadata_refresh_log (media_item_id, attempted_at, error)
+         VALUES (?1, datetime('now'), ?2)
+         ON CONFLICT(media_item_id) DO UPDATE SET attempted_at = excluded.attempted_at, error = excluded.error"
+    )
+    .bind(media_item_id)
+    .bind(error)
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// ── Settings ──
+
+pub async fn save_setting(pool: &DbPool, key: &str, value: &str) -> Result<(), String> {
+    sqlx::query(
+        "INSERT INTO settings (key, value) VALUES (?1, ?2)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value"
+    )
+    .bind(key)
+    .bind(value)
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub async fn load_all_settings(pool: &DbPool) -> Result<std::collections::HashMap<String, String>, String> {
+    let rows = sqlx::query("SELECT key, value FROM settings")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut map = std::collections::HashMap::new();
+    for row in rows {
+        map.insert(row.get("key"), row.get("value"));
+    }
+    Ok(map)
+}
+
+pub async fn save_rd_token(pool: &DbPool, token: &RealDebridToken) -> Result<(), String> {
+    sqlx::query("DELETE FROM realdebrid_tokens")
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    sqlx::query(
+        "INSERT INTO realdebrid_tokens (client_id, client_secret, access_token, refresh_token, expires_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)"
+    )
+    .bind(&token.client_id)
+    .bind(&token.client_secret)
+    .bind(&token.access_token)
+    .bind(&token.refresh_token)
+    .bind(&token.expires_at)
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub async fn load_rd_token(pool: &DbPool) -> Result<Option<RealDebridToken>, String> {
+    let row = sqlx::query(
+        "SELECT id, client_id, client_secret, access_token, refresh_token, expires_at, created_at
+         FROM realdebrid_tokens ORDER BY id DESC LIMIT 1"
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(row.map(|row| RealDebridToken {
+        id: row.get("id"),
+        client_id: row.get("client_id"),
+        client_secret: row.get("client_secret"),
+        access_token: row.get("access_token"),
+        refresh_token: row.get("refresh_token"),
+        expires_at: row.get("expires_at"),
+        created_at: row.get("created_at"),
+    }))
+}
+
+// ── Discover ──
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscoverList {
+    pub id: String,
+    pub list_type: String,
+    pub provider: Option<String>,
+    pub provider_logo: Option<String>,
+    pub country: Option<String>,
+    pub title: String,
+    pub created_at: String,
+    pub items: Vec<DiscoverItemRow>,
+    /// Fehler der letzten Aktualisierung.
+    pub last_error: Option<String>,
+    /// Letzte Aktualisierung fehlgeschlagen, die Einträge stammen von davor.
+    #[serde(default)]
+    pub stale: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscoverItemRow {
+    pub id: String,
+    pub list_id: String,
+    pub media_type: String,
+    pub external_id: Option<String>,
+    pub tmdb_id: Option<i64>,
+    pub title: String,
+    pub year: Option<i32>,
+    pub rating: Option<f64>,
+    pub poster_url: Option<String>,
+    pub overview: Option<String>,
+    pub provider: Option<String>,
+    pub sort_order: i32,
+    /// Bibliothekseintrag mit gleicher TMDb-ID.
+    pub media_item_id: Option<String>,
+    #[serde(default)]
+    pub on_watchlist: bool,
+    #[serde(default)]
+    pub watched: bool,
+}
+
+/// Ersetzt die Discover-Listen in einer Transaktion. Erfolgreich geladene Listen
+/// ersetzen die bisherige mit gleichem Schlüssel; bei fehlgeschlagenen bleibt sie
+/// erhalten und bekommt den Fehler. Listen ohne Schlüssel im Ergebnis entfallen.
+pub async fn replace_discover_lists(pool: &DbPool, lists: &[DiscoverListResult]) -> Result<(), String> {
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    let existing: Vec<(String, Option<String>)> = sqlx::query("SELECT id, list_key FROM discover_lists")
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(|r| (r.get("id"), r.get("list_key")))
+        .collect();
+    for (id, key) in &existing {
+        if !lists.iter().any(|l| key.as_deref() == Some(l.key.as_str())) {
+            sqlx::query("DELETE FROM discover_lists WHERE id = ?1")
+                .bind(id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+    }
+
+    for (position, list) in lists.iter().enumerate() {
+        let kept = existing.iter().any(|(_, key)| key.as_deref() == Some(list.key.as_str()));
+        if let (Some(error), true) = (&list.error, kept) {
+            sqlx::query("UPDATE discover_lists SET last_error = ?1, sort_order = ?2 WHERE list_key = ?3")
+                .bind(error)
+                .bind(position as i32)
+                .bind(&list.key)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
+            continue;
+        }
+
+        sqlx::query("DELETE FROM discover_lists WHERE list_key = ?1")
+            .bind(&list.key)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let list_id = uuid::Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO discover_lists (id, list_key, list_type, provider, provider_logo, country, title, sort_order, last_error)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)"
+        )
+        .bind(&list_id)
+        .bind(&list.key)
+        .bind(&list.list_type)
+        .bind(&list.provider)
+        .bind(&list.provider_logo)
+        .bind(&list.country)
+        .bind(&list.title)
+        .bind(position as i32)
+        .bind(&list.error)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        for (i, item) in list.items.iter().enumerate() {
+            sqlx::query(
+                "INSERT INTO discover_items (id, list_id, media_type, tmdb_id, title, year, rating, poster_url, overview, provider, raw_json, sort_order)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)"
+            )
+            .bind(uuid::Uuid::new_v4().to_string())
+            .bind(&list_id)
+            .bind(&item.media_type)
+            .bind(item.tmdb_id)
+            .bind(&item.title)
+            .bind(item.year)
+            .bind(item.rating)
+            .bind(&item.poster_url)
+            .bind(&item.overview)
+            .bind(&list.provider)
+            .bind(&item.raw_json)
+            .bind(i as i32)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        }
+    }
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub async fn get_discover_lists(pool: &DbPool) -> Result<Vec<DiscoverList>, String> {
+    let list_rows = sqlx::query(
+        "SELECT id, list_type, provider, provider_logo, country, title, created_at, last_error
+         FROM discover_lists ORDER BY sort_order, rowid"
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut lists = Vec::new();
+
+    for lr in &list_rows {
+        let list_id: String = lr.get("id");
+        let last_error: Option<String> = lr.get("last_error");
+
+        let item_rows = sqlx::query(
+            "SELECT id, list_id, media_type, external_id, tmdb_id, title, year, rating, poster_url, overview, provider, sort_order
+             FROM discover_items WHERE list_id = ?1 ORDER BY sort_order"
+        )
+        .bind(&list_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let items: Vec<DiscoverItemRow> = item_rows.iter().map(|r| DiscoverItemRow {
+            id: r.get("id"),
+            list_id: r.get("list_id"),
+            media_type: r.get("media_type"),
             external_id: r.get("external_id"),
             tmdb_id: r.get("tmdb_id"),
             title: r.get("title"),
@@ -537,15 +1604,21 @@ pub async fn get_discover_lists(pool: &DbPool) -> Result<Vec<DiscoverList>, Stri
             overview: r.get("overview"),
             provider: r.get("provider"),
             sort_order: r.get("sort_order"),
+            media_item_id: None,
+            on_watchlist: false,
+            watched: false,
         }).collect();
 
         lists.push(DiscoverList {
             id: list_id,
             list_type: lr.get("list_type"),
             provider: lr.get("provider"),
+            provider_logo: lr.get("provider_logo"),
             country: lr.get("country"),
             title: lr.get("title"),
             created_at: lr.get("created_at"),
+            stale: last_error.is_some() && !items.is_empty(),
+            last_error,
             items,
         });
     }
@@ -553,6 +1626,67 @@ pub async fn get_discover_lists(pool: &DbPool) -> Result<Vec<DiscoverList>, Stri
     Ok(lists)
 }
 
+// ── Gespeicherte Discover-Abfragen ──
+
+pub async fn save_discover_query(
+    pool: &DbPool,
+    id: &str,
+    name: &str,
+    query: &DiscoverQuery,
+) -> Result<(), String> {
+    let json = serde_json::to_string(query).map_err(|e| e.to_string())?;
+    sqlx::query(
+        "INSERT INTO discover_queries (id, name, query_json) VALUES (?1, ?2, ?3)
+         ON CONFLICT(id) DO UPDATE SET name = excluded.name, query_json = excluded.query_json"
+    )
+    .bind(id)
+    .bind(name)
+    .bind(&json)
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub async fn get_discover_queries(pool: &DbPool) -> Result<Vec<SavedDiscoverQuery>, String> {
+    let rows = sqlx::query(
+        "SELECT id, name, query_json, created_at FROM discover_queries ORDER BY created_at, rowid"
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows
+        .iter()
+        .filter_map(|r| {
+            let json: String = r.get("query_json");
+            Some(SavedDiscoverQuery {
+                id: r.get("id"),
+                name: r.get("name"),
+                query: serde_json::from_str(&json).ok()?,
+                created_at: r.get("created_at"),
+            })
+        })
+        .collect())
+}
+
+/// Löscht die Abfrage samt ihrer Discover-Liste.
+pub async fn delete_discover_query(pool: &DbPool, id: &str) -> Result<(), String> {
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    sqlx::query("DELETE FROM discover_lists WHERE list_key = ?1")
+        .bind(format!("custom:{}", id))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    sqlx::query("DELETE FROM discover_queries WHERE id = ?1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+    Ok(())
+}
+
 // ── AI Recommendations Cache ──
 
 pub async fn get_ai_recommendations_cached(
@@ -591,3 +1725,328 @@ pub async fn save_ai_recommendations_cache(
     .map_err(|e| e.to_string())?;
     Ok(())
 }
+
+// ── Similar Titles Cache ──
+
+/// Gespeicherte ähnliche Titel, sofern nicht älter als `max_age_days`.
+pub async fn get_similar_titles_cached(
+    pool: &DbPool,
+    tmdb_id: i64,
+    media_type: &str,
+    max_age_days: i64,
+) -> Result<Option<Vec<SimilarTitle>>, String> {
+    let row = sqlx::query(
+        "SELECT data_json FROM similar_titles_cache
+         WHERE tmdb_id = ?1 AND media_type = ?2 AND updated_at >= datetime('now', ?3)"
+    )
+    .bind(tmdb_id)
+    .bind(media_type)
+    .bind(format!("-{} days", max_age_days))
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(row.and_then(|r| {
+        let json: String = r.get("data_json");
+        serde_json::from_str(&json).ok()
+    }))
+}
+
+pub async fn save_similar_titles_cache(
+    pool: &DbPool,
+    tmdb_id: i64,
+    media_type: &str,
+    titles: &[SimilarTitle],
+) -> Result<(), String> {
+    let json = serde_json::to_string(titles).map_err(|e| e.to_string())?;
+    sqlx::query(
+        "INSERT INTO similar_titles_cache (tmdb_id, media_type, data_json, updated_at)
+         VALUES (?1, ?2, ?3, datetime('now'))
+         ON CONFLICT(tmdb_id, media_type) DO UPDATE SET data_json = excluded.data_json, updated_at = datetime('now')"
+    )
+    .bind(tmdb_id)
+    .bind(media_type)
+    .bind(&json)
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// ── Watchlist & Gesehen ──
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchlistEntry {
+    pub tmdb_id: i64,
+    /// "movie" oder "tv"
+    pub media_type: String,
+    pub title: String,
+    pub year: Option<i32>,
+    pub poster_url: Option<String>,
+    pub added_at: Option<String>,
+}
+
+pub async fn add_to_watchlist(pool: &DbPool, entry: &WatchlistEntry) -> Result<(), String> {
+    sqlx::query(
+        "INSERT INTO watchlist (tmdb_id, media_type, title, year, poster_url, added_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, datetime('now'))
+         ON CONFLICT(tmdb_id, media_type) DO UPDATE SET
+            title = excluded.title, year = excluded.year, poster_url = excluded.poster_url"
+    )
+    .bind(entry.tmdb_id)
+    .bind(&entry.media_type)
+    .bind(&entry.title)
+    .bind(entry.year)
+    .bind(&entry.poster_url)
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub async fn remove_from_watchlist(pool: &DbPool, tmdb_id: i64, media_type: &str) -> Result<(), String> {
+    sqlx::query("DELETE FROM watchlist WHERE tmdb_id = ?1 AND media_type = ?2")
+        .bind(tmdb_id)
+        .bind(media_type)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Neueste zuerst.
+pub async fn get_watchlist(pool: &DbPool) -> Result<Vec<WatchlistEntry>, String> {
+    let rows = sqlx::query(
+        "SELECT tmdb_id, media_type, title, year, poster_url, added_at
+         FROM watchlist ORDER BY added_at DESC, title"
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(rows
+        .iter()
+        .map(|r| WatchlistEntry {
+            tmdb_id: r.get("tmdb_id"),
+            media_type: r.get("media_type"),
+            title: r.get("title"),
+            year: r.get("year"),
+            poster_url: r.get("poster_url"),
+            added_at: r.get("added_at"),
+        })
+        .collect())
+}
+
+/// Titel auf der Watchlist als (TMDb-ID, "movie"/"tv").
+pub async fn get_watchlist_keys(pool: &DbPool) -> Result<std::collections::HashSet<(i64, String)>, String> {
+    let rows = sqlx::query("SELECT tmdb_id, media_type FROM watchlist")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(|r| (r.get("tmdb_id"), r.get("media_type"))).collect())
+}
+
+pub async fn set_watched(pool: &DbPool, tmdb_id: i64, media_type: &str, watched: bool) -> Result<(), String> {
+    let query = if watched {
+        "INSERT INTO watched (tmdb_id, media_type, watched_at) VALUES (?1, ?2, datetime('now'))
+         ON CONFLICT(tmdb_id, media_type) DO UPDATE SET watched_at = excluded.watched_at"
+    } else {
+        "DELETE FROM watched WHERE tmdb_id = ?1 AND media_type = ?2"
+    };
+    sqlx::query(query)
+        .bind(tmdb_id)
+        .bind(media_type)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Gesehene Titel als (TMDb-ID, "movie"/"tv").
+pub async fn get_watched_keys(pool: &DbPool) -> Result<std::collections::HashSet<(i64, String)>, String> {
+    let rows = sqlx::query("SELECT tmdb_id, media_type FROM watched")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(|r| (r.get("tmdb_id"), r.get("media_type"))).collect())
+}
+
+// ── People ──
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LibraryPerson {
+    pub id: i64,
+    pub name: String,
+    pub profile_url: Option<String>,
+    /// Bibliothekstitel mit der Person; bei Mitwirkenden die gemeinsamen Titel.
+    pub title_count: i64,
+}
+
+/// Ein Credit einer Person an einem Bibliothekseintrag.
+#[derive(Debug, Clone)]
+pub struct PersonItemCredit {
+    pub media_item_id: String,
+    pub tmdb_id: Option<i64>,
+    pub media_type: String,
+    pub title: String,
+    pub release_date: Option<String>,
+    pub poster_url: Option<String>,
+    pub department: String,
+    pub role: String,
+    pub character: Option<String>,
+}
+
+/// Titel-Schlüssel: Episoden einer Serie zählen über die TMDb-ID als ein Titel.
+const TITLE_KEY: &str = "m.media_type || ':' || COALESCE(meta.tmdb_id, m.id)";
+
+/// Überträgt Besetzung und Crew mit TMDb-ID aus `metadata.cast_crew` nach
+/// `people`/`media_credits`; wie die Nachpflege in Migration 021, nur für ein Item.
+pub async fn sync_media_credits(pool: &DbPool, media_item_id: &str) -> Result<(), String> {
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    sqlx::query("DELETE FROM media_credits WHERE media_item_id = ?1")
+        .bind(media_item_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    for path in ["$.cast", "$.crew"] {
+        sqlx::query(
+            "INSERT INTO people (id, name, profile_url)
+             SELECT json_extract(p.value, '$.id'), json_extract(p.value, '$.name'), json_extract(p.value, '$.profile_url')
+             FROM metadata meta,
+                  json_each(CASE WHEN json_valid(meta.cast_crew) THEN meta.cast_crew END, ?2) p
+             WHERE meta.media_item_id = ?1
+               AND json_type(p.value, '$.id') = 'integer' AND json_type(p.value, '$.name') = 'text'
+             ON CONFLICT(id) DO UPDATE SET name = excluded.name,
+                 profile_url = COALESCE(excluded.profile_url, people.profile_url)"
+        )
+        .bind(media_item_id)
+        .bind(path)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+    sqlx::query(
+        "INSERT OR IGNORE INTO media_credits (media_item_id, person_id, department, role, character, sort_order)
+         SELECT meta.media_item_id, json_extract(p.value, '$.id'), 'Acting', 'Actor',
+                json_extract(p.value, '$.character'), p.key
+         FROM metadata meta,
+              json_each(CASE WHEN json_valid(meta.cast_crew) THEN meta.cast_crew END, '$.cast') p
+         WHERE meta.media_item_id = ?1
+           AND json_type(p.value, '$.id') = 'integer' AND json_type(p.value, '$.name') = 'text'"
+    )
+    .bind(media_item_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+    sqlx::query(
+        "INSERT OR IGNORE INTO media_credits (media_item_id, person_id, department, role, character, sort_order)
+         SELECT meta.media_item_id, json_extract(p.value, '$.id'),
+                COALESCE(json_extract(p.value, '$.department'), 'Crew'),
+                COALESCE(json_extract(p.value, '$.job'), 'Crew'), NULL, p.key
+         FROM metadata meta,
+              json_each(CASE WHEN json_valid(meta.cast_crew) THEN meta.cast_crew END, '$.crew') p
+         WHERE meta.media_item_id = ?1
+           AND json_type(p.value, '$.id') = 'integer' AND json_type(p.value, '$.name') = 'text'"
+    )
+    .bind(media_item_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Alle Credits einer Person in der Bibliothek, neueste Titel zuerst.
+pub async fn get_person_item_credits(pool: &DbPool, person_id: i64) -> Result<Vec<PersonItemCredit>, String> {
+    let rows = sqlx::query(
+        "SELECT c.media_item_id, meta.tmdb_id, m.media_type, COALESCE(meta.title, m.title) AS title,
+                meta.release_date, meta.poster_url, c.department, c.role, c.character
+         FROM media_credits c
+         JOIN media_items m ON m.id = c.media_item_id
+         LEFT JOIN metadata meta ON meta.media_item_id = m.id
+         WHERE c.person_id = ?1
+         ORDER BY meta.release_date DESC, m.title, c.sort_order"
+    )
+    .bind(person_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(rows
+        .iter()
+        .map(|r| PersonItemCredit {
+            media_item_id: r.get("media_item_id"),
+            tmdb_id: r.get("tmdb_id"),
+            media_type: r.get("media_type"),
+            title: r.get("title"),
+            release_date: r.get("release_date"),
+            poster_url: r.get("poster_url"),
+            department: r.get("department"),
+            role: r.get("role"),
+            character: r.get("character"),
+        })
+        .collect())
+}
+
+/// Personen, die mit `person_id` die meisten Bibliothekstitel teilen.
+pub async fn get_person_collaborators(
+    pool: &DbPool,
+    person_id: i64,
+    limit: i64,
+) -> Result<Vec<LibraryPerson>, String> {
+    let sql = format!(
+        "SELECT p.id, p.name, p.profile_url, COUNT(DISTINCT {}) AS title_count
+         FROM media_credits own
+         JOIN media_credits other
+           ON other.media_item_id = own.media_item_id AND other.person_id != own.person_id
+         JOIN people p ON p.id = other.person_id
+         JOIN media_items m ON m.id = own.media_item_id
+         LEFT JOIN metadata meta ON meta.media_item_id = m.id
+         WHERE own.person_id = ?1
+         GROUP BY p.id
+         ORDER BY title_count DESC, p.name
+         LIMIT ?2",
+        TITLE_KEY
+    );
+    let rows = sqlx::query(&sql)
+        .bind(person_id)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(library_person).collect())
+}
+
+/// Personen mit den meisten Bibliothekstiteln; `query` filtert nach Namen.
+pub async fn get_library_people(
+    pool: &DbPool,
+    query: &str,
+    limit: i64,
+) -> Result<Vec<LibraryPerson>, String> {
+    let sql = format!(
+        "SELECT p.id, p.name, p.profile_url, COUNT(DISTINCT {}) AS title_count
+         FROM people p
+         JOIN media_credits c ON c.person_id = p.id
+         JOIN media_items m ON m.id = c.media_item_id
+         LEFT JOIN metadata meta ON meta.media_item_id = m.id
+         WHERE ?1 = '' OR p.name LIKE '%' || ?1 || '%'
+         GROUP BY p.id
+         ORDER BY title_count DESC, p.name
+         LIMIT ?2",
+        TITLE_KEY
+    );
+    let rows = sqlx::query(&sql)
+        .bind(query.trim())
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(library_person).collect())
+}
+
+fn library_person(r: &sqlx::sqlite::SqliteRow) -> LibraryPerson {
+    LibraryPerson {
+        id: r.get("id"),
+        name: r.get("name"),
+        profile_url: r.get("profile_url"),
+        title_count: r.get("title_count"),
+    }
+}
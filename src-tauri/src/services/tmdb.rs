@@ -1,26 +1,30 @@
 use async_trait::async_trait;
-use reqwest::Client;
 use serde::Deserialize;
 use serde_json::Value;
 
+use super::language::LanguagePrefs;
 use super::metadata_provider::{
-    CastCrew, CastMember, CrewMember, MetadataProvider, MetadataResult, SearchQuery,
+    Capabilities, CastCrew, CastMember, CrewMember, EpisodeDetails, ExternalIds, ImageSet,
+    MetadataProvider, MetadataResult, MetadataTranslation, PersonInfo, ProviderImage,
+    RatingSource, SearchQuery,
 };
+use super::tmdb_api;
+use super::endpoints;
+use super::tmdb_http;
 
-const TMDB_BASE: &str = "https://api.themoviedb.org/3";
 const TMDB_IMG: &str = "https://image.tmdb.org/t/p";
 const TMDB_PROFILE_IMG: &str = "https://image.tmdb.org/t/p/w185";
 
 pub struct TmdbProvider {
     api_key: String,
-    client: Client,
+    languages: LanguagePrefs,
 }
 
 impl TmdbProvider {
-    pub fn new(api_key: String) -> Self {
+    pub fn new(api_key: String, languages: LanguagePrefs) -> Self {
         Self {
             api_key,
-            client: Client::new(),
+            languages,
         }
     }
 
@@ -31,6 +35,79 @@ impl TmdbProvider {
     fn backdrop_url(path: &str) -> String {
         format!("{}/w1280{}", TMDB_IMG, path)
     }
+
+    fn ratings(vote_average: Option<f64>, vote_count: Option<i64>) -> Vec<RatingSource> {
+        vote_average
+            .filter(|v| *v > 0.0)
+            .map(|v| vec![RatingSource::new("tmdb", v, vote_count)])
+            .unwrap_or_default()
+    }
+
+    /// Bilder nach Sprachkette sortiert, bestes zuerst. Backdrops bevorzugen
+    /// textlose Bilder, Poster die bevorzugte Sprache.
+    fn rank_images<'a>(
+        &self,
+        images: &'a [ImageEntry],
+        original: Option<&str>,
+        textless_first: bool,
+    ) -> Vec<&'a ImageEntry> {
+        let rank = |i: &ImageEntry| match (&i.iso_639_1, textless_first) {
+            (None, true) => 0,
+            (lang, _) => 1 + self.languages.rank(lang.as_deref(), None, original),
+        };
+        let mut ranked: Vec<&ImageEntry> = images.iter().collect();
+        ranked.sort_by(|a, b| {
+            rank(a).cmp(&rank(b)).then(
+                b.vote_average
+                    .unwrap_or(0.0)
+                    .total_cmp(&a.vote_average.unwrap_or(0.0)),
+            )
+        });
+        ranked
+    }
+
+    fn pick_image(
+        &self,
+        images: &[ImageEntry],
+        original: Option<&str>,
+        textless_first: bool,
+    ) -> Option<String> {
+        self.rank_images(images, original, textless_first)
+            .first()
+            .map(|i| i.file_path.clone())
+    }
+
+    fn provider_image(entry: &ImageEntry, url: fn(&str) -> String) -> ProviderImage {
+        ProviderImage {
+            url: url(&entry.file_path),
+            language: entry.iso_639_1.clone(),
+            width: entry.width,
+            height: entry.height,
+        }
+    }
+
+    /// Übersetzungen für die Sprachen der Kette und die Originalsprache.
+    fn translations(&self, raw: &Value) -> Vec<MetadataTranslation> {
+        let original = raw.get("original_language").and_then(|v| v.as_str());
+        let entries: Vec<TranslationEntry> = raw
+            .pointer("/translations/translations")
+            .cloned()
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default();
+        entries
+            .into_iter()
+            .filter(|t| self.languages.wants(&t.iso_639_1, original))
+            .map(|t| MetadataTranslation {
+                language: match &t.iso_3166_1 {
+                    Some(region) if !region.is_empty() => format!("{}-{}", t.iso_639_1, region),
+                    _ => t.iso_639_1.clone(),
+                },
+                title: t.data.title.filter(|s| !s.is_empty()),
+                overview: t.data.overview.filter(|s| !s.is_empty()),
+                tagline: t.data.tagline.filter(|s| !s.is_empty()),
+            })
+            .collect()
+    }
 }
 
 // --- TMDb response types ---
@@ -51,6 +128,7 @@ struct SearchEntry {
     #[serde(alias = "first_air_date")]
     release_date: Option<String>,
     vote_average: Option<f64>,
+    vote_count: Option<i64>,
     genre_ids: Option<Vec<i64>>,
 }
 
@@ -68,6 +146,7 @@ struct MovieDetail {
     backdrop_path: Option<String>,
     release_date: Option<String>,
     vote_average: Option<f64>,
+    vote_count: Option<i64>,
     runtime: Option<i64>,
     genres: Option<Vec<Genre>>,
 }
@@ -81,10 +160,43 @@ struct TvDetail {
     backdrop_path: Option<String>,
     first_air_date: Option<String>,
     vote_average: Option<f64>,
+    vote_count: Option<i64>,
     episode_run_time: Option<Vec<i64>>,
     genres: Option<Vec<Genre>>,
 }
 
+#[derive(Debug, Deserialize)]
+struct ImageEntry {
+    file_path: String,
+    iso_639_1: Option<String>,
+    vote_average: Option<f64>,
+    width: Option<i64>,
+    height: Option<i64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Images {
+    #[serde(default)]
+    posters: Vec<ImageEntry>,
+    #[serde(default)]
+    backdrops: Vec<ImageEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranslationEntry {
+    iso_639_1: String,
+    iso_3166_1: Option<String>,
+    data: TranslationData,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranslationData {
+    #[serde(alias = "name")]
+    title: Option<String>,
+    overview: Option<String>,
+    tagline: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct CreditsCast {
     id: Option<i64>,
@@ -95,6 +207,7 @@ struct CreditsCast {
 
 #[derive(Debug, Deserialize)]
 struct CreditsCrew {
+    id: Option<i64>,
     name: String,
     job: String,
     department: String,
@@ -115,15 +228,28 @@ impl MetadataProvider for TmdbProvider {
         "tmdb"
     }
 
+    fn priority(&self) -> i32 {
+        0
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::MOVIES
+            | Capabilities::SERIES
+            | Capabilities::EPISODES
+            | Capabilities::PEOPLE
+            | Capabilities::IMAGES
+    }
+
     async fn search(
         &self,
         query: &SearchQuery,
     ) -> Result<Vec<MetadataResult>, String> {
         let endpoint = if query.media_type == "series" { "tv" } else { "movie" };
         let mut url = format!(
-            "{}/search/{}?api_key={}&query={}&language=de-DE",
-            TMDB_BASE, endpoint, self.api_key,
+            "{}/search/{}?api_key={}&query={}&language={}",
+            endpoints::tmdb(), endpoint, self.api_key,
             urlencoded(&query.title),
+            self.languages.primary(),
         );
         if let Some(year) = query.year {
             let param = if query.media_type == "series" {
@@ -134,23 +260,22 @@ impl MetadataProvider for TmdbProvider {
             url.push_str(&format!("&{}={}", param, year));
         }
 
-        let resp = self.client.get(&url).send().await.map_err(|e| e.to_string())?;
-        let raw_text = resp.text().await.map_err(|e| e.to_string())?;
-        let search_resp: SearchResponse =
-            serde_json::from_str(&raw_text).map_err(|e| e.to_string())?;
+        let search_resp: SearchResponse = tmdb_http::get_json(&url).await?;
 
         let results = search_resp
             .results
             .into_iter()
             .take(5)
             .map(|entry| MetadataResult {
-                provider_id: entry.id,
+                provider: self.name().to_string(),
+                provider_id: entry.id.to_string(),
+                media_type: if endpoint == "tv" { "series" } else { "movie" }.to_string(),
                 title: entry.title.unwrap_or_default(),
                 overview: entry.overview.unwrap_or_default(),
                 poster_url: entry.poster_path.as_deref().map(Self::poster_url),
                 backdrop_url: entry.backdrop_path.as_deref().map(Self::backdrop_url),
                 release_date: entry.release_date,
-                rating: entry.vote_average,
+                ratings: Self::ratings(entry.vote_average, entry.vote_count),
                 runtime: None,
                 genres: entry
                     .genre_ids
@@ -160,6 +285,10 @@ impl MetadataProvider for TmdbProvider {
                     .collect(),
                 cast_crew: None,
                 raw_response: String::new(),
+                field_sources: Default::default(),
+                language: Some(self.languages.primary().to_string()),
+                translations: Vec::new(),
+                external_ids: ExternalIds::default(),
             })
             .collect();
 
@@ -168,38 +297,70 @@ impl MetadataProvider for TmdbProvider {
 
     async fn fetch_details(
         &self,
-        provider_id: i64,
+        provider_id: &str,
         media_type: &str,
     ) -> Result<Option<MetadataResult>, String> {
+        let provider_id = tmdb_id(provider_id)?;
         let endpoint = if media_type == "series" { "tv" } else { "movie" };
 
         let detail_url = format!(
-            "{}/{}/{}?api_key={}&language=de-DE",
-            TMDB_BASE, endpoint, provider_id, self.api_key
+            "{}/{}/{}?api_key={}&language={}&append_to_response=translations,images,external_ids,{}&include_image_language={}&include_video_language={}",
+            endpoints::tmdb(), endpoint, provider_id, self.api_key,
+            self.languages.primary(),
+            tmdb_api::extras_append_to_response(media_type),
+            self.languages.include_image_language(),
+            self.languages.include_image_language(),
         );
         let credits_url = format!(
-            "{}/{}/{}/credits?api_key={}&language=de-DE",
-            TMDB_BASE, endpoint, provider_id, self.api_key
+            "{}/{}/{}/credits?api_key={}&language={}",
+            endpoints::tmdb(), endpoint, provider_id, self.api_key, self.languages.primary()
         );
 
         let (detail_resp, credits_resp) = tokio::join!(
-            self.client.get(&detail_url).send(),
-            self.client.get(&credits_url).send(),
+            tmdb_http::get_json_opt::<Value>(&detail_url),
+            tmdb_http::get_json::<CreditsResponse>(&credits_url),
         );
 
-        let detail_text = detail_resp
-            .map_err(|e| e.to_string())?
-            .text()
-            .await
-            .map_err(|e| e.to_string())?;
-
-        let raw_detail: Value =
-            serde_json::from_str(&detail_text).map_err(|e| e.to_string())?;
+        let raw_detail = match detail_resp? {
+            Some(raw) => raw,
+            None => return Ok(None),
+        };
 
-        let credits = match credits_resp {
-            Ok(r) => r.json::<CreditsResponse>().await.ok(),
-            Err(_) => None,
+        // Leere Felder in der bevorzugten Sprache über die Fallback-Kette auffüllen.
+        let localized = |field: &str| -> Option<String> {
+            raw_detail
+                .get(field)
+                .and_then(|v| v.as_str())
+                .filter(|v| !v.trim().is_empty())
+                .map(String::from)
+                .or_else(|| self.languages.pick_translated(&raw_detail, field))
+        };
+        let title_field = if media_type == "series" { "name" } else { "title" };
+        let title = localized(title_field);
+        let overview = localized("overview");
+        let translations = self.translations(&raw_detail);
+        let overview_language = if raw_detail
+            .get("overview")
+            .and_then(|v| v.as_str())
+            .is_some_and(|v| !v.trim().is_empty())
+        {
+            Some(self.languages.primary().to_string())
+        } else {
+            translations
+                .iter()
+                .find(|t| t.overview.is_some() && t.overview == overview)
+                .map(|t| t.language.clone())
         };
+        let original_language = raw_detail.get("original_language").and_then(|v| v.as_str());
+        let images: Images = raw_detail
+            .get("images")
+            .cloned()
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default();
+        let poster_path = self.pick_image(&images.posters, original_language, false);
+        let backdrop_path = self.pick_image(&images.backdrops, original_language, true);
+
+        let credits = credits_resp.ok();
 
         let cast_crew = credits.map(|c| CastCrew {
             cast: c
@@ -224,6 +385,7 @@ impl MetadataProvider for TmdbProvider {
                     )
                 })
                 .map(|m| CrewMember {
+                    id: m.id,
                     name: m.name,
                     job: m.job,
                     department: m.department,
@@ -232,6 +394,8 @@ impl MetadataProvider for TmdbProvider {
                 .collect(),
         });
 
+        let external_ids = external_ids(&raw_detail);
+
         if media_type == "series" {
             let detail: TvDetail =
                 serde_json::from_value(raw_detail.clone()).map_err(|e| e.to_string())?;
@@ -241,13 +405,21 @@ impl MetadataProvider for TmdbProvider {
                 .and_then(|v| v.first().copied());
 
             Ok(Some(MetadataResult {
-                provider_id: detail.id,
-                title: detail.name.unwrap_or_default(),
-                overview: detail.overview.unwrap_or_default(),
-                poster_url: detail.poster_path.as_deref().map(Self::poster_url),
-                backdrop_url: detail.backdrop_path.as_deref().map(Self::backdrop_url),
+                provider: self.name().to_string(),
+                provider_id: detail.id.to_string(),
+                media_type: "series".to_string(),
+                title: title.or(detail.name).unwrap_or_default(),
+                overview: overview.or(detail.overview).unwrap_or_default(),
+                poster_url: poster_path
+                    .or(detail.poster_path)
+                    .as_deref()
+                    .map(Self::poster_url),
+                backdrop_url: backdrop_path
+                    .or(detail.backdrop_path)
+                    .as_deref()
+                    .map(Self::backdrop_url),
                 release_date: detail.first_air_date,
-                rating: detail.vote_average,
+                ratings: Self::ratings(detail.vote_average, detail.vote_count),
                 runtime,
                 genres: detail
                     .genres
@@ -257,19 +429,31 @@ impl MetadataProvider for TmdbProvider {
                     .collect(),
                 cast_crew,
                 raw_response: raw_detail.to_string(),
+                field_sources: Default::default(),
+                language: overview_language,
+                translations,
+                external_ids,
             }))
         } else {
             let detail: MovieDetail =
                 serde_json::from_value(raw_detail.clone()).map_err(|e| e.to_string())?;
 
             Ok(Some(MetadataResult {
-                provider_id: detail.id,
-                title: detail.title.unwrap_or_default(),
-                overview: detail.overview.unwrap_or_default(),
-                poster_url: detail.poster_path.as_deref().map(Self::poster_url),
-                backdrop_url: detail.backdrop_path.as_deref().map(Self::backdrop_url),
+                provider: self.name().to_string(),
+                provider_id: detail.id.to_string(),
+                media_type: "movie".to_string(),
+                title: title.or(detail.title).unwrap_or_default(),
+                overview: overview.or(detail.overview).unwrap_or_default(),
+                poster_url: poster_path
+                    .or(detail.poster_path)
+                    .as_deref()
+                    .map(Self::poster_url),
+                backdrop_url: backdrop_path
+                    .or(detail.backdrop_path)
+                    .as_deref()
+                    .map(Self::backdrop_url),
                 release_date: detail.release_date,
-                rating: detail.vote_average,
+                ratings: Self::ratings(detail.vote_average, detail.vote_count),
                 runtime: detail.runtime,
                 genres: detail
                     .genres
@@ -279,9 +463,62 @@ impl MetadataProvider for TmdbProvider {
                     .collect(),
                 cast_crew,
                 raw_response: raw_detail.to_string(),
+                field_sources: Default::default(),
+                language: overview_language,
+                translations,
+                external_ids,
             }))
         }
     }
+
+    async fn fetch_episode(
+        &self,
+        show_id: &str,
+        season: u32,
+        episode: u32,
+    ) -> Result<Option<EpisodeDetails>, String> {
+        tmdb_api::fetch_episode_details(&self.api_key, &self.languages, tmdb_id(show_id)?, season, episode)
+            .await
+    }
+
+    async fn fetch_images(&self, provider_id: &str, media_type: &str) -> Result<ImageSet, String> {
+        let endpoint = if media_type == "series" { "tv" } else { "movie" };
+        let url = format!(
+            "{}/{}/{}/images?api_key={}&include_image_language={}",
+            endpoints::tmdb(),
+            endpoint,
+            tmdb_id(provider_id)?,
+            self.api_key,
+            self.languages.include_image_language(),
+        );
+        // Die Bilder-Antwort nennt keine Originalsprache.
+        let images: Images = tmdb_http::get_json(&url).await?;
+        Ok(ImageSet {
+            posters: self
+                .rank_images(&images.posters, None, false)
+                .into_iter()
+                .map(|i| Self::provider_image(i, Self::poster_url))
+                .collect(),
+            backdrops: self
+                .rank_images(&images.backdrops, None, true)
+                .into_iter()
+                .map(|i| Self::provider_image(i, Self::backdrop_url))
+                .collect(),
+        })
+    }
+
+    async fn fetch_person(&self, person_id: &str) -> Result<Option<PersonInfo>, String> {
+        tmdb_api::fetch_person_details(&self.api_key, &self.languages, tmdb_id(person_id)?)
+            .await
+            .map(Some)
+    }
+}
+
+fn tmdb_id(provider_id: &str) -> Result<i64, String> {
+    provider_id
+        .trim()
+        .parse()
+        .map_err(|_| format!("Ungültige TMDb-ID: {}", provider_id))
 }
 
 fn urlencoded(s: &str) -> String {
@@ -293,3 +530,18 @@ fn urlencoded(s: &str) -> String {
         })
         .collect()
 }
+
+/// IDs aus `append_to_response=external_ids`; Filme haben die IMDb-ID auch direkt im Datensatz.
+pub fn external_ids(raw: &Value) -> ExternalIds {
+    let ids = raw.get("external_ids").unwrap_or(&Value::Null);
+    let text = |v: Option<&Value>| {
+        v.and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+    };
+    ExternalIds {
+        imdb_id: text(ids.get("imdb_id")).or_else(|| text(raw.get("imdb_id"))),
+        tvdb_id: ids.get("tvdb_id").and_then(|v| v.as_i64()),
+        wikidata_id: text(ids.get("wikidata_id")),
+    }
+}
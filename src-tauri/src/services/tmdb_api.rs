@@ -1,9 +1,17 @@
-//! Erweiterte TMDb-API: Person-Details, Extended Info, Actor-Suggestions.
+//! Erweiterte TMDb-API: Person-Details, Extended Info, Actor-Suggestions,
+//! ähnliche Titel, Suche über externe IDs, Filmreihen.
 
-use reqwest::Client;
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use regex::Regex;
 use serde::Deserialize;
 
-const TMDB_BASE: &str = "https://api.themoviedb.org/3";
+use super::language::LanguagePrefs;
+use super::metadata_provider::{CastMember, EpisodeDetails, PersonInfo};
+use super::endpoints;
+use super::tmdb_http;
+
 const TMDB_IMG: &str = "https://image.tmdb.org/t/p";
 const TMDB_PROFILE: &str = "https://image.tmdb.org/t/p/w185";
 
@@ -24,10 +32,73 @@ pub struct PersonDetails {
     pub children: Option<String>,
 }
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, Clone, Default, serde::Serialize)]
 pub struct MediaExtendedInfo {
     pub tagline: Option<String>,
     pub trivia_facts: Vec<String>,
+    pub videos: Vec<MediaVideo>,
+    pub keywords: Vec<Keyword>,
+    pub certifications: Vec<Certification>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct MediaVideo {
+    /// "YouTube" oder "Vimeo"
+    pub site: String,
+    pub key: String,
+    pub name: Option<String>,
+    /// "Trailer", "Teaser", "Clip", "Featurette", ...
+    pub video_type: Option<String>,
+    pub language: Option<String>,
+    pub official: bool,
+    pub published_at: Option<String>,
+    /// Abspiel-URL beim Videoportal.
+    pub url: String,
+}
+
+/// Abspiel-URL; `None` für Portale, die wir nicht einbinden können.
+pub fn video_url(site: &str, key: &str) -> Option<String> {
+    match site {
+        "YouTube" => Some(format!("https://www.youtube.com/watch?v={}", key)),
+        "Vimeo" => Some(format!("https://vimeo.com/{}", key)),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Keyword {
+    pub id: i64,
+    pub name: String,
+}
+
+/// Altersfreigabe eines Landes, z.B. DE "12" oder US "PG-13".
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Certification {
+    /// ISO 3166-1
+    pub country: String,
+    pub certification: String,
+    pub release_date: Option<String>,
+    /// Anzeigetext, z.B. "FSK 12".
+    pub label: String,
+}
+
+impl Certification {
+    pub fn new(country: String, certification: String, release_date: Option<String>) -> Self {
+        let label = match country.as_str() {
+            "DE" => format!("FSK {}", certification),
+            "AT" => format!("JMK {}", certification),
+            "CH" if certification.chars().all(|c| c.is_ascii_digit()) => {
+                format!("ab {}", certification)
+            }
+            _ => certification.clone(),
+        };
+        Self {
+            country,
+            certification,
+            release_date,
+            label,
+        }
+    }
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -40,6 +111,23 @@ pub struct ActorMovieSuggestion {
     pub match_reason: String,
 }
 
+/// Film- oder Serien-Credit einer Person, als Rolle oder in einer Crew-Abteilung.
+#[derive(Debug, serde::Serialize)]
+pub struct PersonCredit {
+    pub tmdb_id: i64,
+    /// "movie" oder "tv"
+    pub media_type: String,
+    pub title: String,
+    pub release_date: Option<String>,
+    pub year: Option<i32>,
+    pub poster_url: Option<String>,
+    /// "Acting" für Rollen, sonst die TMDb-Abteilung (Directing, Writing, ...).
+    pub department: String,
+    /// Rollenname bzw. Tätigkeit.
+    pub role: Option<String>,
+    pub episode_count: Option<i64>,
+}
+
 #[derive(Debug, Deserialize)]
 struct PersonResponse {
     id: i64,
@@ -52,47 +140,63 @@ struct PersonResponse {
     known_for_department: Option<String>,
 }
 
+/// Eintrag aus `/person/{id}/combined_credits` (Filme und Serien).
 #[derive(Debug, Deserialize)]
-struct MovieCreditsCast {
-    id: Option<i64>,
+struct CombinedCredit {
+    id: i64,
+    /// "movie" oder "tv"
+    media_type: Option<String>,
+    #[serde(alias = "name")]
     title: Option<String>,
+    #[serde(alias = "first_air_date")]
     release_date: Option<String>,
     poster_path: Option<String>,
+    popularity: Option<f64>,
+    #[serde(default)]
+    genre_ids: Vec<i64>,
+    character: Option<String>,
+    job: Option<String>,
+    department: Option<String>,
+    episode_count: Option<i64>,
 }
 
 #[derive(Debug, Deserialize)]
-struct MovieCreditsResponse {
-    cast: Vec<MovieCreditsCast>,
+struct CombinedCreditsResponse {
+    #[serde(default)]
+    cast: Vec<CombinedCredit>,
+    #[serde(default)]
+    crew: Vec<CombinedCredit>,
 }
 
 fn profile_url(path: &str) -> String {
     format!("{}{}", TMDB_PROFILE, path)
 }
 
+fn still_url(path: &str) -> String {
+    format!("{}/w300{}", TMDB_IMG, path)
+}
+
 fn poster_url(path: &str) -> String {
     format!("{}/w342{}", TMDB_IMG, path)
 }
 
-pub async fn fetch_person_details(api_key: &str, person_id: i64) -> Result<PersonDetails, String> {
+pub async fn fetch_person_details(
+    api_key: &str,
+    languages: &LanguagePrefs,
+    person_id: i64,
+) -> Result<PersonInfo, String> {
     let url = format!(
-        "{}/person/{}?api_key={}&language=de-DE",
-        TMDB_BASE, person_id, api_key
+        "{}/person/{}?api_key={}&language={}&append_to_response=translations",
+        endpoints::tmdb(), person_id, api_key, languages.primary()
     );
-    let client = Client::new();
-    let resp = client.get(&url).send().await.map_err(|e| e.to_string())?;
-    let p: PersonResponse = resp.json().await.map_err(|e| e.to_string())?;
-
-    let age = p.birthday.as_ref().and_then(|b| {
-        chrono::NaiveDate::parse_from_str(b, "%Y-%m-%d")
-            .ok()
-            .map(|birth| {
-                let today = chrono::Local::now().naive_local().date();
-                (today.signed_duration_since(birth).num_days() / 365) as i32
-            })
-    });
+    let raw: serde_json::Value = tmdb_http::get_json(&url).await?;
+    let mut p: PersonResponse = serde_json::from_value(raw.clone()).map_err(|e| e.to_string())?;
+    if p.biography.as_deref().map(|b| b.trim().is_empty()).unwrap_or(true) {
+        p.biography = languages.pick_translated(&raw, "biography");
+    }
 
-    Ok(PersonDetails {
-        id: p.id,
+    Ok(PersonInfo {
+        provider_id: p.id.to_string(),
         name: p.name.unwrap_or_default(),
         biography: p.biography.filter(|s| !s.trim().is_empty()),
         profile_url: p.profile_path.as_deref().map(profile_url),
@@ -100,73 +204,758 @@ pub async fn fetch_person_details(api_key: &str, person_id: i64) -> Result<Perso
         deathday: p.deathday,
         place_of_birth: p.place_of_birth,
         known_for_department: p.known_for_department,
-        age: age.map(|d| d as i32),
-        anecdotes: Vec::new(),
-        height: None,
-        partner_status: None,
-        children: None,
     })
 }
 
+impl PersonDetails {
+    /// Aus den Provider-Angaben; Anekdoten und persönliche Angaben ergänzt der Aufrufer.
+    pub fn from_info(id: i64, info: PersonInfo) -> Self {
+        let age = info.birthday.as_ref().and_then(|b| {
+            chrono::NaiveDate::parse_from_str(b, "%Y-%m-%d")
+                .ok()
+                .map(|birth| {
+                    let today = chrono::Local::now().naive_local().date();
+                    (today.signed_duration_since(birth).num_days() / 365) as i32
+                })
+        });
+        Self {
+            id,
+            name: info.name,
+            biography: info.biography,
+            profile_url: info.profile_url,
+            birthday: info.birthday,
+            deathday: info.deathday,
+            place_of_birth: info.place_of_birth,
+            known_for_department: info.known_for_department,
+            age,
+            anecdotes: Vec::new(),
+            height: None,
+            partner_status: None,
+            children: None,
+        }
+    }
+}
+
+/// Alle Film- und Serien-Credits einer Person; `None`, wenn TMDb sie nicht kennt.
+pub async fn fetch_person_credits(
+    api_key: &str,
+    languages: &LanguagePrefs,
+    person_id: i64,
+) -> Result<Option<Vec<PersonCredit>>, String> {
+    let url = format!(
+        "{}/person/{}/combined_credits?api_key={}&language={}",
+        endpoints::tmdb(), person_id, api_key, languages.primary()
+    );
+    let data: CombinedCreditsResponse = match tmdb_http::get_json_opt(&url).await? {
+        Some(d) => d,
+        None => return Ok(None),
+    };
+
+    let cast = data.cast.into_iter().map(|c| {
+        let role = c.character.clone();
+        (c, "Acting".to_string(), role)
+    });
+    let crew = data.crew.into_iter().map(|c| {
+        let department = c.department.clone().unwrap_or_else(|| "Crew".to_string());
+        let role = c.job.clone();
+        (c, department, role)
+    });
+    Ok(Some(
+        cast.chain(crew)
+            .filter_map(|(c, department, role)| {
+                let media_type = match c.media_type.as_deref() {
+                    Some(t @ ("movie" | "tv")) => t.to_string(),
+                    _ => return None,
+                };
+                let title = c.title.filter(|t| !t.trim().is_empty())?;
+                let release_date = c.release_date.filter(|d| !d.is_empty());
+                Some(PersonCredit {
+                    tmdb_id: c.id,
+                    media_type,
+                    title,
+                    year: release_date
+                        .as_deref()
+                        .and_then(|d| d.get(..4))
+                        .and_then(|y| y.parse().ok()),
+                    release_date,
+                    poster_url: c.poster_path.as_deref().map(poster_url),
+                    department,
+                    role: role.filter(|r| !r.trim().is_empty()),
+                    episode_count: c.episode_count,
+                })
+            })
+            .collect(),
+    ))
+}
+
 pub async fn fetch_media_extended(
     api_key: &str,
+    languages: &LanguagePrefs,
     tmdb_id: i64,
     media_type: &str,
 ) -> Result<MediaExtendedInfo, String> {
     let endpoint = if media_type == "series" { "tv" } else { "movie" };
     let url = format!(
-        "{}/{}/{}?api_key={}&language=de-DE",
-        TMDB_BASE, endpoint, tmdb_id, api_key
+        "{}/{}/{}?api_key={}&language={}&append_to_response=translations,{}&include_video_language={}",
+        endpoints::tmdb(),
+        endpoint,
+        tmdb_id,
+        api_key,
+        languages.primary(),
+        extras_append_to_response(media_type),
+        languages.include_image_language()
     );
-    let client = Client::new();
-    let resp = client.get(&url).send().await.map_err(|e| e.to_string())?;
-    let json: serde_json::Value = resp.json().await.map_err(|e| e.to_string())?;
-    let tagline = json.get("tagline").and_then(|v| v.as_str()).map(String::from);
-    Ok(MediaExtendedInfo {
+    let json: serde_json::Value = tmdb_http::get_json(&url).await?;
+    Ok(parse_media_extended(&json, media_type, languages))
+}
+
+/// Zusätze für `append_to_response`, die `parse_media_extended` auswertet.
+/// Filme haben Freigaben unter `release_dates`, Serien unter `content_ratings`.
+pub fn extras_append_to_response(media_type: &str) -> &'static str {
+    if media_type == "series" {
+        "videos,keywords,content_ratings"
+    } else {
+        "videos,keywords,release_dates"
+    }
+}
+
+/// Liest Tagline, Videos, Keywords und Freigaben aus einer Detail-Antwort mit
+/// den Zusätzen aus `extras_append_to_response`.
+pub fn parse_media_extended(
+    raw: &serde_json::Value,
+    media_type: &str,
+    languages: &LanguagePrefs,
+) -> MediaExtendedInfo {
+    let text = |v: &serde_json::Value, key: &str| {
+        v.get(key)
+            .and_then(|x| x.as_str())
+            .filter(|x| !x.trim().is_empty())
+            .map(String::from)
+    };
+    let array = |pointer: &str| {
+        raw.pointer(pointer)
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default()
+    };
+    let original = raw.get("original_language").and_then(|v| v.as_str());
+
+    let tagline = text(raw, "tagline").or_else(|| languages.pick_translated(raw, "tagline"));
+
+    // Trailer vor anderen Videos, offizielle zuerst, dann nach Sprachkette.
+    let mut videos: Vec<MediaVideo> = array("/videos/results")
+        .iter()
+        .filter_map(|v| {
+            let site = text(v, "site")?;
+            let key = text(v, "key")?;
+            Some(MediaVideo {
+                url: video_url(&site, &key)?,
+                site,
+                key,
+                name: text(v, "name"),
+                video_type: text(v, "type"),
+                language: text(v, "iso_639_1"),
+                official: v.get("official").and_then(|o| o.as_bool()).unwrap_or(false),
+                published_at: text(v, "published_at"),
+            })
+        })
+        .collect();
+    let type_rank = |t: Option<&str>| match t {
+        Some("Trailer") => 0,
+        Some("Teaser") => 1,
+        _ => 2,
+    };
+    videos.sort_by_key(|v| {
+        (
+            type_rank(v.video_type.as_deref()),
+            !v.official,
+            languages.rank(v.language.as_deref(), None, original),
+        )
+    });
+
+    let keywords_path = if media_type == "series" { "/keywords/results" } else { "/keywords/keywords" };
+    let keywords = array(keywords_path)
+        .iter()
+        .filter_map(|k| {
+            Some(Keyword {
+                id: k.get("id")?.as_i64()?,
+                name: text(k, "name")?,
+            })
+        })
+        .collect();
+
+    let certifications = if media_type == "series" {
+        array("/content_ratings/results")
+            .iter()
+            .filter_map(|r| {
+                Some(Certification::new(text(r, "iso_3166_1")?, text(r, "rating")?, None))
+            })
+            .collect()
+    } else {
+        array("/release_dates/results")
+            .iter()
+            .filter_map(|r| {
+                let country = text(r, "iso_3166_1")?;
+                let dates = r.get("release_dates")?.as_array()?;
+                // Kinostart (Typ 3) bevorzugen, sonst die erste Angabe mit Freigabe.
+                let best = dates
+                    .iter()
+                    .filter(|d| text(d, "certification").is_some())
+                    .min_by_key(|d| {
+                        let kind = d.get("type").and_then(|t| t.as_i64()).unwrap_or(99);
+                        if kind == 3 { 0 } else { kind }
+                    })?;
+                Some(Certification::new(
+                    country,
+                    text(best, "certification")?,
+                    text(best, "release_date").map(|d| d.get(..10).unwrap_or(&d).to_string()),
+                ))
+            })
+            .collect()
+    };
+
+    MediaExtendedInfo {
         tagline,
         trivia_facts: Vec::new(),
-    })
+        videos,
+        keywords,
+        certifications,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct EpisodeResponse {
+    id: i64,
+    name: Option<String>,
+    overview: Option<String>,
+    air_date: Option<String>,
+    still_path: Option<String>,
+    runtime: Option<i64>,
+    vote_average: Option<f64>,
+    guest_stars: Option<Vec<GuestStar>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GuestStar {
+    id: Option<i64>,
+    name: String,
+    character: Option<String>,
+    profile_path: Option<String>,
+}
+
+/// Details einer einzelnen Folge. `None`, wenn TMDb die Folge nicht kennt.
+pub async fn fetch_episode_details(
+    api_key: &str,
+    languages: &LanguagePrefs,
+    tv_id: i64,
+    season: u32,
+    episode: u32,
+) -> Result<Option<EpisodeDetails>, String> {
+    let url = format!(
+        "{}/tv/{}/season/{}/episode/{}?api_key={}&language={}&append_to_response=translations",
+        endpoints::tmdb(), tv_id, season, episode, api_key, languages.primary()
+    );
+    let raw: serde_json::Value = match tmdb_http::get_json_opt(&url).await? {
+        Some(raw) => raw,
+        None => return Ok(None),
+    };
+    let e: EpisodeResponse = serde_json::from_value(raw.clone()).map_err(|e| e.to_string())?;
+
+    let localized = |value: Option<String>, field: &str| {
+        value
+            .filter(|v| !v.trim().is_empty())
+            .or_else(|| languages.pick_translated(&raw, field))
+    };
+
+    Ok(Some(EpisodeDetails {
+        provider_id: e.id.to_string(),
+        name: localized(e.name, "name"),
+        overview: localized(e.overview, "overview"),
+        air_date: e.air_date.filter(|d| !d.is_empty()),
+        still_url: e.still_path.as_deref().map(still_url),
+        runtime: e.runtime,
+        rating: e.vote_average.filter(|v| *v > 0.0),
+        guest_stars: e
+            .guest_stars
+            .unwrap_or_default()
+            .into_iter()
+            .take(20)
+            .map(|g| CastMember {
+                id: g.id,
+                name: g.name,
+                character: g.character,
+                profile_url: g.profile_path.as_deref().map(profile_url),
+            })
+            .collect(),
+    }))
+}
+
+/// Wie viele Schauspieler bzw. Regie-/Drehbuch-Personen für Vorschläge abgefragt werden.
+const SUGGESTION_ACTORS: usize = 5;
+const SUGGESTION_CREW: usize = 3;
+const SUGGESTION_LIMIT: usize = 12;
+
+/// Nachrichten, Reality und Talkshows: Auftritte dort sind keine Empfehlung.
+const NON_FICTION_TV_GENRES: [i64; 3] = [10763, 10764, 10767];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CreditRole {
+    Actor,
+    Director,
+    Writer,
+}
+
+fn crew_role(job: &str) -> Option<CreditRole> {
+    match job {
+        "Director" => Some(CreditRole::Director),
+        "Screenplay" | "Writer" | "Story" | "Teleplay" | "Novel" | "Creator" => {
+            Some(CreditRole::Writer)
+        }
+        _ => None,
+    }
+}
+
+/// Auftritte als sie selbst (Dokus, Making-ofs, Talkshows).
+fn is_self_appearance(credit: &CombinedCredit) -> bool {
+    let character = credit.character.as_deref().unwrap_or("").to_lowercase();
+    character
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|w| matches!(w, "self" | "himself" | "herself" | "themselves"))
+        || (credit.media_type.as_deref() == Some("tv")
+            && credit.genre_ids.iter().any(|g| NON_FICTION_TV_GENRES.contains(g)))
+}
+
+struct SuggestionCandidate {
+    suggestion: ActorMovieSuggestion,
+    popularity: f64,
+    actors: Vec<i64>,
+    directors: Vec<i64>,
+    writers: Vec<i64>,
+}
+
+impl SuggestionCandidate {
+    fn add(&mut self, role: CreditRole, person_id: i64) {
+        let people = match role {
+            CreditRole::Actor => &mut self.actors,
+            CreditRole::Director => &mut self.directors,
+            CreditRole::Writer => &mut self.writers,
+        };
+        if !people.contains(&person_id) {
+            people.push(person_id);
+        }
+    }
+
+    /// Verschiedene Personen, die der Titel mit dem Ausgangstitel teilt.
+    fn overlap(&self) -> usize {
+        let mut people: Vec<i64> = self
+            .actors
+            .iter()
+            .chain(&self.directors)
+            .chain(&self.writers)
+            .copied()
+            .collect();
+        people.sort_unstable();
+        people.dedup();
+        people.len()
+    }
+
+    /// z.B. "2 Schauspieler + gleiche Regie"
+    fn match_reason(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.actors.is_empty() {
+            parts.push(format!("{} Schauspieler", self.actors.len()));
+        }
+        if !self.directors.is_empty() {
+            parts.push("gleiche Regie".to_string());
+        }
+        if !self.writers.is_empty() {
+            parts.push("gleiches Drehbuch".to_string());
+        }
+        let reason = parts.join(" + ");
+        let mut chars = reason.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => reason,
+        }
+    }
 }
 
-pub async fn fetch_actor_movie_suggestions(
+/// Titel, die Besetzung oder Regie/Drehbuch mit dem Ausgangstitel teilen, aus
+/// Film- und Serien-Credits. Sortiert nach Zahl gemeinsamer Personen, dann
+/// Popularität. `exclude` enthält (TMDb-ID, "movie"/"tv"), etwa die Bibliothek;
+/// der Ausgangstitel fällt immer weg.
+pub async fn fetch_credit_suggestions(
     api_key: &str,
+    languages: &LanguagePrefs,
     tmdb_id: i64,
     media_type: &str,
     actor_ids: &[i64],
-    director_ids: &[i64],
-    limit: usize,
+    crew_ids: &[i64],
+    exclude: &HashSet<(i64, String)>,
 ) -> Result<Vec<ActorMovieSuggestion>, String> {
-    let mut results = Vec::new();
-    for &actor_id in actor_ids.iter().take(5) {
+    let source_type = if media_type == "movie" { "movie" } else { "tv" };
+    let people = actor_ids
+        .iter()
+        .take(SUGGESTION_ACTORS)
+        .map(|id| (*id, true))
+        .chain(crew_ids.iter().take(SUGGESTION_CREW).map(|id| (*id, false)));
+
+    let mut candidates: Vec<SuggestionCandidate> = Vec::new();
+    for (person_id, as_actor) in people {
         let url = format!(
-            "{}/person/{}/movie_credits?api_key={}&language=de-DE",
-            TMDB_BASE, actor_id, api_key
+            "{}/person/{}/combined_credits?api_key={}&language={}",
+            endpoints::tmdb(), person_id, api_key, languages.primary()
         );
-        let client = Client::new();
-        if let Ok(resp) = client.get(&url).send().await {
-            if let Ok(data) = resp.json::<MovieCreditsResponse>().await {
-                for c in data.cast.into_iter().take(limit) {
-                    if let (Some(id), Some(title)) = (c.id, c.title) {
-                        let year = c
-                            .release_date
-                            .as_deref()
-                            .and_then(|s| s.get(..4))
-                            .and_then(|s| s.parse::<i32>().ok());
-                        let poster_url = c.poster_path.as_deref().map(poster_url);
-                        results.push(ActorMovieSuggestion {
-                            tmdb_id: id,
+        let data = match tmdb_http::get_json::<CombinedCreditsResponse>(&url).await {
+            Ok(data) => data,
+            Err(e) => {
+                log::warn!("Credits for TMDb person {} failed: {}", person_id, e);
+                continue;
+            }
+        };
+        let credits: Vec<(CreditRole, CombinedCredit)> = if as_actor {
+            data.cast
+                .into_iter()
+                .filter(|c| !is_self_appearance(c))
+                .map(|c| (CreditRole::Actor, c))
+                .collect()
+        } else {
+            data.crew
+                .into_iter()
+                .filter_map(|c| crew_role(c.job.as_deref().unwrap_or("")).map(|role| (role, c)))
+                .collect()
+        };
+
+        for (role, credit) in credits {
+            let credit_type = match credit.media_type.as_deref() {
+                Some(t @ ("movie" | "tv")) => t.to_string(),
+                _ => continue,
+            };
+            if (credit.id == tmdb_id && credit_type == source_type)
+                || exclude.contains(&(credit.id, credit_type.clone()))
+            {
+                continue;
+            }
+            let title = match credit.title.filter(|t| !t.trim().is_empty()) {
+                Some(t) => t,
+                None => continue,
+            };
+            let existing = candidates
+                .iter_mut()
+                .find(|c| c.suggestion.tmdb_id == credit.id && c.suggestion.media_type == credit_type);
+            let candidate = match existing {
+                Some(c) => c,
+                None => {
+                    candidates.push(SuggestionCandidate {
+                        suggestion: ActorMovieSuggestion {
+                            tmdb_id: credit.id,
                             title,
-                            year,
-                            poster_url,
-                            media_type: "movie".to_string(),
-                            match_reason: "Mit diesem Schauspieler".to_string(),
-                        });
-                    }
+                            year: credit
+                                .release_date
+                                .as_deref()
+                                .and_then(|s| s.get(..4))
+                                .and_then(|s| s.parse::<i32>().ok()),
+                            poster_url: credit.poster_path.as_deref().map(poster_url),
+                            media_type: credit_type,
+                            match_reason: String::new(),
+                        },
+                        popularity: credit.popularity.unwrap_or(0.0),
+                        actors: Vec::new(),
+                        directors: Vec::new(),
+                        writers: Vec::new(),
+                    });
+                    candidates.last_mut().unwrap()
                 }
+            };
+            candidate.add(role, person_id);
+        }
+    }
+
+    candidates.sort_by(|a, b| {
+        b.overlap()
+            .cmp(&a.overlap())
+            .then(b.popularity.total_cmp(&a.popularity))
+    });
+    Ok(candidates
+        .into_iter()
+        .take(SUGGESTION_LIMIT)
+        .map(|c| {
+            let match_reason = c.match_reason();
+            ActorMovieSuggestion {
+                match_reason,
+                ..c.suggestion
             }
+        })
+        .collect())
+}
+
+/// Ähnlicher Titel aus `/recommendations` bzw. `/similar`.
+#[derive(Debug, Clone, serde::Serialize, Deserialize)]
+pub struct SimilarTitle {
+    pub tmdb_id: i64,
+    /// "movie" oder "tv"
+    pub media_type: String,
+    pub title: String,
+    pub year: Option<i32>,
+    pub rating: Option<f64>,
+    pub poster_url: Option<String>,
+    pub overview: Option<String>,
+    /// "recommendations" und/oder "similar"
+    pub sources: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TitleListEntry {
+    id: i64,
+    #[serde(alias = "name")]
+    title: Option<String>,
+    #[serde(alias = "first_air_date")]
+    release_date: Option<String>,
+    poster_path: Option<String>,
+    overview: Option<String>,
+    vote_average: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TitleListResponse {
+    #[serde(default)]
+    results: Vec<TitleListEntry>,
+}
+
+/// Empfehlungen und ähnliche Titel zusammengeführt: Titel aus beiden Listen
+/// zuerst, sonst Empfehlungen vor ähnlichen Titeln in TMDb-Reihenfolge.
+pub async fn fetch_similar_titles(
+    api_key: &str,
+    languages: &LanguagePrefs,
+    tmdb_id: i64,
+    media_type: &str,
+) -> Result<Vec<SimilarTitle>, String> {
+    let media_type = if media_type == "movie" { "movie" } else { "tv" };
+    let mut titles: Vec<SimilarTitle> = Vec::new();
+    for source in ["recommendations", "similar"] {
+        let url = format!(
+            "{}/{}/{}/{}?api_key={}&language={}",
+            endpoints::tmdb(), media_type, tmdb_id, source, api_key, languages.primary()
+        );
+        let data: TitleListResponse = match tmdb_http::get_json_opt(&url).await? {
+            Some(d) => d,
+            None => continue,
+        };
+        for entry in data.results {
+            if let Some(existing) = titles.iter_mut().find(|t| t.tmdb_id == entry.id) {
+                existing.sources.push(source.to_string());
+                continue;
+            }
+            let title = match entry.title.filter(|t| !t.trim().is_empty()) {
+                Some(t) => t,
+                None => continue,
+            };
+            titles.push(SimilarTitle {
+                tmdb_id: entry.id,
+                media_type: media_type.to_string(),
+                title,
+                year: entry
+                    .release_date
+                    .as_deref()
+                    .and_then(|d| d.get(..4))
+                    .and_then(|y| y.parse().ok()),
+                rating: entry.vote_average.filter(|r| *r > 0.0),
+                poster_url: entry.poster_path.as_deref().map(poster_url),
+                overview: entry.overview.filter(|o| !o.trim().is_empty()),
+                sources: vec![source.to_string()],
+            });
+        }
+    }
+    titles.sort_by_key(|t| t.sources.len() < 2);
+    Ok(titles)
+}
+
+/// Externe ID, über die TMDb per `/find` einen Titel auflösen kann.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExternalRef {
+    Imdb(String),
+    Tvdb(i64),
+}
+
+impl ExternalRef {
+    /// Erkennt IMDb-Links/IDs ("https://www.imdb.com/title/tt0111161/", "tt0111161")
+    /// und TVDB-IDs ("tvdb:81189", "https://thetvdb.com/?tab=series&id=81189").
+    pub fn parse(input: &str) -> Option<Self> {
+        let lower = input.trim().to_lowercase();
+
+        static IMDB_ID: OnceLock<Regex> = OnceLock::new();
+        let imdb = IMDB_ID.get_or_init(|| Regex::new(r"\btt(\d{7,})\b").unwrap());
+        if let Some(caps) = imdb.captures(&lower) {
+            return Some(Self::Imdb(format!("tt{}", &caps[1])));
+        }
+
+        let tvdb = if let Some(rest) = lower.strip_prefix("tvdb:") {
+            Some(rest.trim())
+        } else if lower.contains("thetvdb.com") {
+            lower
+                .split(['?', '&', '/'])
+                .map(|part| part.strip_prefix("id=").unwrap_or(part))
+                .filter(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+                .last()
+        } else {
+            None
+        };
+        tvdb.and_then(|id| id.parse().ok()).map(Self::Tvdb)
+    }
+
+    fn source(&self) -> &'static str {
+        match self {
+            Self::Imdb(_) => "imdb_id",
+            Self::Tvdb(_) => "tvdb_id",
         }
     }
-    results.dedup_by(|a, b| a.tmdb_id == b.tmdb_id);
-    results.truncate(limit);
-    Ok(results)
+
+    fn value(&self) -> String {
+        match self {
+            Self::Imdb(id) => id.clone(),
+            Self::Tvdb(id) => id.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FindResult {
+    pub tmdb_id: i64,
+    /// "movie" oder "series"
+    pub media_type: String,
+    pub title: String,
+    pub overview: Option<String>,
+    pub poster_url: Option<String>,
+    pub release_date: Option<String>,
+    pub rating: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FindEntry {
+    id: i64,
+    title: Option<String>,
+    name: Option<String>,
+    overview: Option<String>,
+    poster_path: Option<String>,
+    release_date: Option<String>,
+    first_air_date: Option<String>,
+    vote_average: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FindResponse {
+    #[serde(default)]
+    movie_results: Vec<FindEntry>,
+    #[serde(default)]
+    tv_results: Vec<FindEntry>,
+}
+
+/// TMDb-Titel zu einer IMDb- oder TVDB-ID.
+pub async fn find_by_external_id(
+    api_key: &str,
+    languages: &LanguagePrefs,
+    external: &ExternalRef,
+) -> Result<Vec<FindResult>, String> {
+    let url = format!(
+        "{}/find/{}?api_key={}&language={}&external_source={}",
+        endpoints::tmdb(),
+        external.value(),
+        api_key,
+        languages.primary(),
+        external.source()
+    );
+    let data: FindResponse = tmdb_http::get_json(&url).await?;
+
+    let convert = |e: FindEntry, media_type: &str| FindResult {
+        tmdb_id: e.id,
+        media_type: media_type.to_string(),
+        title: e.title.or(e.name).unwrap_or_default(),
+        overview: e.overview.filter(|o| !o.trim().is_empty()),
+        poster_url: e.poster_path.as_deref().map(poster_url),
+        release_date: e.release_date.or(e.first_air_date).filter(|d| !d.is_empty()),
+        rating: e.vote_average.filter(|v| *v > 0.0),
+    };
+    Ok(data
+        .movie_results
+        .into_iter()
+        .map(|e| convert(e, "movie"))
+        .chain(data.tv_results.into_iter().map(|e| convert(e, "series")))
+        .collect())
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TmdbCollection {
+    pub id: i64,
+    pub name: String,
+    pub overview: Option<String>,
+    pub poster_url: Option<String>,
+    pub backdrop_url: Option<String>,
+    /// Nach Erscheinungsdatum sortiert, unveröffentlichte Teile zuletzt.
+    pub parts: Vec<CollectionPart>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CollectionPart {
+    pub tmdb_id: i64,
+    pub title: String,
+    pub overview: Option<String>,
+    pub poster_url: Option<String>,
+    pub release_date: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CollectionResponse {
+    id: i64,
+    name: Option<String>,
+    overview: Option<String>,
+    poster_path: Option<String>,
+    backdrop_path: Option<String>,
+    #[serde(default)]
+    parts: Vec<FindEntry>,
+}
+
+/// Filmreihe (`belongs_to_collection` der Filmdetails); `None`, wenn TMDb sie nicht mehr kennt.
+pub async fn fetch_collection(
+    api_key: &str,
+    languages: &LanguagePrefs,
+    collection_id: i64,
+) -> Result<Option<TmdbCollection>, String> {
+    let url = format!(
+        "{}/collection/{}?api_key={}&language={}",
+        endpoints::tmdb(),
+        collection_id,
+        api_key,
+        languages.primary()
+    );
+    let data: CollectionResponse = match tmdb_http::get_json_opt(&url).await? {
+        Some(d) => d,
+        None => return Ok(None),
+    };
+
+    let mut parts: Vec<CollectionPart> = data
+        .parts
+        .into_iter()
+        .map(|e| CollectionPart {
+            tmdb_id: e.id,
+            title: e.title.or(e.name).unwrap_or_default(),
+            overview: e.overview.filter(|o| !o.trim().is_empty()),
+            poster_url: e.poster_path.as_deref().map(poster_url),
+            release_date: e.release_date.filter(|d| !d.is_empty()),
+        })
+        .collect();
+    parts.sort_by(|a, b| match (&a.release_date, &b.release_date) {
+        (Some(x), Some(y)) => x.cmp(y),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => a.title.cmp(&b.title),
+    });
+
+    Ok(Some(TmdbCollection {
+        id: data.id,
+        name: data.name.unwrap_or_default(),
+        overview: data.overview.filter(|o| !o.trim().is_empty()),
+        poster_url: data.poster_path.as_deref().map(poster_url),
+        backdrop_url: data.backdrop_path.map(|p| format!("{}/w1280{}", TMDB_IMG, p)),
+        parts,
+    }))
 }
@@ -0,0 +1,292 @@
+//! Gemeinsamer HTTP-Zugang zu TMDb.
+//!
+//! Alle TMDb-Anfragen laufen über einen Client mit Timeouts, einem Token-Bucket
+//! (TMDb drosselt bei zu vielen Anfragen mit HTTP 429) und Retries mit Backoff,
+//! die `Retry-After` beachten. Erfolgreiche Antworten landen in der Tabelle
+//! `http_cache` und werden je nach Endpunkt eine Weile lokal ausgeliefert.
+
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use reqwest::{Client, StatusCode};
+use serde::de::DeserializeOwned;
+use sqlx::Row;
+use tokio::sync::Mutex;
+
+use crate::db::DbPool;
+use crate::services::endpoints;
+
+/// Dauerhafte Rate und Burst des Token-Buckets.
+const RATE_PER_SEC: f64 = 20.0;
+const BURST: f64 = 20.0;
+
+const MAX_RETRIES: u32 = 3;
+const BASE_BACKOFF_MS: u64 = 500;
+/// Längere `Retry-After`-Angaben werden gekappt.
+const MAX_RETRY_AFTER_SECS: u64 = 30;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(20);
+
+const HOUR: i64 = 60 * 60;
+const DAY: i64 = 24 * HOUR;
+
+struct TokenBucket {
+    tokens: f64,
+    last: Instant,
+}
+
+struct TmdbHttp {
+    client: Client,
+    bucket: Mutex<TokenBucket>,
+}
+
+/// Ob eine Anfrage aus `http_cache` beantwortet werden darf. Mit `Refresh` wird
+/// immer bei TMDb nachgefragt und der Eintrag mit der Antwort erneuert.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheMode {
+    Use,
+    Refresh,
+}
+
+static HTTP: OnceLock<TmdbHttp> = OnceLock::new();
+static CACHE_POOL: OnceLock<DbPool> = OnceLock::new();
+
+fn http() -> &'static TmdbHttp {
+    HTTP.get_or_init(|| TmdbHttp {
+        client: Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_else(|_| Client::new()),
+        bucket: Mutex::new(TokenBucket {
+            tokens: BURST,
+            last: Instant::now(),
+        }),
+    })
+}
+
+/// Aktiviert den Antwort-Cache. Ohne Aufruf wird jede Anfrage direkt gestellt.
+/// Abgelaufene Einträge werden dabei aufgeräumt.
+pub async fn init_cache(pool: DbPool) {
+    if let Err(e) = purge_expired(&pool).await {
+        log::warn!("TMDb cache cleanup failed: {}", e);
+    }
+    let _ = CACHE_POOL.set(pool);
+}
+
+/// Wartet, bis der Token-Bucket eine Anfrage erlaubt.
+async fn acquire() {
+    loop {
+        let wait = {
+            let mut bucket = http().bucket.lock().await;
+            let now = Instant::now();
+            let elapsed = now.duration_since(bucket.last).as_secs_f64();
+            bucket.tokens = (bucket.tokens + elapsed * RATE_PER_SEC).min(BURST);
+            bucket.last = now;
+            if bucket.tokens >= 1.0 {
+                bucket.tokens -= 1.0;
+                return;
+            }
+            Duration::from_secs_f64((1.0 - bucket.tokens) / RATE_PER_SEC)
+        };
+        tokio::time::sleep(wait).await;
+    }
+}
+
+/// Cache-Dauer je Endpunkt. Suchen und Listen ändern sich schnell, Credits,
+/// Genres und externe IDs kaum.
+fn ttl_secs(url: &str) -> i64 {
+    let base = endpoints::tmdb();
+    let path = url
+        .strip_prefix(base.as_str())
+        .unwrap_or(url)
+        .split('?')
+        .next()
+        .unwrap_or("");
+    if path.starts_with("/search/") {
+        HOUR
+    } else if path.starts_with("/discover/")
+        || path.starts_with("/trending/")
+        || path.ends_with("/top_rated")
+        || path.ends_with("/popular")
+        || path.ends_with("/now_playing")
+        || path.ends_with("/upcoming")
+        || path.ends_with("/on_the_air")
+        || path.ends_with("/watch/providers")
+    {
+        6 * HOUR
+    } else if path.starts_with("/genre/")
+        || path.ends_with("/credits")
+        || path.ends_with("/movie_credits")
+        || path.ends_with("/external_ids")
+        || path.starts_with("/person/")
+    {
+        7 * DAY
+    } else {
+        3 * DAY
+    }
+}
+
+/// Cache-Schlüssel ist die URL ohne `api_key`, damit ein neuer Schlüssel den
+/// Cache nicht entwertet und der Schlüssel nicht in der Datenbank landet.
+fn cache_key(url: &str) -> String {
+    let (base, query) = match url.split_once('?') {
+        Some(parts) => parts,
+        None => return url.to_string(),
+    };
+    let params: Vec<&str> = query
+        .split('&')
+        .filter(|p| !p.starts_with("api_key="))
+        .collect();
+    if params.is_empty() {
+        base.to_string()
+    } else {
+        format!("{}?{}", base, params.join("&"))
+    }
+}
+
+async fn cache_get(pool: &DbPool, key: &str) -> Option<String> {
+    sqlx::query("SELECT body FROM http_cache WHERE cache_key = ?1 AND expires_at > datetime('now')")
+        .bind(key)
+        .fetch_optional(pool)
+        .await
+        .ok()
+        .flatten()
+        .map(|row| row.get("body"))
+}
+
+async fn cache_put(pool: &DbPool, key: &str, body: &str, ttl: i64) -> Result<(), String> {
+    sqlx::query(
+        "INSERT INTO http_cache (cache_key, body, fetched_at, expires_at)
+         VALUES (?1, ?2, datetime('now'), datetime('now', ?3))
+         ON CONFLICT(cache_key) DO UPDATE SET body = excluded.body,
+           fetched_at = excluded.fetched_at, expires_at = excluded.expires_at"
+    )
+    .bind(key)
+    .bind(body)
+    .bind(format!("+{} seconds", ttl))
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub async fn purge_expired(pool: &DbPool) -> Result<u64, String> {
+    let result = sqlx::query("DELETE FROM http_cache WHERE expires_at <= datetime('now')")
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(result.rows_affected())
+}
+
+pub async fn clear_cache(pool: &DbPool) -> Result<(), String> {
+    sqlx::query("DELETE FROM http_cache")
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn retry_after(resp: &reqwest::Response) -> Option<Duration> {
+    let secs: u64 = resp
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(Duration::from_secs(secs.min(MAX_RETRY_AFTER_SECS)))
+}
+
+fn backoff(attempt: u32) -> Duration {
+    Duration::from_millis(BASE_BACKOFF_MS * 2u64.pow(attempt))
+}
+
+/// Fehlermeldung aus dem TMDb-Fehlerformat (`status_message`), sonst der Statuscode.
+fn error_message(status: StatusCode, body: &str) -> String {
+    let message = serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|v| v.get("status_message").and_then(|m| m.as_str()).map(String::from));
+    match message {
+        Some(m) => format!("TMDb-Fehler {}: {}", status.as_u16(), m),
+        None => format!("TMDb-Fehler {}", status.as_u16()),
+    }
+}
+
+/// Antworttext einer TMDb-URL; `None` bei 404.
+pub async fn get_text_opt(url: &str) -> Result<Option<String>, String> {
+    fetch_text(url, CacheMode::Use).await
+}
+
+async fn fetch_text(url: &str, mode: CacheMode) -> Result<Option<String>, String> {
+    let key = cache_key(url);
+    let pool = CACHE_POOL.get();
+    if let (Some(pool), CacheMode::Use) = (pool, mode) {
+        if let Some(body) = cache_get(pool, &key).await {
+            return Ok(Some(body));
+        }
+    }
+
+    let mut attempt = 0;
+    loop {
+        acquire().await;
+        let resp = match http().client.get(url).send().await {
+            Ok(resp) => resp,
+            // Fehlermeldungen ohne URL, sonst stünde der API-Key im Log.
+            Err(e) if attempt < MAX_RETRIES && (e.is_timeout() || e.is_connect()) => {
+                log::warn!("TMDb request failed ({}), retrying: {}", e.without_url(), key);
+                tokio::time::sleep(backoff(attempt)).await;
+                attempt += 1;
+                continue;
+            }
+            Err(e) => return Err(e.without_url().to_string()),
+        };
+
+        let status = resp.status();
+        if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+            if attempt < MAX_RETRIES {
+                let wait = retry_after(&resp).unwrap_or_else(|| backoff(attempt));
+                log::warn!("TMDb {} – retry in {:?}: {}", status.as_u16(), wait, key);
+                tokio::time::sleep(wait).await;
+                attempt += 1;
+                continue;
+            }
+        }
+        if status == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let body = resp.text().await.map_err(|e| e.without_url().to_string())?;
+        if !status.is_success() {
+            return Err(error_message(status, &body));
+        }
+        if let Some(pool) = pool {
+            if let Err(e) = cache_put(pool, &key, &body, ttl_secs(url)).await {
+                log::warn!("TMDb cache write failed: {}", e);
+            }
+        }
+        return Ok(Some(body));
+    }
+}
+
+pub async fn get_json<T: DeserializeOwned>(url: &str) -> Result<T, String> {
+    get_json_with(url, CacheMode::Use).await
+}
+
+/// Wie [`get_json`]; mit [`CacheMode::Refresh`] am Cache vorbei.
+pub async fn get_json_with<T: DeserializeOwned>(url: &str, mode: CacheMode) -> Result<T, String> {
+    let body = fetch_text(url, mode)
+        .await?
+        .ok_or_else(|| "TMDb-Fehler 404: Nicht gefunden".to_string())?;
+    serde_json::from_str(&body).map_err(|e| e.to_string())
+}
+
+/// Wie [`get_json`], aber 404 ergibt `None`.
+pub async fn get_json_opt<T: DeserializeOwned>(url: &str) -> Result<Option<T>, String> {
+    match get_text_opt(url).await? {
+        Some(body) => serde_json::from_str(&body).map(Some).map_err(|e| e.to_string()),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,241 @@
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::endpoints;
+use super::metadata_provider::{
+    Capabilities, CastCrew, CastMember, ExternalIds, MetadataProvider, MetadataResult, RatingSource,
+    SearchQuery,
+};
+
+/// TVmaze kennt nur Serien; die öffentliche API braucht keinen Key.
+pub struct TvmazeProvider {
+    client: Client,
+}
+
+impl TvmazeProvider {
+    pub fn new() -> Self {
+        Self {
+            client: Client::new(),
+        }
+    }
+}
+
+impl Default for TvmazeProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// TVmaze liefert die Beschreibung als HTML ("<p><b>Lost</b> ist ...</p>").
+fn strip_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut in_tag = false;
+    for c in s.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out.replace("&amp;", "&")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .trim()
+        .to_string()
+}
+
+// --- TVmaze response types ---
+
+#[derive(Debug, Deserialize)]
+struct SearchEntry {
+    show: Show,
+}
+
+#[derive(Debug, Deserialize)]
+struct Show {
+    id: i64,
+    name: Option<String>,
+    summary: Option<String>,
+    premiered: Option<String>,
+    runtime: Option<i64>,
+    #[serde(rename = "averageRuntime")]
+    average_runtime: Option<i64>,
+    genres: Option<Vec<String>>,
+    rating: Option<ShowRating>,
+    image: Option<Image>,
+    externals: Option<Externals>,
+    #[serde(rename = "_embedded")]
+    embedded: Option<Embedded>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Externals {
+    thetvdb: Option<i64>,
+    imdb: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ShowRating {
+    average: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Image {
+    medium: Option<String>,
+    original: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Embedded {
+    cast: Option<Vec<CastEntry>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CastEntry {
+    person: Person,
+    character: Option<Character>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Person {
+    name: String,
+    image: Option<Image>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Character {
+    name: Option<String>,
+}
+
+impl Show {
+    fn into_result(self, provider: &str, raw_response: String) -> MetadataResult {
+        let poster_url = self
+            .image
+            .as_ref()
+            .and_then(|i| i.original.clone().or_else(|| i.medium.clone()));
+        let cast_crew = self.embedded.and_then(|e| e.cast).map(|cast| CastCrew {
+            cast: cast
+                .into_iter()
+                .take(20)
+                .map(|c| CastMember {
+                    id: None,
+                    name: c.person.name,
+                    character: c.character.and_then(|ch| ch.name),
+                    profile_url: c.person.image.and_then(|i| i.medium),
+                })
+                .collect(),
+            crew: Vec::new(),
+        });
+
+        let external_ids = self
+            .externals
+            .map(|e| ExternalIds {
+                imdb_id: e.imdb.filter(|id| !id.is_empty()),
+                tvdb_id: e.thetvdb,
+                wikidata_id: None,
+            })
+            .unwrap_or_default();
+
+        MetadataResult {
+            provider: provider.to_string(),
+            provider_id: self.id.to_string(),
+            media_type: "series".to_string(),
+            title: self.name.unwrap_or_default(),
+            overview: self.summary.as_deref().map(strip_html).unwrap_or_default(),
+            poster_url,
+            backdrop_url: None,
+            release_date: self.premiered,
+            ratings: self
+                .rating
+                .and_then(|r| r.average)
+                .map(|v| vec![RatingSource::new(provider, v, None)])
+                .unwrap_or_default(),
+            runtime: self.average_runtime.or(self.runtime),
+            genres: self.genres.unwrap_or_default(),
+            cast_crew,
+            raw_response,
+            field_sources: Default::default(),
+            language: Some("en".to_string()),
+            translations: Vec::new(),
+            external_ids,
+        }
+    }
+}
+
+// --- trait implementation ---
+
+#[async_trait]
+impl MetadataProvider for TvmazeProvider {
+    fn name(&self) -> &str {
+        "tvmaze"
+    }
+
+    fn priority(&self) -> i32 {
+        20
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::SERIES
+    }
+
+    async fn search(
+        &self,
+        query: &SearchQuery,
+    ) -> Result<Vec<MetadataResult>, String> {
+        if query.media_type != "series" {
+            return Ok(Vec::new());
+        }
+
+        let resp = self
+            .client
+            .get(format!("{}/search/shows", endpoints::tvmaze()))
+            .query(&[("q", query.title.as_str())])
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        let entries: Vec<SearchEntry> = resp.json().await.map_err(|e| e.to_string())?;
+
+        let year = query.year.map(|y| y.to_string());
+        let results = entries
+            .into_iter()
+            .map(|e| e.show)
+            .filter(|show| match (&year, &show.premiered) {
+                (Some(y), Some(p)) => p.starts_with(y.as_str()),
+                _ => true,
+            })
+            .take(5)
+            .map(|show| show.into_result(self.name(), String::new()))
+            .collect();
+
+        Ok(results)
+    }
+
+    async fn fetch_details(
+        &self,
+        provider_id: &str,
+        media_type: &str,
+    ) -> Result<Option<MetadataResult>, String> {
+        if media_type != "series" {
+            return Ok(None);
+        }
+
+        let resp = self
+            .client
+            .get(format!("{}/shows/{}", endpoints::tvmaze(), provider_id))
+            .query(&[("embed", "cast")])
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let raw_text = resp.text().await.map_err(|e| e.to_string())?;
+        let raw: Value = serde_json::from_str(&raw_text).map_err(|e| e.to_string())?;
+        let show: Show = serde_json::from_value(raw.clone()).map_err(|e| e.to_string())?;
+
+        Ok(Some(show.into_result(self.name(), raw.to_string())))
+    }
+}
@@ -0,0 +1,331 @@
+//! Offline-Integrationstests: lokale Ersatz-Server spielen aufgezeichnete
+//! Antworten aus `fixtures/` ab, [`Harness`] lenkt die Dienst-URLs der App dorthin.
+//!
+//! Die URLs gelten prozessweit, deshalb laufen Tests mit einem `Harness`
+//! innerhalb einer Test-Datei nacheinander.
+
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+use app_lib::harness::endpoints::{self, Endpoints};
+
+/// So antwortet TMDb auf unbekannte Pfade; gilt für alle Pfade ohne Route.
+const NOT_FOUND_BODY: &str =
+    r#"{"success":false,"status_code":34,"status_message":"The resource you requested could not be found."}"#;
+
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    /// Datei unter `fixtures/`, z. B. `tmdb/search_movie_inception.json`.
+    pub fn fixture(name: &str) -> Self {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures").join(name);
+        let body = std::fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("Fixture {} nicht lesbar: {}", path.display(), e));
+        Self { status: 200, body }
+    }
+
+    pub fn json(body: serde_json::Value) -> Self {
+        Self {
+            status: 200,
+            body: body.to_string(),
+        }
+    }
+
+    /// Leere Antwort mit Statuscode.
+    pub fn status(status: u16) -> Self {
+        Self {
+            status,
+            body: String::new(),
+        }
+    }
+}
+
+/// Antworten für Methode und Pfad, optional nur bei bestimmten Query-Parametern.
+/// Mehrere Antworten werden der Reihe nach ausgeliefert, die letzte wiederholt sich.
+pub struct Route {
+    method: &'static str,
+    path: String,
+    query: Vec<(String, String)>,
+    responses: Vec<Response>,
+    hits: usize,
+}
+
+impl Route {
+    fn new(method: &'static str, path: &str) -> Self {
+        Self {
+            method,
+            path: path.to_string(),
+            query: Vec::new(),
+            responses: Vec::new(),
+            hits: 0,
+        }
+    }
+
+    pub fn get(path: &str) -> Self {
+        Self::new("GET", path)
+    }
+
+    pub fn post(path: &str) -> Self {
+        Self::new("POST", path)
+    }
+
+    pub fn query(mut self, key: &str, value: &str) -> Self {
+        self.query.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn reply(mut self, response: Response) -> Self {
+        self.responses.push(response);
+        self
+    }
+
+    fn matches(&self, request: &Request) -> bool {
+        request.method == self.method
+            && request.path == self.path
+            && self
+                .query
+                .iter()
+                .all(|(k, v)| request.param(k) == Some(v.as_str()))
+    }
+
+    fn next_response(&mut self) -> Response {
+        let idx = self.hits.min(self.responses.len().saturating_sub(1));
+        self.hits += 1;
+        self.responses
+            .get(idx)
+            .cloned()
+            .unwrap_or_else(|| Response::status(204))
+    }
+}
+
+/// Eine vom Ersatz-Server empfangene Anfrage; Query und Formular dekodiert.
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub query: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn param(&self, key: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Feld aus einem `application/x-www-form-urlencoded`-Body.
+    pub fn form(&self, key: &str) -> Option<String> {
+        parse_pairs(&self.body)
+            .into_iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                    Some(b) => {
+                        out.push(b);
+                        i += 2;
+                    }
+                    None => out.push(b'%'),
+                }
+            }
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn parse_pairs(s: &str) -> Vec<(String, String)> {
+    s.split('&')
+        .filter(|p| !p.is_empty())
+        .map(|p| {
+            let (k, v) = p.split_once('=').unwrap_or((p, ""));
+            (percent_decode(k), percent_decode(v))
+        })
+        .collect()
+}
+
+/// HTTP/1.1-Server auf einem freien lokalen Port. Pfade ohne Route ergeben 404.
+pub struct FakeServer {
+    addr: SocketAddr,
+    requests: Arc<Mutex<Vec<Request>>>,
+    task: JoinHandle<()>,
+}
+
+impl FakeServer {
+    pub async fn start(routes: Vec<Route>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("Ersatz-Server: kein freier Port");
+        let addr = listener.local_addr().expect("Ersatz-Server: keine Adresse");
+        let routes = Arc::new(Mutex::new(routes));
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let task = tokio::spawn({
+            let requests = requests.clone();
+            async move {
+                while let Ok((stream, _)) = listener.accept().await {
+                    tokio::spawn(handle(stream, routes.clone(), requests.clone()));
+                }
+            }
+        });
+
+        Self {
+            addr,
+            requests,
+            task,
+        }
+    }
+
+    /// Basis-URL ohne abschließenden Schrägstrich, z. B. `http://127.0.0.1:41234`.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Alle bisherigen Anfragen an `path`, in Eingangsreihenfolge.
+    pub fn requests_to(&self, path: &str) -> Vec<Request> {
+        self.requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|r| r.path == path)
+            .cloned()
+            .collect()
+    }
+}
+
+impl Drop for FakeServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn handle(
+    mut stream: TcpStream,
+    routes: Arc<Mutex<Vec<Route>>>,
+    requests: Arc<Mutex<Vec<Request>>>,
+) {
+    let request = match read_request(&mut stream).await {
+        Some(r) => r,
+        None => return,
+    };
+    let response = routes
+        .lock()
+        .unwrap()
+        .iter_mut()
+        .find(|r| r.matches(&request))
+        .map(|r| r.next_response())
+        .unwrap_or_else(|| Response {
+            status: 404,
+            body: NOT_FOUND_BODY.to_string(),
+        });
+    requests.lock().unwrap().push(request);
+
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        reason(response.status),
+        response.body.len()
+    );
+    let _ = stream.write_all(head.as_bytes()).await;
+    let _ = stream.write_all(response.body.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        403 => "Forbidden",
+        404 => "Not Found",
+        429 => "Too Many Requests",
+        _ => "Status",
+    }
+}
+
+async fn read_request(stream: &mut TcpStream) -> Option<Request> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let head_end = loop {
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        let n = stream.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            return None;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+
+    let head = String::from_utf8_lossy(&buf[..head_end]).into_owned();
+    let mut lines = head.lines();
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let target = request_line.next()?.to_string();
+    let content_length = lines
+        .filter_map(|l| l.split_once(':'))
+        .find(|(k, _)| k.trim().eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, v)| v.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+
+    while buf.len() < head_end + content_length {
+        let n = stream.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+
+    let (path, query) = target.split_once('?').unwrap_or((target.as_str(), ""));
+    Some(Request {
+        method,
+        path: path.to_string(),
+        query: parse_pairs(query),
+        body: String::from_utf8_lossy(&buf[head_end..]).into_owned(),
+    })
+}
+
+static LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Lenkt die Dienst-URLs der App für die Dauer eines Tests um; beim Drop
+/// gelten wieder die echten Adressen.
+pub struct Harness {
+    _guard: tokio::sync::MutexGuard<'static, ()>,
+}
+
+impl Harness {
+    pub async fn install(endpoints: Endpoints) -> Self {
+        let guard = LOCK.lock().await;
+        endpoints::set(endpoints);
+        Self { _guard: guard }
+    }
+}
+
+impl Drop for Harness {
+    fn drop(&mut self) {
+        endpoints::reset();
+    }
+}
@@ -0,0 +1,444 @@
+use std::collections::HashMap;
+
+use app_lib::harness::discovery::{self, DiscoveryProvider};
+use app_lib::harness::endpoints::Endpoints;
+use app_lib::harness::tmdb_http::CacheMode;
+use serde_json::json;
+use test_harness::{FakeServer, Harness, Response, Route};
+
+fn providers() -> Vec<DiscoveryProvider> {
+    [(8, "Netflix"), (9, "Prime Video"), (337, "Disney+"), (350, "Apple TV+")]
+        .into_iter()
+        .map(|(id, name)| DiscoveryProvider {
+            tmdb_provider_id: id,
+            name: name.to_string(),
+            logo_url: Some(format!("https://image.tmdb.org/t/p/w92/{}.jpg", id)),
+        })
+        .collect()
+}
+
+async fn setup(routes: Vec<Route>) -> (FakeServer, Harness) {
+    let server = FakeServer::start(routes).await;
+    let harness = Harness::install(Endpoints {
+        tmdb: Some(format!("{}/3", server.url())),
+        ..Default::default()
+    })
+    .await;
+    (server, harness)
+}
+
+/// Leere Antworten für Trends und Kino-Listen; die TV-Listen laufen über `/discover/tv`.
+fn chart_routes() -> Vec<Route> {
+    [
+        "/3/trending/all/day",
+        "/3/trending/all/week",
+        "/3/movie/now_playing",
+        "/3/movie/upcoming",
+    ]
+    .into_iter()
+    .map(|path| Route::get(path).reply(Response::json(json!({ "results": [] }))))
+    .collect()
+}
+
+const CHART_KEYS: [&str; 7] = [
+    "trending_day",
+    "trending_week",
+    "now_playing",
+    "upcoming",
+    "airing_week",
+    "imdb_top",
+    "top_rated_tv",
+];
+
+#[tokio::test]
+async fn fetch_all_lists_pages_through_every_list() {
+    let mut routes = chart_routes();
+    routes.extend([
+        Route::get("/3/movie/top_rated").reply(Response::fixture("tmdb/movie_top_rated.json")),
+        Route::get("/3/discover/movie")
+            .query("with_watch_providers", "8")
+            .reply(Response::fixture("tmdb/discover_movie_netflix.json")),
+        Route::get("/3/discover/movie").reply(Response::fixture("tmdb/discover_movie.json")),
+        Route::get("/3/discover/tv").reply(Response::fixture("tmdb/discover_tv.json")),
+    ]);
+    let (server, _harness) = setup(routes).await;
+
+    let providers = providers();
+    let lists = discovery::fetch_all_lists("test-key", "de-DE", "DE", &providers, &[], CacheMode::Use).await;
+
+    // Feste Listen plus Filme und Serien je Streaming-Anbieter.
+    assert_eq!(lists.len(), CHART_KEYS.len() + 2 * providers.len());
+    assert!(lists.iter().all(|l| l.error.is_none()));
+    let keys: Vec<&str> = lists.iter().take(CHART_KEYS.len()).map(|l| l.key.as_str()).collect();
+    assert_eq!(keys, CHART_KEYS);
+
+    let top = &lists[5];
+    assert_eq!(top.key, "imdb_top");
+    assert_eq!(top.list_type, "imdb_top");
+    assert_eq!(top.country, "DE");
+    // Drei Seiten à zwei gültige Filme; Einträge ohne Titel fallen weg.
+    assert_eq!(top.items.len(), 6);
+    assert_eq!(top.items[0].tmdb_id, 278);
+    assert_eq!(top.items[0].title, "Die Verurteilten");
+    assert_eq!(top.items[0].year, Some(1994));
+    assert_eq!(
+        top.items[0].poster_url.as_deref(),
+        Some("https://image.tmdb.org/t/p/w342/lQZP8rAkqrBmGbxNETPM3JIcC4e.jpg")
+    );
+
+    let netflix_movies = &lists[7];
+    assert_eq!(netflix_movies.provider.as_deref(), Some("8"));
+    assert_eq!(
+        netflix_movies.provider_logo.as_deref(),
+        Some("https://image.tmdb.org/t/p/w92/8.jpg")
+    );
+    assert_eq!(netflix_movies.items.len(), 2);
+    assert_eq!(netflix_movies.items[0].title, "Carry-On");
+
+    let netflix_series = &lists[8];
+    assert_eq!(netflix_series.key, "tv:8");
+    assert_eq!(netflix_series.title, "Top Serien auf Netflix");
+    assert_eq!(netflix_series.items.len(), 2);
+    assert_eq!(netflix_series.items[0].media_type, "tv");
+    assert_eq!(netflix_series.items[0].year, Some(2016));
+
+    let prime_movies = &lists[9];
+    assert_eq!(prime_movies.provider.as_deref(), Some("9"));
+    assert_eq!(prime_movies.items[0].tmdb_id, 27205);
+
+    let top_pages: Vec<String> = server
+        .requests_to("/3/movie/top_rated")
+        .iter()
+        .filter_map(|r| r.param("page").map(String::from))
+        .collect();
+    assert_eq!(top_pages, vec!["1", "2", "3"]);
+    let discover = server.requests_to("/3/discover/movie");
+    assert_eq!(discover.len(), 2 * providers.len());
+    assert!(discover.iter().all(|r| r.param("watch_region") == Some("DE")));
+}
+
+#[tokio::test]
+async fn failing_list_keeps_the_others() {
+    let mut routes = chart_routes();
+    routes.extend([
+        Route::get("/3/movie/top_rated").reply(Response::fixture("tmdb/movie_top_rated.json")),
+        Route::get("/3/discover/movie")
+            .query("with_watch_providers", "8")
+            .reply(Response::status(500)),
+        Route::get("/3/discover/movie").reply(Response::fixture("tmdb/discover_movie.json")),
+    ]);
+    let (_server, _harness) = setup(routes).await;
+
+    let lists = discovery::fetch_all_lists("test-key", "de-DE", "DE", &providers()[..2], &[], CacheMode::Use).await;
+
+    let keys: Vec<&str> = lists.iter().skip(CHART_KEYS.len()).map(|l| l.key.as_str()).collect();
+    assert_eq!(keys, vec!["movie:8", "tv:8", "movie:9", "tv:9"]);
+    assert!(lists[5].error.is_none());
+    assert_eq!(lists[5].items.len(), 6);
+
+    let netflix_movies = &lists[7];
+    assert!(netflix_movies.items.is_empty());
+    assert!(netflix_movies.error.as_deref().is_some_and(|e| e.contains("500")));
+    assert_eq!(netflix_movies.title, "Top Filme auf Netflix");
+    assert_eq!(netflix_movies.provider.as_deref(), Some("8"));
+
+    // Serien-Endpunkt fehlt ganz.
+    assert!(lists[8].error.as_deref().is_some_and(|e| e.contains("404")));
+    assert!(lists[9].error.is_none());
+    assert!(!lists[9].items.is_empty());
+}
+
+#[tokio::test]
+async fn trending_mixes_movies_and_series_and_skips_people() {
+    let (server, _harness) = setup(vec![
+        Route::get("/3/trending/all/week").reply(Response::json(json!({ "results": [
+            { "id": 27205, "media_type": "movie", "title": "Inception", "release_date": "2010-07-15" },
+            { "id": 6193, "media_type": "person", "name": "Leonardo DiCaprio" },
+            { "id": 1399, "media_type": "tv", "name": "Game of Thrones", "first_air_date": "2011-04-17" }
+        ]}))),
+        Route::get("/3/movie/now_playing").reply(Response::json(json!({ "results": [
+            { "id": 1, "title": "Neu" }
+        ]}))),
+        Route::get("/3/movie/27205/watch/providers").reply(Response::json(json!({ "results": {
+            "CH": { "flatrate": [{ "provider_id": 8 }] }
+        }}))),
+        Route::get("/3/tv/1399/watch/providers").reply(Response::json(json!({ "results": {
+            "US": { "flatrate": [{ "provider_id": 1899 }] }
+        }}))),
+    ])
+    .await;
+
+    let lists = discovery::fetch_all_lists("test-key", "de-CH", "CH", &[], &[], CacheMode::Use).await;
+
+    assert_eq!(lists.len(), CHART_KEYS.len());
+    let week = &lists[1];
+    assert_eq!(week.list_type, "trending_week");
+    assert_eq!(week.title, "Diese Woche im Trend");
+    let items: Vec<(i64, &str)> = week.items.iter().map(|i| (i.tmdb_id, i.media_type.as_str())).collect();
+    // Zwei Seiten mit derselben Antwort; die Serie gibt es in der Schweiz nicht.
+    assert_eq!(items, vec![(27205, "movie"), (27205, "movie")]);
+    assert_eq!(server.requests_to("/3/movie/27205/watch/providers").len(), 1);
+
+    assert_eq!(lists[2].items[0].title, "Neu");
+    let request = &server.requests_to("/3/movie/now_playing")[0];
+    assert_eq!(request.param("region"), Some("CH"));
+    assert_eq!(request.param("language"), Some("de-CH"));
+    assert!(lists[0].error.is_some());
+}
+
+#[tokio::test]
+async fn series_lists_use_the_region() {
+    let (server, _harness) = setup(vec![
+        Route::get("/3/discover/tv").reply(Response::fixture("tmdb/discover_tv.json")),
+    ])
+    .await;
+
+    let lists = discovery::fetch_all_lists("test-key", "de-AT", "AT", &[], &[], CacheMode::Use).await;
+
+    assert_eq!(lists[4].key, "airing_week");
+    assert_eq!(lists[6].key, "top_rated_tv");
+    assert!(!lists[4].items.is_empty());
+    assert!(lists[6].items.iter().all(|i| i.media_type == "tv"));
+
+    let requests = server.requests_to("/3/discover/tv");
+    assert_eq!(requests.len(), 4);
+    assert!(requests.iter().all(|r| r.param("watch_region") == Some("AT")));
+    assert!(requests.iter().all(|r| r.param("with_watch_monetization_types").is_some()));
+    let airing = requests.iter().find(|r| r.param("air_date.gte").is_some()).expect("airing");
+    assert!(airing.param("air_date.lte").is_some());
+    let top = requests.iter().find(|r| r.param("sort_by") == Some("vote_average.desc")).expect("top rated");
+    assert!(top.param("vote_count.gte").is_some());
+}
+
+#[tokio::test]
+async fn watch_providers_merge_movie_and_tv_by_region_priority() {
+    let (server, _harness) = setup(vec![
+        Route::get("/3/watch/providers/movie").reply(Response::json(json!({ "results": [
+            { "provider_id": 8, "provider_name": "Netflix", "logo_path": "/netflix.jpg",
+              "display_priority": 1, "display_priorities": { "CH": 2 } },
+            { "provider_id": 1899, "provider_name": "Play Suisse", "logo_path": "/playsuisse.jpg",
+              "display_priorities": { "CH": 5 } }
+        ]}))),
+        Route::get("/3/watch/providers/tv").reply(Response::json(json!({ "results": [
+            { "provider_id": 8, "provider_name": "Netflix", "logo_path": "/netflix.jpg",
+              "display_priorities": { "CH": 2 } },
+            { "provider_id": 29, "provider_name": "Sky Show", "logo_path": "/sky.jpg",
+              "display_priorities": { "CH": 1 } }
+        ]}))),
+    ])
+    .await;
+
+    let providers = discovery::fetch_watch_providers("test-key", "de-CH", "CH")
+        .await
+        .expect("providers");
+
+    let names: Vec<&str> = providers.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, vec!["Sky Show", "Netflix", "Play Suisse"]);
+    assert_eq!(
+        providers[0].logo_url.as_deref(),
+        Some("https://image.tmdb.org/t/p/w92/sky.jpg")
+    );
+    let request = &server.requests_to("/3/watch/providers/tv")[0];
+    assert_eq!(request.param("watch_region"), Some("CH"));
+
+    assert_eq!(discovery::selected_provider_ids(None), discovery::DEFAULT_PROVIDER_IDS);
+    assert_eq!(discovery::selected_provider_ids(Some("29, 1899,x")), vec![29, 1899]);
+    assert!(discovery::selected_provider_ids(Some("")).is_empty());
+
+    let logos = HashMap::from([(8, "https://image.tmdb.org/t/p/w92/netflix.jpg".to_string())]);
+    let fallback = discovery::fallback_providers(&[8, 29], &logos);
+    assert_eq!(fallback[0].name, "Netflix");
+    assert_eq!(fallback[0].logo_url.as_deref(), Some("https://image.tmdb.org/t/p/w92/netflix.jpg"));
+    assert_eq!(fallback[1].name, "Anbieter 29");
+    assert_eq!(fallback[1].logo_url, None);
+}
+
+#[tokio::test]
+async fn discover_query_applies_filters_and_saved_queries_become_lists() {
+    let (server, _harness) = setup(vec![Route::get("/3/discover/tv").reply(Response::json(json!({
+        "page": 3, "total_pages": 812, "total_results": 16240,
+        "results": [
+            { "id": 1399, "name": "Game of Thrones", "first_air_date": "2011-04-17", "genre_ids": [18, 10765] },
+            { "id": 2, "name": "" }
+        ]
+    })))])
+    .await;
+
+    let query = discovery::DiscoverQuery {
+        media_type: "series".into(),
+        genre_ids: vec![18, 10765],
+        year_from: Some(2010),
+        year_to: Some(2019),
+        min_rating: Some(7.5),
+        runtime_max: Some(70),
+        provider_ids: vec![8, 9],
+        monetization_types: vec!["flatrate".into()],
+        sort_by: Some("vote_average.desc".into()),
+        ..Default::default()
+    };
+    let page = discovery::discover_query("test-key", "de-CH", "CH", &query, 3)
+        .await
+        .expect("page");
+
+    assert_eq!((page.page, page.total_pages, page.total_results), (3, 500, 16240));
+    assert_eq!(page.results.len(), 1);
+    assert_eq!(page.results[0].media_type, "tv");
+    assert_eq!(page.results[0].genre_ids, vec![18, 10765]);
+
+    let request = &server.requests_to("/3/discover/tv")[0];
+    assert_eq!(request.param("with_genres"), Some("18|10765"));
+    assert_eq!(request.param("first_air_date.gte"), Some("2010-01-01"));
+    assert_eq!(request.param("first_air_date.lte"), Some("2019-12-31"));
+    assert_eq!(request.param("vote_average.gte"), Some("7.5"));
+    assert_eq!(request.param("with_runtime.lte"), Some("70"));
+    assert_eq!(request.param("with_watch_providers"), Some("8|9"));
+    assert_eq!(request.param("with_watch_monetization_types"), Some("flatrate"));
+    assert_eq!(request.param("watch_region"), Some("CH"));
+    assert_eq!(request.param("sort_by"), Some("vote_average.desc"));
+    assert_eq!(request.param("page"), Some("3"));
+
+    let invalid = discovery::DiscoverQuery { sort_by: Some("x&api_key=y".into()), ..Default::default() };
+    assert!(discovery::discover_query("test-key", "de-CH", "CH", &invalid, 1).await.is_err());
+
+    let saved = discovery::SavedDiscoverQuery {
+        id: "q1".into(),
+        name: "Fantasy-Serien".into(),
+        query: discovery::DiscoverQuery { match_all_genres: true, ..query },
+        created_at: String::new(),
+    };
+    let lists = discovery::fetch_all_lists("test-key", "de-CH", "CH", &[], &[saved], CacheMode::Use).await;
+    let custom = lists.last().expect("custom list");
+    assert_eq!(custom.key, "custom:q1");
+    assert_eq!(custom.list_type, "custom");
+    assert_eq!(custom.title, "Fantasy-Serien");
+    assert_eq!(custom.items.len(), 2);
+    // Die TV-Listen fragen `/discover/tv` ohne Genres.
+    let requests: Vec<_> = server
+        .requests_to("/3/discover/tv")
+        .into_iter()
+        .filter(|r| r.param("with_genres").is_some())
+        .collect();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[2].param("with_genres"), Some("18,10765"));
+}
+
+#[tokio::test]
+async fn search_merges_types_with_counts_and_parses_year() {
+    let (server, _harness) = setup(vec![
+        Route::get("/3/search/movie").reply(Response::json(json!({
+            "page": 1, "total_pages": 2, "total_results": 23,
+            "results": [
+                { "id": 841, "title": "Der Wüstenplanet", "release_date": "1984-12-14", "popularity": 40.0, "vote_average": 6.3 },
+                { "id": 841, "title": "Der Wüstenplanet", "release_date": "1984-12-14", "popularity": 40.0 }
+            ]
+        }))),
+        Route::get("/3/search/tv").reply(Response::json(json!({
+            "page": 1, "total_pages": 1, "total_results": 1,
+            "results": [{ "id": 90228, "name": "Dune: Prophecy", "first_air_date": "2024-11-17", "popularity": 10.0 }]
+        }))),
+        Route::get("/3/search/person").reply(Response::json(json!({
+            "page": 1, "total_pages": 1, "total_results": 1,
+            "results": [{ "id": 1, "name": "Dune Person", "profile_path": "/p.jpg", "popularity": 99.0,
+                          "known_for_department": "Acting", "known_for": [{ "title": "Dune" }, { "name": "Dune: Prophecy" }] }]
+        }))),
+        Route::get("/3/search/collection").reply(Response::json(json!({
+            "page": 1, "total_pages": 1, "total_results": 1,
+            "results": [{ "id": 726871, "name": "Dune Filmreihe", "poster_path": "/c.jpg" }]
+        }))),
+    ])
+    .await;
+
+    let page = discovery::search_tmdb("test-key", "de-DE", "Düne (1984)", &[], 1)
+        .await
+        .expect("search");
+
+    assert_eq!((page.query.as_str(), page.year), ("Düne", Some(1984)));
+    assert_eq!((page.total_pages, page.total_results), (2, 26));
+    assert_eq!((page.counts.movie, page.counts.tv, page.counts.person, page.counts.collection), (23, 1, 1, 1));
+    let found: Vec<(&str, i64)> = page.results.iter().map(|r| (r.media_type.as_str(), r.tmdb_id)).collect();
+    assert_eq!(found, vec![("person", 1), ("movie", 841), ("tv", 90228), ("collection", 726871)]);
+    assert_eq!(page.results[0].known_for, vec!["Dune", "Dune: Prophecy"]);
+    assert_eq!(page.results[0].image_url.as_deref(), Some("https://image.tmdb.org/t/p/w342/p.jpg"));
+
+    let movie_request = &server.requests_to("/3/search/movie")[0];
+    assert_eq!(movie_request.param("query"), Some("Düne"));
+    assert_eq!(movie_request.param("primary_release_year"), Some("1984"));
+    assert_eq!(server.requests_to("/3/search/tv")[0].param("first_air_date_year"), Some("1984"));
+    assert_eq!(server.requests_to("/3/search/person")[0].param("primary_release_year"), None);
+
+    let people = discovery::search_tmdb("test-key", "de-DE", "1984", &["person".to_string()], 2)
+        .await
+        .expect("people");
+    assert_eq!((people.query.as_str(), people.year), ("1984", None));
+    assert_eq!(people.counts.movie, 0);
+    assert!(people.results.iter().all(|r| r.media_type == "person"));
+    assert_eq!(server.requests_to("/3/search/person")[1].param("page"), Some("2"));
+    assert_eq!(server.requests_to("/3/search/movie").len(), 1);
+
+    assert!(discovery::search_tmdb("test-key", "de-DE", "Dune", &["album".to_string()], 1).await.is_err());
+}
+
+#[tokio::test]
+async fn search_keeps_title_years_and_ranks_collections_per_type() {
+    let (server, _harness) = setup(vec![
+        Route::get("/3/search/movie")
+            .query("primary_release_year", "2049")
+            .reply(Response::json(json!({ "page": 1, "total_pages": 0, "total_results": 0, "results": [] }))),
+        Route::get("/3/search/movie").reply(Response::json(json!({
+            "page": 1, "total_pages": 1, "total_results": 2,
+            "results": [
+                { "id": 335984, "title": "Blade Runner 2049", "release_date": "2017-10-04", "popularity": 50.0 },
+                { "id": 78, "title": "Blade Runner", "release_date": "1982-06-25", "popularity": 30.0 }
+            ]
+        }))),
+        Route::get("/3/search/collection").reply(Response::json(json!({
+            "page": 1, "total_pages": 1, "total_results": 1,
+            "results": [{ "id": 422837, "name": "Blade Runner Filmreihe" }]
+        }))),
+    ])
+    .await;
+
+    let types = ["movie".to_string(), "collection".to_string()];
+    let page = discovery::search_tmdb("test-key", "de-DE", "Blade Runner 2049", &types, 1)
+        .await
+        .expect("search");
+
+    assert_eq!((page.query.as_str(), page.year), ("Blade Runner 2049", None));
+    let found: Vec<i64> = page.results.iter().map(|r| r.tmdb_id).collect();
+    assert_eq!(found, vec![335984, 422837, 78]);
+    let requests = server.requests_to("/3/search/movie");
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].param("query"), Some("Blade Runner"));
+    assert_eq!(requests[1].param("query"), Some("Blade Runner 2049"));
+    assert_eq!(requests[1].param("primary_release_year"), None);
+}
+
+#[tokio::test]
+async fn search_returns_working_types_and_retries_only_for_titles() {
+    let (server, _harness) = setup(vec![Route::get("/3/search/person").reply(Response::json(json!({
+        "page": 1, "total_pages": 1, "total_results": 1,
+        "results": [{ "id": 7, "name": "Prince", "popularity": 20.0 }]
+    })))])
+    .await;
+
+    // Nur Personen: kein zweiter Durchlauf ohne Jahr.
+    let people = discovery::search_tmdb("test-key", "de-DE", "Prince 1999", &["person".to_string()], 1)
+        .await
+        .expect("people");
+    assert_eq!(people.year, Some(1999));
+    assert_eq!(people.results.len(), 1);
+    assert_eq!(server.requests_to("/3/search/person").len(), 1);
+
+    // Filme scheitern: die Personen bleiben, die Art wird gemeldet, kein zweiter Durchlauf.
+    let types = ["movie".to_string(), "person".to_string()];
+    let mixed = discovery::search_tmdb("test-key", "de-DE", "Prince 1999", &types, 1)
+        .await
+        .expect("mixed");
+    assert_eq!(mixed.failed_types, vec!["movie"]);
+    assert_eq!(mixed.results.iter().map(|r| r.tmdb_id).collect::<Vec<_>>(), vec![7]);
+    assert_eq!(server.requests_to("/3/search/movie").len(), 1);
+    assert_eq!(server.requests_to("/3/search/person").len(), 2);
+
+    // Scheitern alle Arten, scheitert die Suche.
+    assert!(discovery::search_tmdb("test-key", "de-DE", "Prince", &["collection".to_string()], 1).await.is_err());
+}
@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use app_lib::harness::endpoints::Endpoints;
+use app_lib::harness::language::LanguagePrefs;
+use app_lib::harness::metadata_merge::MergePolicy;
+use app_lib::harness::metadata_service::MetadataService;
+use app_lib::harness::omdb::OmdbProvider;
+use app_lib::harness::tmdb::TmdbProvider;
+use test_harness::{FakeServer, Harness, Response, Route};
+
+#[tokio::test]
+async fn merged_details_keep_omdb_ratings_when_tmdb_is_complete() {
+    let server = FakeServer::start(vec![
+        Route::get("/3/movie/27205").reply(Response::fixture("tmdb/movie_27205.json")),
+        Route::get("/3/movie/27205/credits").reply(Response::fixture("tmdb/movie_27205_credits.json")),
+        Route::get("/omdb/").query("i", "tt1375666").reply(Response::fixture("omdb/tt1375666.json")),
+    ])
+    .await;
+    let _harness = Harness::install(Endpoints {
+        tmdb: Some(format!("{}/3", server.url())),
+        omdb: Some(format!("{}/omdb", server.url())),
+        ..Default::default()
+    })
+    .await;
+
+    let service = MetadataService::new();
+    service
+        .register_provider(Arc::new(TmdbProvider::new("tmdb-key".into(), LanguagePrefs::default())))
+        .await;
+    service.register_provider(Arc::new(OmdbProvider::new("omdb-key".into()))).await;
+
+    let merged = service
+        .fetch_details_merged("27205", "movie", "tmdb", &MergePolicy::default())
+        .await
+        .expect("details")
+        .expect("found");
+
+    // TMDb liefert alle Felder ...
+    assert_eq!(merged.provider, "tmdb");
+    assert!(merged.field_sources.values().all(|p| p == "tmdb"));
+    // ... OMDb wird trotzdem nach seinen Bewertungen gefragt.
+    assert_eq!(server.requests_to("/omdb/").len(), 1);
+    let sources: Vec<&str> = merged.ratings.iter().map(|r| r.source.as_str()).collect();
+    for source in ["imdb", "rotten_tomatoes", "metacritic"] {
+        assert!(sources.contains(&source), "{source} fehlt in {sources:?}");
+    }
+}
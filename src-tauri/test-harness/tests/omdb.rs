@@ -0,0 +1,89 @@
+use app_lib::harness::endpoints::Endpoints;
+use app_lib::harness::metadata_provider::{MetadataProvider, SearchQuery};
+use app_lib::harness::omdb::OmdbProvider;
+use serde_json::json;
+use test_harness::{FakeServer, Harness, Response, Route};
+
+const API_KEY: &str = "test-key";
+
+async fn setup(routes: Vec<Route>) -> (FakeServer, Harness, OmdbProvider) {
+    let server = FakeServer::start(routes).await;
+    let harness = Harness::install(Endpoints {
+        omdb: Some(server.url()),
+        ..Default::default()
+    })
+    .await;
+    (server, harness, OmdbProvider::new(API_KEY.to_string()))
+}
+
+#[tokio::test]
+async fn search_maps_results_and_sends_query() {
+    let (server, _harness, provider) = setup(vec![
+        Route::get("/").reply(Response::fixture("omdb/search_inception.json")),
+    ])
+    .await;
+
+    let results = provider
+        .search(&SearchQuery {
+            title: "Inception".into(),
+            year: Some(2010),
+            media_type: "movie".into(),
+        })
+        .await
+        .expect("search");
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].provider, "omdb");
+    assert_eq!(results[0].provider_id, "tt1375666");
+    assert_eq!(results[0].media_type, "movie");
+    assert_eq!(results[0].external_ids.imdb_id.as_deref(), Some("tt1375666"));
+    assert_eq!(results[0].release_date.as_deref(), Some("2010"));
+    // "N/A" gilt als fehlend.
+    assert!(results[1].poster_url.is_none());
+
+    let request = &server.requests_to("/")[0];
+    assert_eq!(request.param("apikey"), Some(API_KEY));
+    assert_eq!(request.param("s"), Some("Inception"));
+    assert_eq!(request.param("type"), Some("movie"));
+    assert_eq!(request.param("y"), Some("2010"));
+}
+
+#[tokio::test]
+async fn details_normalize_ratings_and_credits() {
+    let (server, _harness, provider) = setup(vec![
+        Route::get("/").query("i", "tt1375666").reply(Response::fixture("omdb/tt1375666.json")),
+        Route::get("/").reply(Response::json(json!({ "Response": "False", "Error": "Incorrect IMDb ID." }))),
+    ])
+    .await;
+
+    let detail = provider
+        .fetch_details("tt1375666", "movie")
+        .await
+        .expect("details")
+        .expect("found");
+
+    assert_eq!(detail.title, "Inception");
+    assert_eq!(detail.release_date.as_deref(), Some("2010-07-16"));
+    assert_eq!(detail.runtime, Some(148));
+    assert_eq!(detail.genres, vec!["Action", "Adventure", "Sci-Fi"]);
+    let ratings: Vec<(&str, f64, &str)> = detail
+        .ratings
+        .iter()
+        .map(|r| (r.source.as_str(), r.value, r.display.as_str()))
+        .collect();
+    assert_eq!(
+        ratings,
+        vec![("imdb", 8.8, "8.8/10"), ("rotten_tomatoes", 8.7, "87%"), ("metacritic", 7.4, "74/100")]
+    );
+    assert_eq!(detail.ratings[0].votes, Some(2_600_000));
+    let cast_crew = detail.cast_crew.expect("credits");
+    assert_eq!(cast_crew.cast.len(), 3);
+    let crew: Vec<(&str, &str)> = cast_crew.crew.iter().map(|c| (c.name.as_str(), c.job.as_str())).collect();
+    assert_eq!(
+        crew,
+        vec![("Christopher Nolan", "Director"), ("Christopher Nolan", "Writer"), ("Jonathan Nolan", "Writer")]
+    );
+    assert_eq!(server.requests_to("/")[0].param("plot"), Some("full"));
+
+    assert!(provider.fetch_details("tt0000000", "movie").await.expect("lookup").is_none());
+}
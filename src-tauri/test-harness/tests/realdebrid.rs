@@ -0,0 +1,149 @@
+use app_lib::harness::endpoints::Endpoints;
+use app_lib::harness::{rd_streams, realdebrid};
+use serde_json::json;
+use test_harness::{FakeServer, Harness, Response, Route};
+
+const TOKEN: &str = "GJ4PQ7XDNTFYKQ6HDSMZXU5ZKYO3CFHPUJQJZWQZC5A2ZD3F7XAQ";
+const HASH: &str = "a1b2c3d4e5f60718293a4b5c6d7e8f9012345678";
+
+async fn install(server: &FakeServer) -> Harness {
+    Harness::install(Endpoints {
+        real_debrid: Some(server.url()),
+        torrentio: Some(server.url()),
+        ..Default::default()
+    })
+    .await
+}
+
+#[tokio::test]
+async fn device_auth_flow() {
+    let server = FakeServer::start(vec![
+        Route::get("/oauth/v2/device/code").reply(Response::fixture("realdebrid/device_code.json")),
+        // Erst nach der Freigabe durch den Nutzer gibt es Zugangsdaten.
+        Route::get("/oauth/v2/device/credentials")
+            .reply(Response::status(403))
+            .reply(Response::fixture("realdebrid/device_credentials.json")),
+        Route::post("/oauth/v2/token").reply(Response::fixture("realdebrid/token.json")),
+        Route::get("/rest/1.0/user").reply(Response::fixture("realdebrid/user.json")),
+    ])
+    .await;
+    let _harness = install(&server).await;
+
+    let code = realdebrid::get_device_code().await.expect("device code");
+    assert_eq!(code.user_code, "7MKZLXPB");
+    assert_eq!(code.interval, 5);
+
+    let pending = realdebrid::poll_credentials(&code.device_code).await.expect("poll");
+    assert!(pending.is_none());
+    let creds = realdebrid::poll_credentials(&code.device_code)
+        .await
+        .expect("poll")
+        .expect("credentials");
+    assert_eq!(creds.client_id, "NCQ2EBEN7FDHG");
+
+    let token = realdebrid::get_token(&creds.client_id, &creds.client_secret, &code.device_code)
+        .await
+        .expect("token");
+    assert_eq!(token.access_token, TOKEN);
+
+    let user = realdebrid::get_user(&token.access_token).await.expect("user");
+    assert_eq!(user.username, "cobb");
+
+    let polls = server.requests_to("/oauth/v2/device/credentials");
+    assert_eq!(polls.len(), 2);
+    assert_eq!(polls[0].param("code"), Some(code.device_code.as_str()));
+    let token_request = &server.requests_to("/oauth/v2/token")[0];
+    assert_eq!(token_request.form("client_id").as_deref(), Some("NCQ2EBEN7FDHG"));
+    assert_eq!(token_request.form("code"), Some(code.device_code.clone()));
+    assert_eq!(
+        token_request.form("grant_type").as_deref(),
+        Some("http://oauth.net/grant_type/device/1.0")
+    );
+}
+
+#[tokio::test]
+async fn user_request_fails_on_rejected_token() {
+    let server = FakeServer::start(vec![Route::get("/rest/1.0/user").reply(Response::status(401))]).await;
+    let _harness = install(&server).await;
+
+    let err = realdebrid::get_user("expired").await.expect_err("401");
+    assert!(err.contains("401"), "{}", err);
+}
+
+#[tokio::test]
+async fn torrentio_stream_resolves_through_real_debrid() {
+    let server = FakeServer::start(vec![
+        Route::get("/stream/movie/tt1375666.json")
+            .reply(Response::fixture("torrentio/stream_movie_tt1375666.json")),
+        Route::post("/rest/1.0/torrents/addMagnet").reply(Response::fixture("realdebrid/add_magnet.json")),
+        Route::get("/rest/1.0/torrents/info/ZXCVBNM4QWERT")
+            .reply(Response::fixture("realdebrid/torrent_info_waiting.json"))
+            .reply(Response::fixture("realdebrid/torrent_info_downloaded.json")),
+        Route::post("/rest/1.0/torrents/selectFiles/ZXCVBNM4QWERT").reply(Response::status(204)),
+        Route::post("/rest/1.0/unrestrict/link").reply(Response::fixture("realdebrid/unrestrict_link.json")),
+    ])
+    .await;
+    let _harness = install(&server).await;
+
+    let streams = rd_streams::fetch_torrentio_streams("tt1375666", "movie", None, None)
+        .await
+        .expect("torrentio");
+    assert_eq!(streams.len(), 2);
+    let (hash, file_idx, quality, _size) = &streams[0];
+    assert_eq!(hash, HASH);
+    assert_eq!(*file_idx, 1);
+    assert_eq!(quality, "1080p");
+
+    let link = rd_streams::info_hash_to_stream(TOKEN, hash, *file_idx, quality, "10 GB")
+        .await
+        .expect("stream")
+        .expect("downloaded");
+    assert_eq!(link.title, "Inception.2010.1080p.BluRay.x264.mkv");
+    assert_eq!(link.quality, "1080p");
+    assert_eq!(link.size, "10 GB");
+    assert_eq!(
+        link.stream_url,
+        "https://download.real-debrid.com/d/QWERTYUIOP123/Inception.2010.1080p.BluRay.x264.mkv"
+    );
+
+    let add = &server.requests_to("/rest/1.0/torrents/addMagnet")[0];
+    assert_eq!(add.form("magnet"), Some(format!("magnet:?xt=urn:btih:{}", HASH)));
+    // Die Datei mit `fileIdx` 1 hat bei RealDebrid die ID 2.
+    let select = server.requests_to("/rest/1.0/torrents/selectFiles/ZXCVBNM4QWERT");
+    assert_eq!(select.len(), 1);
+    assert_eq!(select[0].form("files").as_deref(), Some("2"));
+    assert_eq!(server.requests_to("/rest/1.0/torrents/info/ZXCVBNM4QWERT").len(), 2);
+    let unrestrict = &server.requests_to("/rest/1.0/unrestrict/link")[0];
+    assert_eq!(
+        unrestrict.form("link").as_deref(),
+        Some("https://real-debrid.com/d/QWERTYUIOP123")
+    );
+}
+
+#[tokio::test]
+async fn yts_search_returns_magnets() {
+    let server = FakeServer::start(vec![Route::get("/api/v2/list_movies.json").reply(Response::json(json!({
+        "status": "ok",
+        "data": { "movie_count": 1, "movies": [{ "title": "Inception", "torrents": [
+            { "url": "magnet:?xt=urn:btih:abc", "quality": "1080p", "size": "1.85 GB" },
+            { "url": "https://yts.mx/torrent/download/abc", "quality": "720p", "size": "1 GB" }
+        ]}]}
+    })))])
+    .await;
+    let _harness = Harness::install(Endpoints {
+        yts: Some(format!("{}/api/v2", server.url())),
+        ..Default::default()
+    })
+    .await;
+
+    let magnets = rd_streams::search_movie_torrents("Inception: The IMAX Experience", Some(2010))
+        .await
+        .expect("yts");
+    assert_eq!(
+        magnets,
+        vec![("magnet:?xt=urn:btih:abc".to_string(), "1080p".to_string(), "1.85 GB".to_string())]
+    );
+    let requests = server.requests_to("/api/v2/list_movies.json");
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].param("query_term"), Some("Inception The IMAX Experience"));
+}
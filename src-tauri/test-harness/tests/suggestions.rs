@@ -0,0 +1,154 @@
+use std::collections::HashSet;
+
+use app_lib::harness::endpoints::Endpoints;
+use app_lib::harness::language::LanguagePrefs;
+use app_lib::harness::tmdb_api;
+use serde_json::json;
+use test_harness::{FakeServer, Harness, Response, Route};
+
+const DICAPRIO: i64 = 6193;
+const GORDON_LEVITT: i64 = 24045;
+const NOLAN: i64 = 525;
+
+fn credits(path: &str, cast: serde_json::Value, crew: serde_json::Value) -> Route {
+    Route::get(path).reply(Response::json(json!({ "cast": cast, "crew": crew })))
+}
+
+#[tokio::test]
+async fn ranks_by_shared_people_and_skips_source_and_library() {
+    let server = FakeServer::start(vec![
+        credits(
+            "/3/person/6193/combined_credits",
+            json!([
+                { "id": 27205, "media_type": "movie", "title": "Inception", "popularity": 90.0, "character": "Dom Cobb" },
+                { "id": 1422, "media_type": "movie", "title": "Departed", "release_date": "2006-10-05", "popularity": 50.0, "character": "Billy Costigan" },
+                { "id": 11324, "media_type": "movie", "title": "Shutter Island", "popularity": 70.0, "character": "Teddy Daniels" },
+                { "id": 4280, "media_type": "movie", "title": "Making of", "popularity": 95.0, "character": "Himself" },
+                { "id": 22980, "media_type": "tv", "name": "Late Show", "popularity": 99.0, "genre_ids": [10767], "character": "Guest" }
+            ]),
+            json!([]),
+        ),
+        credits(
+            "/3/person/24045/combined_credits",
+            json!([
+                { "id": 49026, "media_type": "movie", "title": "The Dark Knight Rises", "release_date": "2012-07-16", "popularity": 60.0, "character": "Blake" },
+                { "id": 1404, "media_type": "tv", "name": "Hinterm Mond gleich links", "first_air_date": "1996-01-09", "popularity": 10.0, "character": "Tommy" }
+            ]),
+            json!([]),
+        ),
+        credits(
+            "/3/person/525/combined_credits",
+            json!([]),
+            json!([
+                { "id": 49026, "media_type": "movie", "title": "The Dark Knight Rises", "popularity": 60.0, "job": "Director" },
+                { "id": 157336, "media_type": "movie", "title": "Interstellar", "popularity": 80.0, "job": "Director" },
+                { "id": 157336, "media_type": "movie", "title": "Interstellar", "popularity": 80.0, "job": "Screenplay" },
+                { "id": 209112, "media_type": "movie", "title": "Batman v Superman", "popularity": 85.0, "job": "Executive Producer" }
+            ]),
+        ),
+    ])
+    .await;
+    let _harness = Harness::install(Endpoints {
+        tmdb: Some(format!("{}/3", server.url())),
+        ..Default::default()
+    })
+    .await;
+
+    let owned: HashSet<(i64, String)> = [(11324, "movie".to_string())].into_iter().collect();
+    let suggestions = tmdb_api::fetch_credit_suggestions(
+        "test-key",
+        &LanguagePrefs::default(),
+        27205,
+        "movie",
+        &[DICAPRIO, GORDON_LEVITT],
+        &[NOLAN],
+        &owned,
+    )
+    .await
+    .expect("suggestions");
+
+    let ranked: Vec<(i64, &str, &str)> = suggestions
+        .iter()
+        .map(|s| (s.tmdb_id, s.media_type.as_str(), s.match_reason.as_str()))
+        .collect();
+    assert_eq!(
+        ranked,
+        vec![
+            (49026, "movie", "1 Schauspieler + gleiche Regie"),
+            (157336, "movie", "Gleiche Regie + gleiches Drehbuch"),
+            (1422, "movie", "1 Schauspieler"),
+            (1404, "tv", "1 Schauspieler"),
+        ]
+    );
+    assert_eq!(suggestions[0].year, Some(2012));
+    assert_eq!(suggestions[3].title, "Hinterm Mond gleich links");
+    assert_eq!(suggestions[3].year, Some(1996));
+}
+
+#[tokio::test]
+async fn failing_person_is_skipped() {
+    let server = FakeServer::start(vec![credits(
+        "/3/person/24045/combined_credits",
+        json!([{ "id": 49026, "media_type": "movie", "title": "The Dark Knight Rises", "character": "Blake" }]),
+        json!([]),
+    )])
+    .await;
+    let _harness = Harness::install(Endpoints {
+        tmdb: Some(format!("{}/3", server.url())),
+        ..Default::default()
+    })
+    .await;
+
+    let suggestions = tmdb_api::fetch_credit_suggestions(
+        "test-key",
+        &LanguagePrefs::default(),
+        27205,
+        "movie",
+        &[DICAPRIO, GORDON_LEVITT],
+        &[],
+        &HashSet::new(),
+    )
+    .await
+    .expect("suggestions");
+
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions[0].tmdb_id, 49026);
+    assert_eq!(server.requests_to("/3/person/6193/combined_credits").len(), 1);
+}
+
+#[tokio::test]
+async fn similar_titles_merge_recommendations_and_similar() {
+    let server = FakeServer::start(vec![
+        Route::get("/3/tv/1399/recommendations").reply(Response::json(json!({ "results": [
+            { "id": 1402, "name": "The Walking Dead", "first_air_date": "2010-10-31", "vote_average": 8.1 },
+            { "id": 94997, "name": "House of the Dragon", "first_air_date": "2022-08-21", "poster_path": "/hotd.jpg", "vote_average": 8.4 }
+        ]}))),
+        Route::get("/3/tv/1399/similar").reply(Response::json(json!({ "results": [
+            { "id": 94997, "name": "House of the Dragon", "first_air_date": "2022-08-21" },
+            { "id": 71912, "name": "The Witcher", "first_air_date": "2019-12-20", "vote_average": 0 },
+            { "id": 1, "name": "" }
+        ]}))),
+    ])
+    .await;
+    let _harness = Harness::install(Endpoints {
+        tmdb: Some(format!("{}/3", server.url())),
+        ..Default::default()
+    })
+    .await;
+
+    let titles = tmdb_api::fetch_similar_titles("test-key", &LanguagePrefs::default(), 1399, "series")
+        .await
+        .expect("similar");
+
+    let ids: Vec<i64> = titles.iter().map(|t| t.tmdb_id).collect();
+    assert_eq!(ids, vec![94997, 1402, 71912]);
+    assert_eq!(titles[0].sources, vec!["recommendations", "similar"]);
+    assert_eq!(titles[0].media_type, "tv");
+    assert_eq!(titles[0].year, Some(2022));
+    assert_eq!(
+        titles[0].poster_url.as_deref(),
+        Some("https://image.tmdb.org/t/p/w342/hotd.jpg")
+    );
+    assert_eq!(titles[2].sources, vec!["similar"]);
+    assert_eq!(titles[2].rating, None);
+}
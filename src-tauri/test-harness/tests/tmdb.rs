@@ -0,0 +1,117 @@
+use app_lib::harness::endpoints::Endpoints;
+use app_lib::harness::language::LanguagePrefs;
+use app_lib::harness::metadata_provider::{MetadataProvider, SearchQuery};
+use app_lib::harness::tmdb::TmdbProvider;
+use test_harness::{FakeServer, Harness, Response, Route};
+
+const API_KEY: &str = "test-key";
+
+async fn setup(routes: Vec<Route>) -> (FakeServer, Harness, TmdbProvider) {
+    let server = FakeServer::start(routes).await;
+    let harness = Harness::install(Endpoints {
+        tmdb: Some(format!("{}/3", server.url())),
+        ..Default::default()
+    })
+    .await;
+    let provider = TmdbProvider::new(API_KEY.to_string(), LanguagePrefs::default());
+    (server, harness, provider)
+}
+
+#[tokio::test]
+async fn search_maps_results_and_sends_query() {
+    let (server, _harness, provider) = setup(vec![
+        Route::get("/3/search/movie").reply(Response::fixture("tmdb/search_movie_inception.json")),
+    ])
+    .await;
+
+    let results = provider
+        .search(&SearchQuery {
+            title: "Inception".into(),
+            year: Some(2010),
+            media_type: "movie".into(),
+        })
+        .await
+        .expect("search");
+
+    assert_eq!(results.len(), 2);
+    let first = &results[0];
+    assert_eq!(first.provider, "tmdb");
+    assert_eq!(first.provider_id, "27205");
+    assert_eq!(first.tmdb_id(), Some(27205));
+    assert_eq!(first.title, "Inception");
+    assert_eq!(first.release_date.as_deref(), Some("2010-07-15"));
+    assert_eq!(
+        first.poster_url.as_deref(),
+        Some("https://image.tmdb.org/t/p/w500/9gk7adHYeDvHkCSEqAvQNLV5Uge.jpg")
+    );
+    assert_eq!(first.primary_rating(), Some(8.369));
+    assert!(results[1].poster_url.is_none());
+
+    let requests = server.requests_to("/3/search/movie");
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].param("api_key"), Some(API_KEY));
+    assert_eq!(requests[0].param("query"), Some("Inception"));
+    assert_eq!(requests[0].param("year"), Some("2010"));
+    assert_eq!(requests[0].param("language"), Some("de-DE"));
+}
+
+#[tokio::test]
+async fn details_merge_detail_and_credits() {
+    let (server, _harness, provider) = setup(vec![
+        Route::get("/3/movie/27205").reply(Response::fixture("tmdb/movie_27205.json")),
+        Route::get("/3/movie/27205/credits").reply(Response::fixture("tmdb/movie_27205_credits.json")),
+    ])
+    .await;
+
+    let detail = provider
+        .fetch_details("27205", "movie")
+        .await
+        .expect("details")
+        .expect("found");
+
+    assert_eq!(detail.provider_id, "27205");
+    assert_eq!(detail.title, "Inception");
+    assert_eq!(detail.runtime, Some(148));
+    assert_eq!(detail.genres, vec!["Action", "Science Fiction", "Abenteuer"]);
+    // Leere Handlung auf Deutsch wird aus den Übersetzungen ergänzt.
+    assert!(detail.overview.starts_with("Der Spezialist Cobb"));
+    assert_eq!(detail.language.as_deref(), Some("de-DE"));
+    // Deutsches Poster vor englischem, textloser Backdrop zuerst.
+    assert_eq!(
+        detail.poster_url.as_deref(),
+        Some("https://image.tmdb.org/t/p/w500/xlaY2zyzMfkhk0HSC5VUwzoZPU1.jpg")
+    );
+    assert_eq!(
+        detail.backdrop_url.as_deref(),
+        Some("https://image.tmdb.org/t/p/w1280/8ZTVqvKDQ8emSGUEMjsS4yHAwrp.jpg")
+    );
+    assert_eq!(detail.external_ids.imdb_id.as_deref(), Some("tt1375666"));
+    assert_eq!(detail.external_ids.wikidata_id.as_deref(), Some("Q25188"));
+
+    let cast_crew = detail.cast_crew.expect("credits");
+    assert_eq!(cast_crew.cast[0].name, "Leonardo DiCaprio");
+    assert_eq!(cast_crew.cast[0].character.as_deref(), Some("Dom Cobb"));
+    let jobs: Vec<&str> = cast_crew.crew.iter().map(|c| c.job.as_str()).collect();
+    assert_eq!(jobs, vec!["Director", "Screenplay"]);
+
+    let request = &server.requests_to("/3/movie/27205")[0];
+    let append = request.param("append_to_response").unwrap_or_default();
+    assert!(append.contains("translations"));
+    assert!(append.contains("release_dates"));
+}
+
+#[tokio::test]
+async fn unknown_title_yields_none() {
+    let (_server, _harness, provider) = setup(Vec::new()).await;
+
+    let detail = provider.fetch_details("1", "movie").await.expect("details");
+    assert!(detail.is_none());
+}
+
+#[tokio::test]
+async fn non_numeric_id_is_rejected() {
+    let (server, _harness, provider) = setup(Vec::new()).await;
+
+    assert!(provider.fetch_details("tt1375666", "movie").await.is_err());
+    assert!(server.requests_to("/3/movie/tt1375666").is_empty());
+}
@@ -0,0 +1,74 @@
+use app_lib::harness::endpoints::Endpoints;
+use app_lib::harness::metadata_provider::{MetadataProvider, SearchQuery};
+use app_lib::harness::tvmaze::TvmazeProvider;
+use test_harness::{FakeServer, Harness, Response, Route};
+
+async fn setup(routes: Vec<Route>) -> (FakeServer, Harness, TvmazeProvider) {
+    let server = FakeServer::start(routes).await;
+    let harness = Harness::install(Endpoints {
+        tvmaze: Some(server.url()),
+        ..Default::default()
+    })
+    .await;
+    (server, harness, TvmazeProvider::new())
+}
+
+#[tokio::test]
+async fn search_filters_by_premiere_year() {
+    let (server, _harness, provider) = setup(vec![
+        Route::get("/search/shows").reply(Response::fixture("tvmaze/search_shows_lost.json")),
+    ])
+    .await;
+
+    let query = |year| SearchQuery {
+        title: "Lost".into(),
+        year,
+        media_type: "series".into(),
+    };
+    let results = provider.search(&query(Some(2004))).await.expect("search");
+    assert_eq!(results.len(), 1);
+    let lost = &results[0];
+    assert_eq!((lost.provider.as_str(), lost.provider_id.as_str()), ("tvmaze", "123"));
+    assert_eq!(lost.overview, "Lost follows the survivors of Oceanic Flight 815.");
+    assert_eq!(lost.runtime, Some(44));
+    assert_eq!(lost.external_ids.imdb_id.as_deref(), Some("tt0411008"));
+    assert_eq!(lost.external_ids.tvdb_id, Some(73739));
+    assert_eq!(lost.primary_rating(), Some(8.0));
+
+    assert_eq!(provider.search(&query(None)).await.expect("search").len(), 2);
+    assert_eq!(server.requests_to("/search/shows")[0].param("q"), Some("Lost"));
+
+    let movies = SearchQuery {
+        media_type: "movie".into(),
+        ..query(None)
+    };
+    assert!(provider.search(&movies).await.expect("movies").is_empty());
+    assert_eq!(server.requests_to("/search/shows").len(), 2);
+}
+
+#[tokio::test]
+async fn details_embed_cast() {
+    let (server, _harness, provider) = setup(vec![
+        Route::get("/shows/123").reply(Response::fixture("tvmaze/show_123.json")),
+    ])
+    .await;
+
+    let detail = provider
+        .fetch_details("123", "series")
+        .await
+        .expect("details")
+        .expect("found");
+
+    assert_eq!(detail.overview, "Lost follows the survivors of Oceanic Flight 815 & their island.");
+    assert_eq!(
+        detail.poster_url.as_deref(),
+        Some("https://static.tvmaze.com/uploads/images/original_untouched/0/1389.jpg")
+    );
+    let cast = detail.cast_crew.expect("cast").cast;
+    assert_eq!(cast[0].name, "Matthew Fox");
+    assert_eq!(cast[0].character.as_deref(), Some("Jack Shephard"));
+    assert!(cast[1].profile_url.is_none());
+    assert_eq!(server.requests_to("/shows/123")[0].param("embed"), Some("cast"));
+
+    assert!(provider.fetch_details("999", "series").await.expect("lookup").is_none());
+}
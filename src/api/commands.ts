@@ -2,16 +2,28 @@ import { invoke } from "@tauri-apps/api/core";
 import type {
   PathCheckResult,
   MediaItem,
+  MediaKind,
+  MediaKindId,
   LibraryItemWithMeta,
   RdStreamLink,
   DebridSearchResult,
   MediaMetadata,
   MetadataSearchResult,
+  ImageSet,
+  MetadataTranslation,
+  EpisodeMetadata,
+  ReviewQueueEntry,
   PersonDetails,
   MediaExtendedInfo,
   ActorMovieSuggestion,
   DiscoverList,
+  WatchProviderOption,
   StreamSearchResult,
+  SearchPage,
+  SearchResultType,
+  DiscoverQuery,
+  DiscoverQueryPage,
+  SavedDiscoverQuery,
   TmdbGenre,
   DeviceCodeResponse,
   UnrestrictedLink,
@@ -23,6 +35,17 @@ import type {
   MusicAlbum,
   MusicTrack,
   MusicPlaylist,
+  ImageCacheStats,
+  KidsModeStatus,
+  CollectionCompleteness,
+  LockableField,
+  MetadataRefreshSummary,
+  ExternalIdLookup,
+  WatchlistEntry,
+  PersonFilmography,
+  LibraryPerson,
+  PersonLibraryTitle,
+  SimilarTitle,
 } from "../types";
 
 export async function checkMediaPath(path: string): Promise<PathCheckResult> {
@@ -37,12 +60,25 @@ export async function scanMediaDirsProgressive(paths: string[]): Promise<void> {
   return invoke("scan_media_dirs_progressive", { paths });
 }
 
-export async function getLibrary(): Promise<MediaItem[]> {
-  return invoke("get_library");
+export async function getLibrary(mediaKind?: MediaKindId | null): Promise<MediaItem[]> {
+  return invoke("get_library", { mediaKind: mediaKind ?? null });
 }
 
-export async function getLibraryWithMetadata(): Promise<LibraryItemWithMeta[]> {
-  return invoke("get_library_with_metadata");
+export async function getLibraryWithMetadata(
+  mediaKind?: MediaKindId | null
+): Promise<LibraryItemWithMeta[]> {
+  return invoke("get_library_with_metadata", { mediaKind: mediaKind ?? null });
+}
+
+export async function getMediaKinds(): Promise<MediaKind[]> {
+  return invoke("get_media_kinds");
+}
+
+export async function setMediaKind(
+  id: string,
+  mediaKind: MediaKindId
+): Promise<MediaItem | null> {
+  return invoke("set_media_kind", { id, mediaKind });
 }
 
 export async function getMediaItem(id: string): Promise<MediaItem | null> {
@@ -119,10 +155,57 @@ export async function getMetadata(
   return invoke("get_metadata", { mediaItemId });
 }
 
+export async function getMetadataTranslations(
+  mediaItemId: string
+): Promise<MetadataTranslation[]> {
+  return invoke("get_metadata_translations", { mediaItemId });
+}
+
+export async function getEpisodeMetadata(
+  mediaItemId: string
+): Promise<EpisodeMetadata | null> {
+  return invoke("get_episode_metadata", { mediaItemId });
+}
+
+export async function fetchEpisodeMetadata(
+  mediaItemId: string
+): Promise<EpisodeMetadata | null> {
+  return invoke("fetch_episode_metadata", { mediaItemId });
+}
+
 export async function fetchMetadataBatch(): Promise<number> {
   return invoke("fetch_metadata_batch");
 }
 
+/** Runs a background refresh pass right away (missing first, then stale). */
+export async function refreshStaleMetadata(): Promise<MetadataRefreshSummary> {
+  return invoke("refresh_stale_metadata");
+}
+
+/** Replaces the list of fields that refreshes must not overwrite. */
+export async function setMetadataLockedFields(
+  mediaItemId: string,
+  fields: LockableField[]
+): Promise<MediaMetadata | null> {
+  return invoke("set_metadata_locked_fields", { mediaItemId, fields });
+}
+
+/**
+ * Resolves an IMDb link/id or TVDB id ("tvdb:81189") via TMDb.
+ * With mediaItemId the item is matched to the found title right away.
+ */
+export async function lookupExternalId(
+  input: string,
+  mediaItemId?: string
+): Promise<ExternalIdLookup> {
+  return invoke("lookup_external_id", { input, mediaItemId: mediaItemId ?? null });
+}
+
+/** Drops all cached TMDb responses. */
+export async function clearTmdbCache(): Promise<void> {
+  return invoke("clear_tmdb_cache");
+}
+
 export async function searchMetadata(
   title: string,
   year: number | null,
@@ -131,6 +214,36 @@ export async function searchMetadata(
   return invoke("search_metadata", { title, year, mediaType });
 }
 
+/** All posters and backdrops a provider has for a title (default provider: TMDb). */
+export async function getMetadataImages(
+  providerId: string,
+  mediaType: string,
+  provider?: string
+): Promise<ImageSet> {
+  return invoke("get_metadata_images", {
+    provider: provider ?? null,
+    providerId,
+    mediaType,
+  });
+}
+
+export async function getMatchReviewQueue(): Promise<ReviewQueueEntry[]> {
+  return invoke("get_match_review_queue");
+}
+
+/** Without providerId the current match is confirmed as-is. */
+export async function confirmMatch(
+  mediaItemId: string,
+  provider?: string,
+  providerId?: string
+): Promise<MediaMetadata | null> {
+  return invoke("confirm_match", {
+    mediaItemId,
+    provider: provider ?? null,
+    providerId: providerId ?? null,
+  });
+}
+
 export async function getPersonDetails(
   personId: number,
   knownFor?: string | null
@@ -159,25 +272,62 @@ export async function getActorMovieSuggestions(
   tmdbId: number,
   mediaType: string,
   actorIds: number[],
-  directorIds?: number[]
+  crewIds?: number[]
 ): Promise<ActorMovieSuggestion[]> {
   return invoke("get_actor_movie_suggestions", {
     tmdbId,
     mediaType,
     actorIds,
-    directorIds: directorIds ?? [],
+    crewIds: crewIds ?? [],
   });
 }
 
 export async function searchStreams(
   query: string,
   media_type: string,
-  genre_id?: number | null
+  genre_id?: number | null,
+  hideOwned?: boolean,
+  hideWatched?: boolean
 ): Promise<StreamSearchResult[]> {
   return invoke("search_streams", {
     query,
     media_type,
     genre_id: genre_id ?? null,
+    hideOwned: hideOwned ?? null,
+    hideWatched: hideWatched ?? null,
+  });
+}
+
+/** Paged search over movies, series, people and collections; no `types` means all. */
+export async function searchTmdb(
+  query: string,
+  types?: SearchResultType[],
+  page?: number,
+  hideOwned?: boolean,
+  hideWatched?: boolean
+): Promise<SearchPage> {
+  return invoke("search_tmdb", {
+    query,
+    types: types ?? null,
+    page: page ?? null,
+    hideOwned: hideOwned ?? null,
+    hideWatched: hideWatched ?? null,
+  });
+}
+
+export async function discoverQuery(
+  query: DiscoverQuery,
+  page?: number,
+  country?: string,
+  hideOwned?: boolean,
+  hideWatched?: boolean
+): Promise<DiscoverQueryPage> {
+  return invoke("discover_query", {
+    query,
+    page: page ?? null,
+    country: country ?? null,
+    hideOwned: hideOwned ?? null,
+    hideWatched: hideWatched ?? null,
   });
 }
 
@@ -185,21 +335,75 @@ export async function getTmdbGenres(): Promise<TmdbGenre[]> {
   return invoke("get_tmdb_genres");
 }
 
-export async function refreshDiscoverLists(country?: string): Promise<DiscoverList[]> {
-  return invoke("refresh_discover_lists", { country: country ?? null });
+export async function getWatchProviders(country?: string): Promise<WatchProviderOption[]> {
+  return invoke("get_watch_providers", { country: country ?? null });
 }
 
-export async function getDiscoverLists(): Promise<DiscoverList[]> {
-  return invoke("get_discover_lists");
+export async function refreshDiscoverLists(
+  country?: string,
+  hideOwned?: boolean,
+  hideWatched?: boolean
+): Promise<DiscoverList[]> {
+  return invoke("refresh_discover_lists", {
+    country: country ?? null,
+    hideOwned: hideOwned ?? null,
+    hideWatched: hideWatched ?? null,
+  });
+}
+
+export async function getDiscoverLists(
+  hideOwned?: boolean,
+  hideWatched?: boolean
+): Promise<DiscoverList[]> {
+  return invoke("get_discover_lists", {
+    hideOwned: hideOwned ?? null,
+    hideWatched: hideWatched ?? null,
+  });
+}
+
+/** Saves (or with `id` overwrites) a query; its list appears on the next discover refresh. */
+export async function saveDiscoverQuery(
+  name: string,
+  query: DiscoverQuery,
+  id?: string
+): Promise<string> {
+  return invoke("save_discover_query", { id: id ?? null, name, query });
+}
+
+export async function getDiscoverQueries(): Promise<SavedDiscoverQuery[]> {
+  return invoke("get_discover_queries");
+}
+
+export async function deleteDiscoverQuery(id: string): Promise<void> {
+  return invoke("delete_discover_query", { id });
 }
 
 export async function getAiRecommendationsList(
   preset: string,
-  forceRefresh?: boolean
+  forceRefresh?: boolean,
+  hideOwned?: boolean,
+  hideWatched?: boolean
 ): Promise<DiscoverList> {
   return invoke("get_ai_recommendations_list", {
     preset,
     force_refresh: forceRefresh ?? false,
+    hideOwned: hideOwned ?? null,
+    hideWatched: hideWatched ?? null,
+  });
+}
+
+/** TMDb recommendations and similar titles; `mediaType` may be "movie", "tv" or "series". */
+export async function getSimilarTitles(
+  tmdbId: number,
+  mediaType: string,
+  forceRefresh?: boolean,
+  checkStreams?: boolean
+): Promise<SimilarTitle[]> {
+  return invoke("get_similar_titles", {
+    tmdbId,
+    mediaType,
+    forceRefresh: forceRefresh ?? null,
+    checkStreams: checkStreams ?? null,
   });
 }
 
@@ -367,3 +571,124 @@ export async function setMusicTrackRating(
 ): Promise<void> {
   return invoke("set_music_track_rating", { trackId, rating });
 }
+
+// ── Image cache ──
+
+/** Local file path for an image, downloading it if needed. */
+export async function getCachedImage(url: string, size?: string): Promise<string> {
+  return invoke("get_cached_image", { url, size: size ?? null });
+}
+
+/** Only images that are already cached; never hits the network. */
+export async function getCachedImagePaths(urls: string[]): Promise<Record<string, string>> {
+  return invoke("get_cached_image_paths", { urls });
+}
+
+export async function cacheLibraryImages(): Promise<number> {
+  return invoke("cache_library_images");
+}
+
+export async function getImageCacheStats(): Promise<ImageCacheStats> {
+  return invoke("get_image_cache_stats");
+}
+
+export async function clearImageCache(): Promise<void> {
+  return invoke("clear_image_cache");
+}
+
+// ── Kids mode ──
+
+export async function getKidsModeStatus(): Promise<KidsModeStatus> {
+  return invoke("get_kids_mode_status");
+}
+
+/** Sets the PIN; `currentPin` is required once a PIN exists. */
+export async function setKidsPin(currentPin: string | null, newPin: string): Promise<void> {
+  return invoke("set_kids_pin", { currentPin, newPin });
+}
+
+export async function setKidsMode(
+  pin: string,
+  enabled: boolean,
+  maxCertification: string | null,
+  country: string | null
+): Promise<KidsModeStatus> {
+  return invoke("set_kids_mode", { pin, enabled, maxCertification, country });
+}
+
+// ── Collections ──
+
+/** Reloads franchise collections of owned movies; returns how many were fetched. */
+export async function refreshCollections(force?: boolean): Promise<number> {
+  return invoke("refresh_collections", { force: force ?? null });
+}
+
+/** Owned and missing movies per collection; `checkStreams` also searches streams for missing ones. */
+export async function getCollectionCompleteness(
+  checkStreams?: boolean
+): Promise<CollectionCompleteness[]> {
+  return invoke("get_collection_completeness", { checkStreams: checkStreams ?? null });
+}
+
+// ── Watchlist & watched ──
+
+export async function getWatchlist(): Promise<WatchlistEntry[]> {
+  return invoke("get_watchlist");
+}
+
+/** `mediaType` may be "movie", "tv" or "series". */
+export async function addToWatchlist(
+  tmdbId: number,
+  mediaType: string,
+  title: string,
+  year?: number | null,
+  posterUrl?: string | null
+): Promise<void> {
+  return invoke("add_to_watchlist", {
+    tmdbId,
+    mediaType,
+    title,
+    year: year ?? null,
+    posterUrl: posterUrl ?? null,
+  });
+}
+
+export async function removeFromWatchlist(tmdbId: number, mediaType: string): Promise<void> {
+  return invoke("remove_from_watchlist", { tmdbId, mediaType });
+}
+
+export async function setWatched(
+  tmdbId: number,
+  mediaType: string,
+  watched: boolean
+): Promise<void> {
+  return invoke("set_watched", { tmdbId, mediaType, watched });
+}
+
+// ── People ──
+
+/** Combined movie and TV credits of a TMDb person with library, watchlist and watched flags. */
+export async function getPersonFilmography(
+  personId: number,
+  ownedOnly?: boolean
+): Promise<PersonFilmography> {
+  return invoke("get_person_filmography", { personId, ownedOnly: ownedOnly ?? null });
+}
+
+/** Owned titles featuring a TMDb person, newest first. */
+export async function getPersonLibraryTitles(personId: number): Promise<PersonLibraryTitle[]> {
+  return invoke("get_person_library_titles", { personId });
+}
+
+/** People sharing the most owned titles with a person. */
+export async function getPersonCollaborators(
+  personId: number,
+  limit?: number
+): Promise<LibraryPerson[]> {
+  return invoke("get_person_collaborators", { personId, limit: limit ?? null });
+}
+
+/** Most frequent people in the library, optionally filtered by name. */
+export async function getLibraryPeople(query?: string, limit?: number): Promise<LibraryPerson[]> {
+  return invoke("get_library_people", { query: query ?? null, limit: limit ?? null });
+}
@@ -24,6 +24,13 @@ export default function DiscoverCard({ item }: DiscoverCardProps) {
             {item.media_type === "movie" ? "🎬" : "📺"}
           </div>
         )}
+        {(item.media_item_id || item.watched || item.on_watchlist) && (
+          <div className="discover-card-flags">
+            {item.media_item_id && <span className="discover-card-flag">In Bibliothek</span>}
+            {item.watched && <span className="discover-card-flag">Gesehen</span>}
+            {item.on_watchlist && <span className="discover-card-flag">Watchlist</span>}
+          </div>
+        )}
       </div>
       <div className="discover-card-info">
         <span className="discover-card-title">{item.title}</span>
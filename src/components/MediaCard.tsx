@@ -1,5 +1,6 @@
 import type { MediaItem, MediaMetadata } from "../types";
 import { useNavigate } from "react-router-dom";
+import { useCachedImage } from "../utils/imageCache";
 
 interface MediaCardProps {
   item: MediaItem;
@@ -9,7 +10,7 @@ interface MediaCardProps {
 
 export default function MediaCard({ item, metadata, versionCount = 1 }: MediaCardProps) {
   const navigate = useNavigate();
-  const posterUrl = metadata?.poster_url;
+  const posterUrl = useCachedImage(metadata?.poster_url, "w342");
   const displayTitle = metadata?.title || item.title;
   const year = metadata?.release_date?.split("-")[0];
 
@@ -39,7 +39,7 @@ export function LibraryProvider({ children }: { children: React.ReactNode }) {
       const item = ev.payload;
       setItems((prev) => {
         const idx = prev.findIndex((x) => x.item.file_path === item.file_path);
-        const newEntry: LibraryItemWithMeta = { item, metadata: null };
+        const newEntry: LibraryItemWithMeta = { item, metadata: null, episode: null };
         if (idx >= 0) {
           const next = [...prev];
           next[idx] = newEntry;
@@ -59,7 +59,7 @@ export function LibraryProvider({ children }: { children: React.ReactNode }) {
         const idx = prev.findIndex((x) => x.item.id === item.id);
         if (idx >= 0) {
           const next = [...prev];
-          next[idx] = { item: next[idx].item, metadata };
+          next[idx] = { ...next[idx], metadata };
           return next;
         }
         return prev;
@@ -76,6 +76,14 @@ export function LibraryProvider({ children }: { children: React.ReactNode }) {
     return () => { unlisten?.(); };
   }, [loadLibrary]);
 
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    listen("kids-mode-changed", () => {
+      loadLibrary();
+    }).then((fn) => { unlisten = fn; });
+    return () => { unlisten?.(); };
+  }, [loadLibrary]);
+
   return (
     <LibraryContext.Provider value={{ items, loading, reload: loadLibrary }}>
       {children}
@@ -17,10 +17,27 @@ export interface MediaItem {
   media_type: "movie" | "series";
   file_size: number | null;
   series_name?: string | null;
+  media_kind?: MediaKindId | null;
   created_at: string;
   updated_at: string;
 }
 
+export type MediaKindId =
+  | "movie"
+  | "series"
+  | "documentary"
+  | "anime"
+  | "concert"
+  | "standup"
+  | "home_video";
+
+export interface MediaKind {
+  id: MediaKindId;
+  label: string;
+  metadata_lookup: boolean;
+  sort_order: number;
+}
+
 export interface MediaMetadata {
   id: string;
   media_item_id: string;
@@ -35,10 +52,61 @@ export interface MediaMetadata {
   genres: string | null;
   cast_crew: string | null;
   raw_response: string | null;
+  /** JSON-encoded RatingSource[] */
+  ratings: string | null;
+  /** JSON-encoded Record<field, provider> */
+  field_sources: string | null;
+  match_confidence: number | null;
+  match_status: MatchStatus | null;
+  /** BCP-47 tag of title/overview, e.g. "de-DE" */
+  language: string | null;
+  /** JSON-encoded LockableField[]; locked fields survive refreshes */
+  locked_fields: string | null;
+  imdb_id: string | null;
+  tvdb_id: number | null;
+  wikidata_id: string | null;
   created_at: string;
   updated_at: string;
 }
 
+export type MatchStatus = "auto" | "needs_review" | "confirmed";
+
+export interface MatchCandidate {
+  media_item_id: string;
+  provider: string;
+  /** In the provider's id space, e.g. "27205" (TMDb) or "tt1375666" (OMDb). */
+  provider_id: string;
+  title: string;
+  overview: string | null;
+  poster_url: string | null;
+  release_date: string | null;
+  media_type: string;
+  /** 0–1 */
+  score: number;
+  rank: number;
+}
+
+export interface ReviewQueueEntry {
+  item: MediaItem;
+  metadata: MediaMetadata | null;
+  candidates: MatchCandidate[];
+}
+
+export interface MetadataTranslation {
+  language: string;
+  title: string | null;
+  overview: string | null;
+  tagline: string | null;
+}
+
+export interface RatingSource {
+  source: string;
+  /** normalized to 0–10 */
+  value: number;
+  display: string;
+  votes: number | null;
+}
+
 export interface CastMember {
   id?: number | null;
   name: string;
@@ -62,9 +130,41 @@ export interface PersonDetails {
   children: string | null;
 }
 
+export interface MediaVideo {
+  site: string;
+  key: string;
+  name: string | null;
+  /** "Trailer", "Teaser", "Clip", ... */
+  video_type: string | null;
+  language: string | null;
+  official: boolean;
+  published_at: string | null;
+  url: string;
+}
+
+export interface MediaKeyword {
+  id: number;
+  name: string;
+}
+
+export interface Certification {
+  /** ISO 3166-1, e.g. "DE" */
+  country: string;
+  certification: string;
+  release_date: string | null;
+  /** Display text, e.g. "FSK 12" */
+  label: string;
+}
+
 export interface MediaExtendedInfo {
   tagline: string | null;
   trivia_facts: string[];
+  /** Certification for the preferred country */
+  certification: Certification | null;
+  /** Trailers first */
+  videos: MediaVideo[];
+  keywords: MediaKeyword[];
+  certifications: Certification[];
 }
 
 export interface ActorMovieSuggestion {
@@ -77,6 +177,7 @@ export interface ActorMovieSuggestion {
 }
 
 export interface CrewMember {
+  id?: number | null;
   name: string;
   job: string;
   department: string;
@@ -97,6 +198,19 @@ export interface MetadataSearchResult {
   rating: number | null;
 }
 
+export interface ProviderImage {
+  url: string;
+  language: string | null;
+  width: number | null;
+  height: number | null;
+}
+
+/** Best-ranked images first. */
+export interface ImageSet {
+  posters: ProviderImage[];
+  backdrops: ProviderImage[];
+}
+
 export interface RdUserInfo {
   username: string;
   email: string;
@@ -165,6 +279,73 @@ export interface StreamSearchResult {
   genre_ids: number[];
 }
 
+export type SearchResultType = "movie" | "tv" | "person" | "collection";
+
+export interface SearchResult {
+  /** Person ids work with getPersonDetails / getPersonFilmography. */
+  tmdb_id: number;
+  media_type: SearchResultType;
+  title: string;
+  year: number | null;
+  rating: number | null;
+  /** Poster, or the profile photo for people. */
+  image_url: string | null;
+  overview: string;
+  known_for_department: string | null;
+  known_for: string[];
+}
+
+export interface SearchPage {
+  /** Query without the year that was parsed out of it. */
+  query: string;
+  year: number | null;
+  page: number;
+  total_pages: number;
+  total_results: number;
+  /** TMDb totals per type across all pages. */
+  counts: Record<SearchResultType, number>;
+  results: SearchResult[];
+  /** Types whose request failed; results from the other types are still included. */
+  failed_types: SearchResultType[];
+}
+
+/** Filters for TMDb /discover; omitted fields are not applied. */
+export interface DiscoverQuery {
+  /** "movie" or "tv" ("series" counts as "tv"). */
+  media_type?: string;
+  genre_ids?: number[];
+  /** Require all genres instead of any of them. */
+  match_all_genres?: boolean;
+  year_from?: number | null;
+  year_to?: number | null;
+  min_rating?: number | null;
+  min_votes?: number | null;
+  /** Minutes. */
+  runtime_min?: number | null;
+  runtime_max?: number | null;
+  /** ISO 639-1, e.g. "ko". */
+  original_language?: string | null;
+  /** TMDb provider ids, applied in the discover country. */
+  provider_ids?: number[];
+  monetization_types?: ("flatrate" | "free" | "ads" | "rent" | "buy")[];
+  /** e.g. "vote_average.desc"; defaults to "popularity.desc". */
+  sort_by?: string | null;
+}
+
+export interface DiscoverQueryPage {
+  page: number;
+  total_pages: number;
+  total_results: number;
+  results: StreamSearchResult[];
+}
+
+export interface SavedDiscoverQuery {
+  id: string;
+  name: string;
+  query: DiscoverQuery;
+  created_at: string;
+}
+
 export interface TmdbGenre {
   id: number;
   name: string;
@@ -184,16 +365,42 @@ export interface DiscoverItem {
   overview: string | null;
   provider: string | null;
   sort_order: number;
+  /** Library item with the same TMDb id, if we have the title. */
+  media_item_id: string | null;
+  on_watchlist: boolean;
+  watched: boolean;
 }
 
 export interface DiscoverList {
   id: string;
-  list_type: "imdb_top" | "streaming_popular";
+  list_type:
+    | "imdb_top"
+    | "streaming_popular"
+    | "trending_day"
+    | "trending_week"
+    | "now_playing"
+    | "upcoming"
+    | "airing_week"
+    | "top_rated_tv"
+    | "custom"
+    | "ai_recommendations";
   provider: string | null;
+  provider_logo: string | null;
   country: string | null;
   title: string;
   created_at: string;
   items: DiscoverItem[];
+  /** Error from the last refresh; the list then keeps its previous items. */
+  last_error: string | null;
+  /** True when the last refresh failed and `items` are from an earlier one. */
+  stale: boolean;
+}
+
+export interface WatchProviderOption {
+  tmdb_provider_id: number;
+  name: string;
+  logo_url: string | null;
+  selected: boolean;
 }
 
 export interface ExternalPlayer {
@@ -209,7 +416,27 @@ export interface Settings {
 
 export interface LibraryItemWithMeta {
   item: MediaItem;
+  /** For series: show-level metadata */
   metadata: MediaMetadata | null;
+  episode: EpisodeMetadata | null;
+}
+
+export interface EpisodeMetadata {
+  media_item_id: string;
+  tmdb_show_id: number;
+  season_number: number;
+  episode_number: number;
+  tmdb_episode_id: number | null;
+  name: string | null;
+  overview: string | null;
+  air_date: string | null;
+  still_url: string | null;
+  runtime: number | null;
+  rating: number | null;
+  /** JSON-encoded CastMember[] */
+  guest_stars: string | null;
+  created_at: string;
+  updated_at: string;
 }
 
 export interface MovieGroup {
@@ -262,3 +489,167 @@ export interface MusicPlaylist {
 export interface MusicTrackWithAlbum extends MusicTrack {
   album?: MusicAlbum;
 }
+
+export interface ImageCacheStats {
+  files: number;
+  bytes: number;
+  max_bytes: number;
+}
+
+export type LockableField =
+  | "title"
+  | "overview"
+  | "poster_url"
+  | "backdrop_url"
+  | "release_date"
+  | "rating"
+  | "runtime"
+  | "genres"
+  | "cast_crew";
+
+/** Kids mode; the `kids_*` settings can only be changed through the PIN-guarded commands. */
+export interface KidsModeStatus {
+  enabled: boolean;
+  /** ISO 3166-1 country whose certifications are used. */
+  country: string;
+  /** Highest allowed certification in the country's format, e.g. "12" or "PG". */
+  max_certification: string | null;
+  has_pin: boolean;
+}
+
+export interface MetadataRefreshSummary {
+  added: number;
+  refreshed: number;
+  failed: number;
+}
+
+export interface ExternalFindResult {
+  tmdb_id: number;
+  media_type: "movie" | "series";
+  title: string;
+  overview: string | null;
+  poster_url: string | null;
+  release_date: string | null;
+  rating: number | null;
+}
+
+export interface ExternalIdLookup {
+  imdb_id: string | null;
+  tvdb_id: number | null;
+  matches: ExternalFindResult[];
+  /** Library items already linked to this id */
+  library_item_ids: string[];
+  /** Set when a mediaItemId was passed and the item was matched */
+  metadata: MediaMetadata | null;
+}
+
+export interface CollectionMovie {
+  tmdb_id: number;
+  title: string;
+  year: number | null;
+  release_date: string | null;
+  poster_url: string | null;
+  /** Library entry when the movie is owned. */
+  media_item_id: string | null;
+  /** Only checked for missing, released movies when requested; null = unknown. */
+  has_streams: boolean | null;
+}
+
+/** A TMDb franchise collection with at least one owned movie. */
+export interface CollectionCompleteness {
+  id: number;
+  name: string;
+  overview: string | null;
+  poster_url: string | null;
+  backdrop_url: string | null;
+  owned: CollectionMovie[];
+  missing: CollectionMovie[];
+  /** Parts without a release yet; not counted as missing. */
+  upcoming: CollectionMovie[];
+}
+
+/** TMDb media type as used by discover lists: series are "tv". */
+export type TmdbMediaType = "movie" | "tv";
+
+export interface WatchlistEntry {
+  tmdb_id: number;
+  media_type: TmdbMediaType;
+  title: string;
+  year: number | null;
+  poster_url: string | null;
+  added_at: string | null;
+}
+
+export interface FilmographyCredit {
+  tmdb_id: number;
+  media_type: TmdbMediaType;
+  title: string;
+  year: number | null;
+  release_date: string | null;
+  poster_url: string | null;
+  /** Characters or jobs within the department, e.g. ["Director", "Screenplay"]. */
+  roles: string[];
+  episode_count: number | null;
+  /** Library entry when the title is owned (an episode for series). */
+  media_item_id: string | null;
+  on_watchlist: boolean;
+  watched: boolean;
+}
+
+export interface FilmographyDepartment {
+  /** "Acting" for roles, otherwise the TMDb crew department. */
+  department: string;
+  /** Newest first; announced titles without a date at the top. */
+  credits: FilmographyCredit[];
+}
+
+export interface PersonFilmography {
+  person_id: number;
+  /** Acting first, then by number of credits. */
+  departments: FilmographyDepartment[];
+  /** Distinct titles before the owned-only filter. */
+  total_titles: number;
+  owned_titles: number;
+}
+
+/** A person from the library's cast and crew index. */
+export interface LibraryPerson {
+  id: number;
+  name: string;
+  profile_url: string | null;
+  /** Owned titles with this person; for collaborators the shared titles. */
+  title_count: number;
+}
+
+/** An owned title featuring a person; episodes of a series are combined. */
+export interface PersonLibraryTitle {
+  /** For series one of the episodes. */
+  media_item_id: string;
+  tmdb_id: number | null;
+  media_type: "movie" | "series";
+  title: string;
+  year: number | null;
+  poster_url: string | null;
+  characters: string[];
+  /** Crew jobs, e.g. "Director". */
+  jobs: string[];
+}
+
+/** A TMDb recommendation or similar title, flagged against the library. */
+export interface SimilarTitle {
+  tmdb_id: number;
+  media_type: TmdbMediaType;
+  title: string;
+  year: number | null;
+  rating: number | null;
+  poster_url: string | null;
+  overview: string | null;
+  /** "recommendations" and/or "similar". */
+  sources: string[];
+  /** Library entry when the title is owned. */
+  media_item_id: string | null;
+  on_watchlist: boolean;
+  watched: boolean;
+  /** Only checked for missing movies when requested; null = unknown. */
+  has_streams: boolean | null;
+}
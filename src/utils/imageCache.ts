@@ -0,0 +1,42 @@
+import { useEffect, useState } from "react";
+import { convertFileSrc } from "@tauri-apps/api/core";
+import { getCachedImage } from "../api/commands";
+
+const resolved = new Map<string, string>();
+
+/**
+ * Serves a remote image from the local cache. Falls back to the remote URL
+ * while the image is being cached or if caching fails.
+ */
+export function useCachedImage(url: string | null | undefined, size?: string): string | undefined {
+  const key = url ? `${size ?? ""}|${url}` : "";
+  const [src, setSrc] = useState<string | undefined>(() =>
+    url ? resolved.get(key) ?? url : undefined
+  );
+
+  useEffect(() => {
+    if (!url) {
+      setSrc(undefined);
+      return;
+    }
+    const cached = resolved.get(key);
+    if (cached) {
+      setSrc(cached);
+      return;
+    }
+    setSrc(url);
+    let cancelled = false;
+    getCachedImage(url, size)
+      .then((path) => {
+        const local = convertFileSrc(path);
+        resolved.set(key, local);
+        if (!cancelled) setSrc(local);
+      })
+      .catch(() => {});
+    return () => {
+      cancelled = true;
+    };
+  }, [key]);
+
+  return src;
+}
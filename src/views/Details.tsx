@@ -14,6 +14,7 @@ import {
 import FormatBoldText from "../components/FormatBoldText";
 import { useLibrary } from "../context/LibraryContext";
 import { buildMovieGroups } from "../utils/libraryGrouping";
+import { useCachedImage } from "../utils/imageCache";
 import type {
   MediaItem,
   MediaMetadata,
@@ -25,6 +26,9 @@ import type {
   ActorMovieSuggestion,
 } from "../types";
 
+/** Crew roles whose other work feeds the "Dazu passend" suggestions. */
+const SUGGESTION_CREW_JOBS = ["Director", "Screenplay", "Writer", "Creator"];
+
 export default function Details() {
   const { id } = useParams<{ id: string }>();
   const navigate = useNavigate();
@@ -112,11 +116,18 @@ export default function Details() {
       .filter((m): m is CastMember & { id: number } => typeof m.id === "number")
       .map((m) => m.id)
       .slice(0, 5);
+    const crewIds = [
+      ...new Set(
+        (castCrew.crew ?? [])
+          .filter((m) => SUGGESTION_CREW_JOBS.includes(m.job) && typeof m.id === "number")
+          .map((m) => m.id as number)
+      ),
+    ].slice(0, 3);
     if (ids.length === 0) return;
-    getActorMovieSuggestions(metadata.tmdb_id, item.media_type, ids, [])
+    getActorMovieSuggestions(metadata.tmdb_id, item.media_type, ids, crewIds)
       .then(setActorSuggestions)
       .catch(() => setActorSuggestions([]));
-  }, [metadata?.tmdb_id, castCrew?.cast, item]);
+  }, [metadata?.tmdb_id, castCrew?.cast, castCrew?.crew, item]);
 
   useEffect(() => {
     if (!metadata?.tmdb_id || !item) return;
@@ -234,12 +245,13 @@ export default function Details() {
     return movieVariants.find((entry) => entry.item.id === selectedVariantId) ?? movieVariants[0];
   }, [movieVariants, selectedVariantId]);
 
+  const backdropUrl = useCachedImage(metadata?.backdrop_url, "w1280");
+  const posterUrl = useCachedImage(metadata?.poster_url, "w500");
+
   if (loading) return <div className="loading-state">Lade Details...</div>;
   if (!item) return <div className="empty-state">Medium nicht gefunden.</div>;
 
   const displayTitle = metadata?.title || item.title;
-  const backdropUrl = metadata?.backdrop_url;
-  const posterUrl = metadata?.poster_url;
 
   function formatRuntime(minutes: number): string {
     const h = Math.floor(minutes / 60);
@@ -383,9 +395,10 @@ export default function Details() {
                 <div className="suggestions-grid">
                   {actorSuggestions.slice(0, 8).map((s) => (
                     <button
-                      key={s.tmdb_id}
+                      key={`${s.media_type}-${s.tmdb_id}`}
                       type="button"
                       className="suggestion-card"
+                      title={s.match_reason}
                       onClick={() =>
                         navigate(`/discover-detail/${s.tmdb_id}/${s.media_type}`)
                       }
@@ -592,11 +605,35 @@ export default function Details() {
               ×
             </button>
             <h2>Weitere Informationen & Trivia</h2>
+            {extendedOverlay.certification && (
+              <span className="overlay-certification">
+                {extendedOverlay.certification.label}
+              </span>
+            )}
             {extendedOverlay.tagline && (
               <p className="overlay-tagline">
                 <FormatBoldText text={extendedOverlay.tagline} />
               </p>
             )}
+            {extendedOverlay.videos.length > 0 && (
+              <div className="overlay-videos">
+                <h4>Trailer & Videos</h4>
+                <ul>
+                  {extendedOverlay.videos.slice(0, 5).map((v) => (
+                    <li key={`${v.site}-${v.key}`}>
+                      <a href={v.url} target="_blank" rel="noopener noreferrer">
+                        {v.name || v.video_type || "Video"}
+                      </a>
+                    </li>
+                  ))}
+                </ul>
+              </div>
+            )}
+            {extendedOverlay.keywords.length > 0 && (
+              <p className="overlay-keywords">
+                {extendedOverlay.keywords.map((k) => k.name).join(" · ")}
+              </p>
+            )}
             {extendedOverlay.trivia_facts.length > 0 && (
               <div className="overlay-trivia">
                 <h4>Trivia</h4>
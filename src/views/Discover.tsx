@@ -475,6 +475,11 @@ function DiscoverSection({
         <h2>
           {sectionIcon}
           {list.title}
+          {list.stale && (
+            <span className="discover-stale" title={list.last_error ?? undefined}>
+              Veraltet
+            </span>
+          )}
           <span className="discover-country-picker">
             <button
               type="button"
@@ -549,6 +554,9 @@ function getSectionIcon(list: DiscoverList): React.ReactElement | null {
   if (list.list_type === "imdb_top") {
     src = PROVIDER_LOGOS.imdb;
     alt = "IMDb";
+  } else if (list.provider_logo) {
+    src = list.provider_logo;
+    alt = list.title;
   } else if (list.provider && PROVIDER_LOGOS[list.provider]) {
     src = PROVIDER_LOGOS[list.provider];
     alt = list.provider;
@@ -22,6 +22,9 @@ import type {
   ActorMovieSuggestion,
 } from "../types";
 
+/** Crew roles whose other work feeds the "Dazu passend" suggestions. */
+const SUGGESTION_CREW_JOBS = ["Director", "Screenplay", "Writer", "Creator"];
+
 export default function DiscoverDetail() {
   const { tmdbId, mediaType } = useParams<{ tmdbId: string; mediaType: string }>();
   const navigate = useNavigate();
@@ -129,11 +132,18 @@ export default function DiscoverDetail() {
       .filter((m): m is CastMember & { id: number } => typeof m.id === "number")
       .map((m) => m.id)
       .slice(0, 5);
+    const crewIds = [
+      ...new Set(
+        (castCrew.crew ?? [])
+          .filter((m) => SUGGESTION_CREW_JOBS.includes(m.job) && typeof m.id === "number")
+          .map((m) => m.id as number)
+      ),
+    ].slice(0, 3);
     if (ids.length === 0) return;
-    getActorMovieSuggestions(metadata.tmdb_id, mediaTypeForApi, ids, [])
+    getActorMovieSuggestions(metadata.tmdb_id, mediaTypeForApi, ids, crewIds)
       .then(setActorSuggestions)
       .catch(() => setActorSuggestions([]));
-  }, [metadata?.tmdb_id, castCrew?.cast, mediaTypeForApi]);
+  }, [metadata?.tmdb_id, castCrew?.cast, castCrew?.crew, mediaTypeForApi]);
 
   useEffect(() => {
     if (!metadata?.tmdb_id) return;
@@ -335,9 +345,10 @@ export default function DiscoverDetail() {
                 <div className="suggestions-grid">
                   {actorSuggestions.slice(0, 8).map((s) => (
                     <button
-                      key={s.tmdb_id}
+                      key={`${s.media_type}-${s.tmdb_id}`}
                       type="button"
                       className="suggestion-card"
+                      title={s.match_reason}
                       onClick={() =>
                         navigate(`/discover-detail/${s.tmdb_id}/${s.media_type}`)
                       }
@@ -526,11 +537,35 @@ export default function DiscoverDetail() {
               ×
             </button>
             <h2>Weitere Informationen & Trivia</h2>
+            {extendedOverlay.certification && (
+              <span className="overlay-certification">
+                {extendedOverlay.certification.label}
+              </span>
+            )}
             {extendedOverlay.tagline && (
               <p className="overlay-tagline">
                 <FormatBoldText text={extendedOverlay.tagline} />
               </p>
             )}
+            {extendedOverlay.videos.length > 0 && (
+              <div className="overlay-videos">
+                <h4>Trailer & Videos</h4>
+                <ul>
+                  {extendedOverlay.videos.slice(0, 5).map((v) => (
+                    <li key={`${v.site}-${v.key}`}>
+                      <a href={v.url} target="_blank" rel="noopener noreferrer">
+                        {v.name || v.video_type || "Video"}
+                      </a>
+                    </li>
+                  ))}
+                </ul>
+              </div>
+            )}
+            {extendedOverlay.keywords.length > 0 && (
+              <p className="overlay-keywords">
+                {extendedOverlay.keywords.map((k) => k.name).join(" · ")}
+              </p>
+            )}
             {extendedOverlay.trivia_facts.length > 0 && (
               <div className="overlay-trivia">
                 <h4>Trivia</h4>
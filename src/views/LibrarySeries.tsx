@@ -79,8 +79,9 @@ export default function LibrarySeries() {
           <section key={seasonNum} className="series-season">
             <h2>Staffel {seasonNum}</h2>
             <div className="episode-grid">
-              {bySeason[seasonNum].map(({ item, metadata }) => {
+              {bySeason[seasonNum].map(({ item, metadata, episode }) => {
                 const info = parseEpisodeInfo(item.title);
+                const imageUrl = episode?.still_url ?? metadata?.poster_url;
                 return (
                   <div
                     key={item.id}
@@ -88,8 +89,8 @@ export default function LibrarySeries() {
                     onClick={() => navigate(`/details/${item.id}`)}
                   >
                     <div className="episode-poster">
-                      {metadata?.poster_url ? (
-                        <img src={metadata.poster_url} alt={item.title} />
+                      {imageUrl ? (
+                        <img src={imageUrl} alt={item.title} />
                       ) : (
                         <div className="episode-placeholder">
                           {info?.display ?? "?"}
@@ -101,7 +102,7 @@ export default function LibrarySeries() {
                         {info?.display ?? item.title}
                       </span>
                       <span className="episode-title">
-                        {metadata?.title || item.title}
+                        {episode?.name || metadata?.title || item.title}
                       </span>
                     </div>
                   </div>
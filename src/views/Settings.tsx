@@ -16,6 +16,10 @@ import {
   rdPollCredentials,
   rdSaveApiKey,
   rdGetStatus,
+  getKidsModeStatus,
+  setKidsPin,
+  setKidsMode,
+  getWatchProviders,
 } from "../api/commands";
 import { useScan } from "../context/ScanContext";
 import type {
@@ -24,6 +28,8 @@ import type {
   RdStatusInfo,
   ApiConfigurationStatus,
   ApiServiceStatus,
+  KidsModeStatus,
+  WatchProviderOption,
 } from "../types";
 
 function ApiStatusLine({ label, status }: { label: string; status: ApiServiceStatus }) {
@@ -95,6 +101,15 @@ export default function SettingsView() {
     server_url: string | null;
   } | null>(null);
   const [checkingQc, setCheckingQc] = useState(false);
+  const [kidsStatus, setKidsStatus] = useState<KidsModeStatus | null>(null);
+  const [kidsPin, setKidsPinInput] = useState("");
+  const [kidsNewPin, setKidsNewPin] = useState("");
+  const [kidsMaxCert, setKidsMaxCert] = useState("");
+  const [kidsCountry, setKidsCountry] = useState("");
+  const [kidsMessage, setKidsMessage] = useState<string | null>(null);
+  const [watchProviders, setWatchProviders] = useState<WatchProviderOption[] | null>(null);
+  const [loadingProviders, setLoadingProviders] = useState(false);
+  const [providersError, setProvidersError] = useState<string | null>(null);
   const [diagnoseResult, setDiagnoseResult] = useState<{
     volumes: string[];
     path_checked: string;
@@ -116,12 +131,18 @@ export default function SettingsView() {
   const load = useCallback(async () => {
     try {
       setLoading(true);
-      const [s, rdStatusRes] = await Promise.all([
+      const [s, rdStatusRes, kids] = await Promise.all([
         loadSettings(),
         rdGetStatus().catch(() => null),
+        getKidsModeStatus().catch(() => null),
       ]);
       setSettings(s);
       setRdStatus(rdStatusRes);
+      setKidsStatus(kids);
+      if (kids) {
+        setKidsMaxCert(kids.max_certification ?? "");
+        setKidsCountry(kids.country);
+      }
       setApiStatus(buildUncheckedApiStatus(s));
       const paths = parsePaths(s["media_paths"]);
       if (paths.length > 0 && !pathInput) setPathInput(paths[0]);
@@ -176,6 +197,35 @@ export default function SettingsView() {
     }
   }
 
+  async function handleLoadProviders() {
+    try {
+      setLoadingProviders(true);
+      setProvidersError(null);
+      setWatchProviders(await getWatchProviders(settings["discover_country"]));
+    } catch (err) {
+      setProvidersError(String(err));
+    } finally {
+      setLoadingProviders(false);
+    }
+  }
+
+  function selectedProviderIds(): number[] {
+    const stored = settings["discover_providers"];
+    if (stored !== undefined) {
+      return stored
+        .split(",")
+        .map((id) => Number(id.trim()))
+        .filter((id) => Number.isInteger(id) && id > 0);
+    }
+    return (watchProviders ?? []).filter((p) => p.selected).map((p) => p.tmdb_provider_id);
+  }
+
+  function toggleProvider(id: number) {
+    const current = selectedProviderIds();
+    const next = current.includes(id) ? current.filter((p) => p !== id) : [...current, id];
+    setSettings({ ...settings, discover_providers: next.join(",") });
+  }
+
   async function handleAddPath() {
     const selected = await open({
       directory: true,
@@ -386,6 +436,34 @@ export default function SettingsView() {
     }
   }
 
+  async function handleSetKidsPin() {
+    try {
+      await setKidsPin(kidsStatus?.has_pin ? kidsPin : null, kidsNewPin);
+      setKidsStatus((prev) => (prev ? { ...prev, has_pin: true } : prev));
+      setKidsNewPin("");
+      setKidsPinInput("");
+      setKidsMessage("PIN gespeichert.");
+    } catch (err) {
+      setKidsMessage(String(err));
+    }
+  }
+
+  async function handleSetKidsMode(enabled: boolean) {
+    try {
+      const status = await setKidsMode(
+        kidsPin,
+        enabled,
+        kidsMaxCert.trim() || null,
+        kidsCountry.trim() || null
+      );
+      setKidsStatus(status);
+      setKidsPinInput("");
+      setKidsMessage(enabled ? "Kindermodus aktiv." : "Kindermodus aus.");
+    } catch (err) {
+      setKidsMessage(String(err));
+    }
+  }
+
   async function handleScanMusic() {
     const paths = getMusicPaths();
     if (paths.length === 0) return;
@@ -871,6 +949,129 @@ export default function SettingsView() {
             maxLength={2}
           />
         </div>
+        <p className="settings-hint">
+          Streaming-Anbieter, für die Discover eigene Listen erstellt. Die
+          Auswahl gilt nach dem nächsten Aktualisieren der Discover-Listen.
+        </p>
+        <button
+          className="btn-secondary"
+          onClick={handleLoadProviders}
+          disabled={loadingProviders}
+        >
+          {loadingProviders ? "Lade Anbieter..." : "Anbieter für Region laden"}
+        </button>
+        {providersError && <p className="status-warn">{providersError}</p>}
+        {watchProviders && (
+          <div className="provider-options">
+            {watchProviders.map((p) => (
+              <label key={p.tmdb_provider_id} className="provider-option">
+                <input
+                  type="checkbox"
+                  checked={selectedProviderIds().includes(p.tmdb_provider_id)}
+                  onChange={() => toggleProvider(p.tmdb_provider_id)}
+                />
+                {p.logo_url && <img src={p.logo_url} alt="" width={20} height={20} />}
+                {p.name}
+              </label>
+            ))}
+          </div>
+        )}
+      </section>
+
+      <section className="settings-section">
+        <h2>Kindermodus</h2>
+        <p className="settings-hint">
+          Blendet Bibliothek, Discover, Empfehlungen und Streams oberhalb der
+          gewählten Altersfreigabe aus. Titel ohne bekannte Freigabe werden
+          ebenfalls ausgeblendet. Änderungen erfordern die PIN.
+        </p>
+        {kidsStatus && (
+          <span className={kidsStatus.enabled ? "rd-status-badge connected" : "rd-status-badge"}>
+            {kidsStatus.enabled
+              ? `Aktiv (bis ${kidsStatus.max_certification ?? "?"}, ${kidsStatus.country})`
+              : "Aus"}
+          </span>
+        )}
+        <div className="setting-row">
+          <label>PIN</label>
+          <input
+            type="password"
+            inputMode="numeric"
+            value={kidsPin}
+            onChange={(e) => setKidsPinInput(e.target.value)}
+            placeholder={kidsStatus?.has_pin ? "Aktuelle PIN" : "Noch keine PIN festgelegt"}
+            maxLength={8}
+          />
+        </div>
+        <div className="setting-row">
+          <label>Neue PIN (4–8 Ziffern)</label>
+          <input
+            type="password"
+            inputMode="numeric"
+            value={kidsNewPin}
+            onChange={(e) => setKidsNewPin(e.target.value)}
+            maxLength={8}
+          />
+        </div>
+        <div className="setting-row">
+          <label>Höchste Freigabe (z.B. 12, PG)</label>
+          <input
+            type="text"
+            value={kidsMaxCert}
+            onChange={(e) => setKidsMaxCert(e.target.value)}
+            placeholder="12"
+          />
+        </div>
+        <div className="setting-row">
+          <label>Land der Freigaben</label>
+          <input
+            type="text"
+            value={kidsCountry}
+            onChange={(e) => setKidsCountry(e.target.value)}
+            placeholder="DE"
+            maxLength={2}
+          />
+        </div>
+        <div className="action-row">
+          <button
+            type="button"
+            className="btn-secondary"
+            onClick={handleSetKidsPin}
+            disabled={!kidsNewPin}
+          >
+            PIN speichern
+          </button>
+          {kidsStatus?.enabled ? (
+            <>
+              <button
+                type="button"
+                className="btn-secondary"
+                onClick={() => handleSetKidsMode(true)}
+                disabled={!kidsPin}
+              >
+                Übernehmen
+              </button>
+              <button
+                type="button"
+                className="btn-primary"
+                onClick={() => handleSetKidsMode(false)}
+                disabled={!kidsPin}
+              >
+                Kindermodus beenden
+              </button>
+            </>
+          ) : (
+            <button
+              type="button"
+              className="btn-primary"
+              onClick={() => handleSetKidsMode(true)}
+              disabled={!kidsPin || !kidsStatus?.has_pin}
+            >
+              Kindermodus aktivieren
+            </button>
+          )}
+        </div>
+        {kidsMessage && <p className="settings-hint">{kidsMessage}</p>}
       </section>
 
       <section className="settings-section">